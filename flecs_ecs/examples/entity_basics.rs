@@ -38,7 +38,7 @@ fn main() {
 
     // Iterate all entities with position
     world.each_entity::<(&Position,)>(|entity, pos| {
-        println!("{} has {:?}", entity.name(), pos);
+        println!("{} has {:?}", entity.name().unwrap(), pos);
     });
 
     // Output
@@ -7,13 +7,13 @@ fn main() {
     world
         .component::<Position>()
         .on_add(|entity, pos| {
-            println!("added {:?} to {:?}", pos, entity.name());
+            println!("added {:?} to {:?}", pos, entity.name().unwrap());
         })
         .on_remove(|entity, pos| {
-            println!("removed {:?} from {:?}", pos, entity.name());
+            println!("removed {:?} from {:?}", pos, entity.name().unwrap());
         })
         .on_set(|entity, pos| {
-            println!("set {:?} for {:?}", pos, entity.name());
+            println!("set {:?} for {:?}", pos, entity.name().unwrap());
         });
 
     let entity = world.new_entity_named(c"Bob");
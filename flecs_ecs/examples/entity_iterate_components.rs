@@ -25,11 +25,15 @@ fn iterate_components(entity: Entity) {
             // If id is a pair, extract & print both parts of the pair
             let rel = id.first();
             let target = id.second();
-            print!("rel: {}, target: {}", rel.name(), target.name());
+            print!(
+                "rel: {}, target: {}",
+                rel.name().unwrap(),
+                target.name().unwrap()
+            );
         } else {
             // Id contains a regular entity. Strip role before printing.
             let comp = id.to_entity();
-            print!("entity: {}", comp.name());
+            print!("entity: {}", comp.name().unwrap());
         }
 
         println!();
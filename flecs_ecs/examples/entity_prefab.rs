@@ -57,7 +57,7 @@ fn main() {
     // Prefab components can be iterated just like regular components:
     world.each_entity::<(&ImpulseSpeed, &mut Position)>(|entity, (impulse_speed, position)| {
         position.x += impulse_speed.value;
-        println!("Entity {}: {:?}", entity.name(), position);
+        println!("Entity {}: {:?}", entity.name().unwrap(), position);
     });
 
     // Output:
@@ -19,7 +19,7 @@ fn main() {
             } else {
                 println!(
                     " - {}: {}: {}: with {:?}",
-                    it.event().name(),
+                    it.event().name().unwrap(),
                     it.event_id().to_str(),
                     it.entity(index),
                     pos
@@ -15,7 +15,7 @@ fn main() {
         .on_each_iter(|it, index, (_pos,)| {
             println!(
                 " - {}: {}: {}",
-                it.event().name(),
+                it.event().name().unwrap(),
                 it.event_id().to_str(),
                 it.entity(index)
             );
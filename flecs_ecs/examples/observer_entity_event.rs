@@ -34,7 +34,7 @@ fn main() {
     });
 
     widget.observe_entity::<Click>(|entity| {
-        println!("clicked on {:?}", entity.name());
+        println!("clicked on {:?}", entity.name().unwrap());
     });
 
     // Observe the Resize event on the widget entity.
@@ -48,7 +48,7 @@ fn main() {
     widget.observe_payload_entity(|entity, payload: &mut Resize| {
         println!(
             "{} resized to {{ {}, {} }}!",
-            entity.name(),
+            entity.name().unwrap(),
             payload.width,
             payload.height
         );
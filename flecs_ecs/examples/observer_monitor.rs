@@ -22,13 +22,13 @@ fn main() {
                 println!(
                     " - Enter: {}: {}",
                     it.event_id().to_str(),
-                    it.entity(index).name()
+                    it.entity(index).name().unwrap()
                 );
             } else if it.event() == flecs::OnRemove::ID {
                 println!(
                     " - Leave: {}: {}",
                     it.event_id().to_str(),
-                    it.entity(index).name()
+                    it.entity(index).name().unwrap()
                 );
             }
         });
@@ -24,9 +24,9 @@ fn main() {
         .on_each_iter(|it, index, (pos_self, pos_parent)| {
             println!(
                 " - {}: {}: {}: self: {{ {}, {} }}, parent: {{ {}, {} }}",
-                it.event().name(),
+                it.event().name().unwrap(),
                 it.event_id().to_str(),
-                it.entity(index).name(),
+                it.entity(index).name().unwrap(),
                 pos_self.x,
                 pos_self.y,
                 pos_parent.x,
@@ -15,9 +15,9 @@ fn main() {
         .on_each_iter(|it, index, (pos, vel)| {
             println!(
                 " - {}: {}: {}: p: {{ {}, {} }}, v: {{ {}, {} }}",
-                it.event().name(),
+                it.event().name().unwrap(),
                 it.event_id().to_str(),
-                it.entity(index).name(),
+                it.entity(index).name().unwrap(),
                 pos.x,
                 pos.y,
                 vel.x,
@@ -27,7 +27,7 @@ fn main() {
         .on_each_iter(|it, index, (pos,)| {
             println!(
                 " - {}: {}: {}: {{ {}, {} }}",
-                it.event().name(),
+                it.event().name().unwrap(),
                 it.event_id().to_str(),
                 it.entity(index),
                 pos.x,
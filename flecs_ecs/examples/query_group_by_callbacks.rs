@@ -1,56 +1,13 @@
 mod common;
-use std::ffi::c_void;
+use std::sync::atomic::{AtomicI32, Ordering};
 
 use common::*;
 
-use std::sync::Mutex;
-
-static GROUP_COUNTER: Mutex<i32> = Mutex::new(0);
-
+// Data that will be associated with a group
 struct GroupCtx {
     counter: i32,
 }
 
-// callbacks need to be extern "C" to be callable from C
-extern "C" fn callback_group_create(
-    world: *mut WorldT,
-    group_id: u64,
-    _group_by_ctx: *mut c_void,
-) -> *mut c_void {
-    let world = World::new_wrap_raw_world(world);
-    println!(
-        "Group created: {:?}",
-        world.new_entity_from_id(group_id).name()
-    );
-
-    println!();
-
-    let mut counter = GROUP_COUNTER.lock().unwrap();
-    *counter += 1;
-
-    // Return data that will be associated with the group
-    let ctx = Box::new(GroupCtx { counter: *counter });
-
-    Box::into_raw(ctx) as *mut std::ffi::c_void // Cast to make sure function type matches
-}
-
-// callbacks need to be extern "C" to be callable from C
-extern "C" fn callback_group_delete(
-    world: *mut WorldT,
-    group_id: u64,
-    ctx: *mut c_void,
-    _group_by_arg: *mut c_void,
-) {
-    let world = World::new_wrap_raw_world(world);
-    println!(
-        "Group deleted: {:?}",
-        world.new_entity_from_id(group_id).name()
-    );
-
-    //Free data associated with group
-    unsafe { drop(Box::from_raw(ctx as *mut GroupCtx)) };
-}
-
 fn main() {
     let world = World::new();
 
@@ -59,14 +16,33 @@ fn main() {
     world.component::<Second>();
     world.component::<Third>();
 
+    let group_counter = AtomicI32::new(0);
+
     // Grouped query
     let query = world
         .query_builder::<(&Position,)>()
         .group_by::<Group>()
         // Callback invoked when a new group is created
-        .on_group_create(Some(callback_group_create))
-        // Callback invoked when a group is deleted
-        .on_group_delete(Some(callback_group_delete))
+        .on_group_create(move |world, group_id| {
+            println!(
+                "Group created: {:?}",
+                world.new_entity_from_id(group_id).name().unwrap()
+            );
+            println!();
+
+            // Return data that will be associated with the group
+            GroupCtx {
+                counter: group_counter.fetch_add(1, Ordering::Relaxed) + 1,
+            }
+        })
+        // Callback invoked when a group is deleted, the data associated with the group is
+        // dropped afterwards
+        .on_group_delete(|world, group_id, _ctx: GroupCtx| {
+            println!(
+                "Group deleted: {:?}",
+                world.new_entity_from_id(group_id).name().unwrap()
+            );
+        })
         .build();
 
     // Create entities in 6 different tables with 3 group ids
@@ -117,7 +93,7 @@ fn main() {
 
     query.iter(|it, (pos,)| {
         let group = world.new_entity_from_id(it.group_id());
-        let ctx = unsafe { &*(query.group_context(group) as *mut GroupCtx) };
+        let ctx = query.group_context::<GroupCtx>(group).unwrap();
         println!(
             "Group: {:?} - Table: [{:?}] - Counter: {}",
             group.path().unwrap(),
@@ -83,7 +83,7 @@ fn main() {
         .each_entity(|entity, (position,)| {
             println!(
                 "Entity {} is at ({}, {})",
-                entity.name(),
+                entity.name().unwrap(),
                 position.x,
                 position.y
             );
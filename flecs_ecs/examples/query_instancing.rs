@@ -53,7 +53,11 @@ fn main() {
             for i in it.iter() {
                 position[i].x += velocity[i].x;
                 position[i].y += velocity[i].y;
-                println!("entity {} has {:?}", it.entity(i).name(), position[i]);
+                println!(
+                    "entity {} has {:?}",
+                    it.entity(i).name().unwrap(),
+                    position[i]
+                );
             }
         } else {
             println!("Velocity is shared");
@@ -61,7 +65,11 @@ fn main() {
             for i in it.iter() {
                 position[i].x += velocity[0].x;
                 position[i].y += velocity[0].y;
-                println!("entity {} has {:?}", it.entity(i).name(), position[i]);
+                println!(
+                    "entity {} has {:?}",
+                    it.entity(i).name().unwrap(),
+                    position[i]
+                );
             }
         }
     });
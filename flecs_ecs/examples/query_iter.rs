@@ -46,7 +46,11 @@ fn main() {
         for i in it.iter() {
             position[i].x += velocity[i].x;
             position[i].y += velocity[i].y;
-            println!(" - entity {}: has {:?}", it.entity(i).name(), position[i]);
+            println!(
+                " - entity {}: has {:?}",
+                it.entity(i).name().unwrap(),
+                position[i]
+            );
         }
 
         println!();
@@ -1,16 +1,10 @@
 mod common;
-use common::*;
+use std::cmp::Ordering;
 
-extern "C" fn compare_position(
-    _e1: EntityT,
-    p1: *const Position,
-    _e2: EntityT,
-    p2: *const Position,
-) -> std::ffi::c_int {
-    let p1 = unsafe { &*p1 };
-    let p2 = unsafe { &*p2 };
+use common::*;
 
-    (p1.x > p2.x) as i32 - (p1.x < p2.x) as i32
+fn compare_position(_e1: Entity, p1: &Position, _e2: Entity, p2: &Position) -> Ordering {
+    p1.x.total_cmp(&p2.x)
 }
 
 fn print_query(query: &Query<'_, (&Position,)>) {
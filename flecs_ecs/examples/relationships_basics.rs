@@ -42,23 +42,23 @@ fn main() {
 
     // Relationships can be iterated for an entity. This iterates (Eats, *):
     bob.for_each_target::<Eats>(|second| {
-        println!("Bob eats {}", second.name());
+        println!("Bob eats {}", second.name().unwrap());
     });
 
     println!();
 
     // Iterate by explicitly providing the pair. This iterates (*, Pears):
     bob.for_each_matching_pair(flecs::Wildcard::ID, pears, |id| {
-        println!("Bob {} pears", id.first().name());
+        println!("Bob {} pears", id.first().name().unwrap());
     });
 
     println!();
 
     // Get first target of relationship
-    println!("Bob eats {}", bob.target::<Eats>(0).name());
+    println!("Bob eats {}", bob.target::<Eats>(0).name().unwrap());
 
     // Get second target of relationship
-    println!("Bob also eats {}", bob.target::<Eats>(1).name());
+    println!("Bob also eats {}", bob.target::<Eats>(1).name().unwrap());
 
     // Output:
     //  Bob eats apples? true
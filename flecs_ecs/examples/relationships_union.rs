@@ -81,7 +81,7 @@ fn main() {
         for i in 0..it.count() {
             println!(
                 "{}: Movement: {:?}, Direction: {:?}",
-                it.entity(i).name(),
+                it.entity(i).name().unwrap(),
                 movement[i]
                     .to_entity(&it.world())
                     .to_constant::<Movement>()
@@ -56,8 +56,8 @@ fn main() {
     rule.each_iter(|it, index, ()| {
         println!(
             "{} eats {}",
-            it.entity(index).name(),
-            it.get_var(food_var).name()
+            it.entity(index).name().unwrap(),
+            it.get_var(food_var).name().unwrap()
         );
     });
 
@@ -7,7 +7,7 @@ use common::*;
 
 // Dummy system
 fn sys(it: &mut Iter) {
-    println!("system {}", it.system().name());
+    println!("system {}", it.system().name().unwrap());
 }
 
 fn main() {
@@ -27,7 +27,7 @@ fn main() {
                 // See the mutate_entity_handle example.
                 let e = it.entity(index);
                 e.destruct();
-                println!("Expire: {} deleted!", e.name());
+                println!("Expire: {} deleted!", e.name().unwrap());
             }
         });
 
@@ -37,7 +37,7 @@ fn main() {
         .on_each_entity(|e, (timeout,)| {
             println!(
                 "PrintExpire: {} has {:.2} seconds left",
-                e.name(),
+                e.name().unwrap(),
                 timeout.value
             );
         });
@@ -47,7 +47,7 @@ fn main() {
         .observer_builder::<(&Timeout,)>()
         .add_event::<flecs::OnRemove>()
         .on_each_entity(|e, (_timeout,)| {
-            println!("Expired: {} actually deleted", e.name());
+            println!("Expired: {} actually deleted", e.name().unwrap());
         });
 
     let e = world
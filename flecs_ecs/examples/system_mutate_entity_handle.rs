@@ -45,7 +45,7 @@ fn main() {
                 //
                 // A shortcut is to use the iterator directly:
                 timeout.to_delete.destruct();
-                println!("Expire: {} deleted!", timeout.to_delete.name());
+                println!("Expire: {} deleted!", timeout.to_delete.name().unwrap());
             }
         });
 
@@ -53,7 +53,7 @@ fn main() {
     world.system_builder::<(&Timeout,)>().on_each(|(timeout,)| {
         println!(
             "PrintExpire: {} has {:.2} seconds left",
-            timeout.to_delete.name(),
+            timeout.to_delete.name().unwrap(),
             timeout.value
         );
     });
@@ -63,7 +63,7 @@ fn main() {
         .observer_builder::<(&Tag,)>()
         .add_event::<flecs::OnRemove>()
         .on_each_entity(|e, (_tag,)| {
-            println!("Expired: {} actually deleted", e.name());
+            println!("Expired: {} actually deleted", e.name().unwrap());
         });
 
     let to_delete = world.new_entity_named(c"ToDelete").add::<Tag>();
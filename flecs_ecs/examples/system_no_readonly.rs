@@ -63,7 +63,11 @@ fn main() {
 
                     plate.add_pair_first::<&Waiter>(waiter);
 
-                    println!("Assigned {} to {}!", waiter.name(), plate.name());
+                    println!(
+                        "Assigned {} to {}!",
+                        waiter.name().unwrap(),
+                        plate.name().unwrap()
+                    );
                 } else {
                     // No available waiters, can't assign the plate
                 }
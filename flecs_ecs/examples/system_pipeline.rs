@@ -18,7 +18,7 @@ fn main() {
         .system_builder::<(&Position,)>()
         .kind::<flecs::pipeline::PostUpdate>()
         .on_each_entity(|e, (p,)| {
-            println!("{}: {{ {}, {} }}", e.name(), p.x, p.y);
+            println!("{}: {{ {}, {} }}", e.name().unwrap(), p.x, p.y);
         });
 
     // Create a few test entities for a Position, Velocity query
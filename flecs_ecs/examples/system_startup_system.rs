@@ -18,14 +18,14 @@ fn main() {
         .system_builder_named::<()>(c"Startup")
         .kind::<flecs::pipeline::OnStart>()
         .on_iter_only(|it| {
-            println!("{}", it.system().name());
+            println!("{}", it.system().name().unwrap());
         });
 
     // Regular system
     world
         .system_builder_named::<()>(c"Update")
         .on_iter_only(|it| {
-            println!("{}", it.system().name());
+            println!("{}", it.system().name().unwrap());
         });
 
     // First frame. This runs both the Startup and Update systems
@@ -39,7 +39,7 @@ fn main() {
     world
         .system_builder_named::<(&Position,)>(c"PrintPosition")
         .on_each_entity(|e, (p,)| {
-            println!("{}: {{ {}, {} }}", e.name(), p.x, p.y);
+            println!("{}: {{ {}, {} }}", e.name().unwrap(), p.x, p.y);
         });
 
     // Create a few test entities for a Position, Velocity query
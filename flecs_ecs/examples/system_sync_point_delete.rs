@@ -33,7 +33,7 @@ fn main() {
         .write_type::<&flecs::Wildcard>()
         .on_each_entity(|e, (p,)| {
             if p.x >= 3.0 {
-                println!("Delete entity {}", e.name());
+                println!("Delete entity {}", e.name().unwrap());
                 e.destruct();
             }
         });
@@ -43,7 +43,7 @@ fn main() {
     world
         .system_builder_named::<(&Position,)>(c"PrintPosition")
         .on_each_entity(|e, (p,)| {
-            println!("{}: {{ {}, {} }}", e.name(), p.x, p.y);
+            println!("{}: {{ {}, {} }}", e.name().unwrap(), p.x, p.y);
         });
 
     // Create a few test entities for a Position, Velocity query
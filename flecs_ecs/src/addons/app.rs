@@ -1,9 +1,13 @@
 //! Optional addon for running the main application loop.
 
-use std::ffi::c_void;
+use std::{ffi::c_void, sync::Arc};
 
 use crate::{
-    core::{c_types::WorldT, world::World, FTime},
+    core::{
+        c_types::WorldT,
+        world::{World, WorldOwner},
+        FTime,
+    },
     sys::{
         ecs_app_desc_t, ecs_app_init_action_t, ecs_app_run, ecs_fini, ecs_get_world_info,
         ecs_should_quit,
@@ -13,6 +17,7 @@ use crate::{
 /// Application interface.
 pub struct App {
     world: *mut WorldT,
+    owner: Option<Arc<WorldOwner>>,
     desc: ecs_app_desc_t,
 }
 
@@ -30,6 +35,7 @@ impl App {
     pub fn new(world: &World) -> Self {
         let mut obj = Self {
             world: world.raw_world,
+            owner: world.owner.clone(),
             desc: ecs_app_desc_t::default(),
         };
 
@@ -186,7 +192,10 @@ impl App {
                 // Only free world if quit flag is set. This ensures that we won't
                 // try to cleanup the world if the app is used in an environment
                 // that takes over the main loop, like with emscripten.
-                ecs_fini(self.world);
+                // An owned world is freed once its last handle is dropped.
+                if self.owner.take().is_none() {
+                    ecs_fini(self.world);
+                }
             }
             result
         }
@@ -0,0 +1,459 @@
+//! Serialization of entities and component values to and from JSON.
+//!
+//! Component values can only be (de)serialized when their type has reflection data, for
+//! example by deriving `Component` with `#[meta]`.
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    core::{capture_log, FlecsError, FlecsErrorCode, IntoEntityId},
+    sys::{
+        ecs_entity_to_json_desc_t, ecs_iter_to_json_desc_t, ecs_os_api, ecs_world_to_json_desc_t,
+    },
+};
+
+/// Errors returned by the JSON operations.
+#[derive(Debug)]
+pub enum JsonError {
+    /// The value could not be serialized, for example because its type has no reflection data.
+    SerializeFailed,
+    /// The JSON could not be parsed, the error carries the reason logged by flecs.
+    Parse(FlecsError),
+    /// The JSON string contained an interior nul byte.
+    InvalidString,
+    /// Reading or writing the JSON failed.
+    Io(std::io::Error),
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonError::SerializeFailed => write!(f, "failed to serialize value to JSON"),
+            JsonError::Parse(err) => write!(f, "failed to parse JSON: {}", err),
+            JsonError::InvalidString => write!(f, "JSON contains an interior nul byte"),
+            JsonError::Io(err) => write!(f, "JSON i/o error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonError::Parse(err) => Some(err),
+            JsonError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for JsonError {
+    fn from(err: std::io::Error) -> Self {
+        JsonError::Io(err)
+    }
+}
+
+/// Convert a JSON string into a C string.
+pub(crate) fn json_to_cstring(json: &str) -> Result<CString, JsonError> {
+    CString::new(json).map_err(|_| JsonError::InvalidString)
+}
+
+/// Run a flecs JSON parse function, which returns null on failure.
+///
+/// The messages flecs logs while parsing are captured and returned as part of the error.
+pub(crate) fn json_parse(parse: impl FnOnce() -> *const c_char) -> Result<(), JsonError> {
+    let (result, log) = capture_log(parse);
+
+    if result.is_null() {
+        Err(JsonError::Parse(
+            FlecsError::new(FlecsErrorCode::InvalidParameter, "failed to parse JSON").with_log(log),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Take ownership of a JSON string returned by flecs.
+///
+/// # Safety
+///
+/// `json` must be null or a string allocated by the flecs OS API.
+pub(crate) unsafe fn json_from_raw(json: *mut c_char) -> Result<String, JsonError> {
+    if json.is_null() {
+        return Err(JsonError::SerializeFailed);
+    }
+
+    let result = CStr::from_ptr(json).to_string_lossy().into_owned();
+
+    if let Some(free_func) = ecs_os_api.free_ {
+        free_func(json as *mut _);
+    }
+
+    Ok(result)
+}
+
+/// Options for serializing an entity to JSON.
+///
+/// The defaults mirror `ECS_ENTITY_TO_JSON_INIT` and serialize the path, the ids and the inherited
+/// components of the entity. Component values are not serialized unless `serialize_values` is set.
+///
+/// # See also
+///
+/// * C API: `ecs_entity_to_json_desc_t`
+#[doc(alias = "ecs_entity_to_json_desc_t")]
+#[derive(Debug, Clone, Copy)]
+pub struct EntityToJsonDesc {
+    pub(crate) desc: ecs_entity_to_json_desc_t,
+}
+
+impl Default for EntityToJsonDesc {
+    fn default() -> Self {
+        // mirrors ECS_ENTITY_TO_JSON_INIT
+        Self {
+            desc: ecs_entity_to_json_desc_t {
+                serialize_path: true,
+                serialize_label: false,
+                serialize_brief: false,
+                serialize_link: false,
+                serialize_color: false,
+                serialize_ids: true,
+                serialize_id_labels: false,
+                serialize_base: true,
+                serialize_private: false,
+                serialize_hidden: false,
+                serialize_values: false,
+                serialize_type_info: false,
+                serialize_alerts: false,
+                serialize_refs: 0,
+                serialize_matches: false,
+            },
+        }
+    }
+}
+
+impl EntityToJsonDesc {
+    /// Create a description with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serialize the full path of the entity.
+    pub fn serialize_path(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_path = value;
+        self
+    }
+
+    /// Serialize the doc name of the entity.
+    pub fn serialize_label(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_label = value;
+        self
+    }
+
+    /// Serialize the brief doc description of the entity.
+    pub fn serialize_brief(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_brief = value;
+        self
+    }
+
+    /// Serialize the doc link of the entity.
+    pub fn serialize_link(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_link = value;
+        self
+    }
+
+    /// Serialize the doc color of the entity.
+    pub fn serialize_color(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_color = value;
+        self
+    }
+
+    /// Serialize the (component) ids of the entity.
+    pub fn serialize_ids(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_ids = value;
+        self
+    }
+
+    /// Serialize the labels of the (component) ids of the entity.
+    pub fn serialize_id_labels(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_id_labels = value;
+        self
+    }
+
+    /// Serialize the components inherited from base entities.
+    pub fn serialize_base(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_base = value;
+        self
+    }
+
+    /// Serialize private components.
+    pub fn serialize_private(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_private = value;
+        self
+    }
+
+    /// Serialize ids that are hidden by an override.
+    pub fn serialize_hidden(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_hidden = value;
+        self
+    }
+
+    /// Serialize component values.
+    pub fn serialize_values(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_values = value;
+        self
+    }
+
+    /// Serialize type info of the components, requires `serialize_values`.
+    pub fn serialize_type_info(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_type_info = value;
+        self
+    }
+
+    /// Serialize the active alerts of the entity.
+    pub fn serialize_alerts(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_alerts = value;
+        self
+    }
+
+    /// Serialize the entities that reference the entity with the specified relationship.
+    pub fn serialize_refs(&mut self, relationship: impl IntoEntityId) -> &mut Self {
+        self.desc.serialize_refs = relationship.get_id();
+        self
+    }
+
+    /// Serialize the queries the entity matches with.
+    pub fn serialize_matches(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_matches = value;
+        self
+    }
+}
+
+/// Options for serializing a world to JSON.
+///
+/// By default the contents of the flecs modules and other modules are not serialized.
+///
+/// # See also
+///
+/// * C API: `ecs_world_to_json_desc_t`
+#[doc(alias = "ecs_world_to_json_desc_t")]
+#[derive(Debug, Clone, Copy)]
+pub struct WorldToJsonDesc {
+    pub(crate) desc: ecs_world_to_json_desc_t,
+}
+
+impl Default for WorldToJsonDesc {
+    fn default() -> Self {
+        Self {
+            desc: ecs_world_to_json_desc_t {
+                serialize_builtin: false,
+                serialize_modules: false,
+            },
+        }
+    }
+}
+
+impl WorldToJsonDesc {
+    /// Create a description with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serialize the builtin flecs entities.
+    pub fn serialize_builtin(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_builtin = value;
+        self
+    }
+
+    /// Serialize modules and their contents.
+    pub fn serialize_modules(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_modules = value;
+        self
+    }
+}
+
+/// Options for serializing query results to JSON.
+///
+/// The defaults serialize the term ids, the matched ids, sources, variables and entities, and
+/// the component values.
+///
+/// # See also
+///
+/// * C API: `ecs_iter_to_json_desc_t`
+#[doc(alias = "ecs_iter_to_json_desc_t")]
+#[derive(Debug, Clone, Copy)]
+pub struct IterToJsonDesc {
+    pub(crate) desc: ecs_iter_to_json_desc_t,
+}
+
+impl Default for IterToJsonDesc {
+    fn default() -> Self {
+        // mirrors ECS_ITER_TO_JSON_INIT
+        Self {
+            desc: ecs_iter_to_json_desc_t {
+                serialize_term_ids: true,
+                serialize_term_labels: false,
+                serialize_ids: true,
+                serialize_id_labels: false,
+                serialize_sources: true,
+                serialize_variables: true,
+                serialize_is_set: true,
+                serialize_values: true,
+                serialize_private: false,
+                serialize_entities: true,
+                serialize_entity_labels: false,
+                serialize_entity_ids: false,
+                serialize_entity_names: false,
+                serialize_variable_labels: false,
+                serialize_variable_ids: false,
+                serialize_colors: false,
+                measure_eval_duration: false,
+                serialize_type_info: false,
+                serialize_table: false,
+                serialize_rows: false,
+                serialize_field_info: false,
+                dont_serialize_results: false,
+            },
+        }
+    }
+}
+
+impl IterToJsonDesc {
+    /// Create a description with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serialize the component ids of the query terms.
+    pub fn serialize_term_ids(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_term_ids = value;
+        self
+    }
+
+    /// Serialize the labels of the component ids of the query terms.
+    pub fn serialize_term_labels(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_term_labels = value;
+        self
+    }
+
+    /// Serialize the actual (matched) component ids.
+    pub fn serialize_ids(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_ids = value;
+        self
+    }
+
+    /// Serialize the labels of the actual (matched) component ids.
+    pub fn serialize_id_labels(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_id_labels = value;
+        self
+    }
+
+    /// Serialize the sources of the fields.
+    pub fn serialize_sources(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_sources = value;
+        self
+    }
+
+    /// Serialize the values of the query variables.
+    pub fn serialize_variables(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_variables = value;
+        self
+    }
+
+    /// Serialize whether fields are set, for optional terms.
+    pub fn serialize_is_set(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_is_set = value;
+        self
+    }
+
+    /// Serialize component values.
+    pub fn serialize_values(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_values = value;
+        self
+    }
+
+    /// Serialize private components.
+    pub fn serialize_private(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_private = value;
+        self
+    }
+
+    /// Serialize the matched entities.
+    pub fn serialize_entities(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_entities = value;
+        self
+    }
+
+    /// Serialize the doc names of the matched entities.
+    pub fn serialize_entity_labels(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_entity_labels = value;
+        self
+    }
+
+    /// Serialize the numerical ids of the matched entities.
+    pub fn serialize_entity_ids(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_entity_ids = value;
+        self
+    }
+
+    /// Serialize the names instead of the paths of the matched entities.
+    pub fn serialize_entity_names(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_entity_names = value;
+        self
+    }
+
+    /// Serialize the doc names of the query variables.
+    pub fn serialize_variable_labels(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_variable_labels = value;
+        self
+    }
+
+    /// Serialize the numerical ids of the query variables.
+    pub fn serialize_variable_ids(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_variable_ids = value;
+        self
+    }
+
+    /// Serialize the doc colors of the matched entities.
+    pub fn serialize_colors(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_colors = value;
+        self
+    }
+
+    /// Serialize the time it took to evaluate the query.
+    pub fn measure_eval_duration(&mut self, value: bool) -> &mut Self {
+        self.desc.measure_eval_duration = value;
+        self
+    }
+
+    /// Serialize type information of the fields.
+    pub fn serialize_type_info(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_type_info = value;
+        self
+    }
+
+    /// Serialize all components of the matched tables instead of only the fields.
+    pub fn serialize_table(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_table = value;
+        self
+    }
+
+    /// Serialize each matched entity as a separate element.
+    pub fn serialize_rows(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_rows = value;
+        self
+    }
+
+    /// Serialize metadata of the fields of the query.
+    pub fn serialize_field_info(&mut self, value: bool) -> &mut Self {
+        self.desc.serialize_field_info = value;
+        self
+    }
+
+    /// Skip evaluating the query, only serializing the query metadata.
+    pub fn dont_serialize_results(&mut self, value: bool) -> &mut Self {
+        self.desc.dont_serialize_results = value;
+        self
+    }
+}
@@ -0,0 +1,662 @@
+//! Safe wrapper around the flecs meta cursor.
+//!
+//! A [`MetaCursor`] walks a value using the reflection data of its type, which allows reading
+//! and writing members of components without knowing their Rust type at compile time.
+
+use std::{
+    ffi::{c_char, c_void, CStr, CString},
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+};
+
+use crate::{
+    core::{Entity, EntityT, IntoEntityId, WorldT},
+    sys::{
+        ecs_meta_cursor, ecs_meta_cursor_t, ecs_meta_dotmember, ecs_meta_elem, ecs_meta_get_bool,
+        ecs_meta_get_char, ecs_meta_get_entity, ecs_meta_get_float, ecs_meta_get_int,
+        ecs_meta_get_member, ecs_meta_get_string, ecs_meta_get_type, ecs_meta_get_uint,
+        ecs_meta_get_unit, ecs_meta_is_collection, ecs_meta_member, ecs_meta_next, ecs_meta_pop,
+        ecs_meta_push, ecs_meta_set_bool, ecs_meta_set_char, ecs_meta_set_entity,
+        ecs_meta_set_float, ecs_meta_set_int, ecs_meta_set_null, ecs_meta_set_string,
+        ecs_meta_set_uint, ecs_meta_type_op_kind_t, ecs_meta_type_op_kind_t_EcsOpBitmask,
+        ecs_meta_type_op_kind_t_EcsOpBool, ecs_meta_type_op_kind_t_EcsOpChar,
+        ecs_meta_type_op_kind_t_EcsOpEntity, ecs_meta_type_op_kind_t_EcsOpEnum,
+        ecs_meta_type_op_kind_t_EcsOpId, ecs_meta_type_op_kind_t_EcsOpString,
+        ecs_meta_type_op_kind_t_EcsOpU64, ecs_meta_type_op_kind_t_EcsOpUPtr, ecs_meta_type_op_t,
+        ecs_vec_count,
+    },
+};
+
+/// Maximum nesting depth of a cursor, as defined by `ECS_META_MAX_SCOPE_DEPTH`.
+const MAX_SCOPE_DEPTH: i32 = 32;
+
+/// Errors returned by [`MetaCursor`] operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetaCursorError {
+    /// The type has no reflection data, so a cursor cannot be created for it.
+    InvalidType(EntityT),
+    /// Flecs rejected the operation, the reason is reported through the flecs log.
+    OperationFailed(&'static str),
+    /// The value at the cursor cannot be read as, or assigned from, the requested type.
+    InvalidConversion(&'static str),
+    /// The value at the cursor does not fit in the requested type.
+    OutOfRange,
+    /// A string argument contained an interior nul byte.
+    InvalidString,
+}
+
+impl Display for MetaCursorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetaCursorError::InvalidType(id) => {
+                write!(f, "type {} has no reflection data", id)
+            }
+            MetaCursorError::OperationFailed(operation) => {
+                write!(f, "meta cursor operation '{}' failed", operation)
+            }
+            MetaCursorError::InvalidConversion(ty) => {
+                write!(f, "invalid conversion of value at cursor to or from {}", ty)
+            }
+            MetaCursorError::OutOfRange => write!(f, "value at cursor is out of range"),
+            MetaCursorError::InvalidString => write!(f, "string contains an interior nul byte"),
+        }
+    }
+}
+
+impl std::error::Error for MetaCursorError {}
+
+/// Cursor that navigates and edits a value using the reflection data of its type.
+///
+/// A cursor starts at the root of the value. Use [`push`](Self::push) to enter a struct or
+/// collection, [`member`](Self::member) / [`elem`](Self::elem) / [`next`](Self::next) to move
+/// inside of it and [`pop`](Self::pop) to leave it again. Operations that fail leave the cursor
+/// where it was.
+///
+/// The cursor borrows both the world and the value for its lifetime.
+///
+/// # See also
+///
+/// * C++ API: `cursor`
+#[doc(alias = "cursor")]
+pub struct MetaCursor<'w> {
+    cursor: ecs_meta_cursor_t,
+    phantom: PhantomData<&'w mut c_void>,
+}
+
+impl<'w> MetaCursor<'w> {
+    /// Create a cursor for a value of the specified type.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid value of type `type_id` that outlives the cursor.
+    pub(crate) unsafe fn new(
+        world: *const WorldT,
+        type_id: EntityT,
+        ptr: *mut c_void,
+    ) -> Result<Self, MetaCursorError> {
+        let cursor = ecs_meta_cursor(world, type_id, ptr);
+
+        if !cursor.valid {
+            return Err(MetaCursorError::InvalidType(type_id));
+        }
+
+        Ok(Self {
+            cursor,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns the operation the cursor currently points to, if it points to one.
+    fn current_op(&self) -> Option<&ecs_meta_type_op_t> {
+        let scope = &self.cursor.scope[self.cursor.depth as usize];
+
+        if scope.ops.is_null() || scope.op_cur < 0 || scope.op_cur >= scope.op_count {
+            return None;
+        }
+
+        Some(unsafe { &*scope.ops.add(scope.op_cur as usize) })
+    }
+
+    /// Check that the cursor points to a value, which is required by most operations.
+    fn check_position(&self, operation: &'static str) -> Result<(), MetaCursorError> {
+        self.current_op()
+            .map(|_| ())
+            .ok_or(MetaCursorError::OperationFailed(operation))
+    }
+
+    /// Check that the value at the cursor is of a kind accepted by `accepts`.
+    fn check_kind(
+        &self,
+        ty: &'static str,
+        accepts: impl FnOnce(ecs_meta_type_op_kind_t) -> bool,
+    ) -> Result<(), MetaCursorError> {
+        match self.current_op() {
+            Some(op) if accepts(op.kind) => Ok(()),
+            _ => Err(MetaCursorError::InvalidConversion(ty)),
+        }
+    }
+
+    /// Run a flecs cursor operation, restoring the cursor if the operation fails.
+    fn apply(
+        &mut self,
+        operation: &'static str,
+        func: impl FnOnce(*mut ecs_meta_cursor_t) -> i32,
+    ) -> Result<(), MetaCursorError> {
+        let saved = self.cursor;
+
+        if func(&mut self.cursor) != 0 {
+            self.cursor = saved;
+            return Err(MetaCursorError::OperationFailed(operation));
+        }
+
+        Ok(())
+    }
+
+    /// Enter the struct or collection at the cursor.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::push`
+    #[doc(alias = "cursor::push")]
+    pub fn push(&mut self) -> Result<(), MetaCursorError> {
+        self.check_position("push")?;
+        if self.cursor.depth + 1 >= MAX_SCOPE_DEPTH {
+            return Err(MetaCursorError::OperationFailed("push"));
+        }
+
+        self.apply("push", |cursor| unsafe { ecs_meta_push(cursor) })
+    }
+
+    /// Leave the current struct or collection.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::pop`
+    #[doc(alias = "cursor::pop")]
+    pub fn pop(&mut self) -> Result<(), MetaCursorError> {
+        self.apply("pop", |cursor| unsafe { ecs_meta_pop(cursor) })
+    }
+
+    /// Move the cursor to the next member or element.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::next`
+    #[doc(alias = "cursor::next")]
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<(), MetaCursorError> {
+        self.check_position("next")?;
+
+        self.apply("next", |cursor| unsafe { ecs_meta_next(cursor) })
+    }
+
+    /// Move the cursor to an element of the current collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the element.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::elem`
+    #[doc(alias = "cursor::elem")]
+    pub fn elem(&mut self, index: usize) -> Result<(), MetaCursorError> {
+        let index = i32::try_from(index).map_err(|_| MetaCursorError::OutOfRange)?;
+        self.apply("elem", |cursor| unsafe { ecs_meta_elem(cursor, index) })
+    }
+
+    /// Move the cursor to a member of the current struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the member.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::member`
+    #[doc(alias = "cursor::member")]
+    pub fn member(&mut self, name: &str) -> Result<(), MetaCursorError> {
+        let name = CString::new(name).map_err(|_| MetaCursorError::InvalidString)?;
+        self.apply("member", |cursor| unsafe {
+            ecs_meta_member(cursor, name.as_ptr())
+        })
+    }
+
+    /// Move the cursor to a nested member of the current struct.
+    ///
+    /// The path is a dot separated list of member names, for example `"position.x"`. The
+    /// cursor returns to the current scope on the next navigation operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The dot separated path to the member.
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_meta_dotmember`
+    #[doc(alias = "ecs_meta_dotmember")]
+    pub fn dotmember(&mut self, path: &str) -> Result<(), MetaCursorError> {
+        let path = CString::new(path).map_err(|_| MetaCursorError::InvalidString)?;
+        self.apply("dotmember", |cursor| unsafe {
+            ecs_meta_dotmember(cursor, path.as_ptr())
+        })
+    }
+
+    /// Returns whether the current scope is a collection.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::is_collection`
+    #[doc(alias = "cursor::is_collection")]
+    pub fn is_collection(&self) -> bool {
+        unsafe { ecs_meta_is_collection(&self.cursor) }
+    }
+
+    /// Returns the number of elements of the current collection.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements, or an error if the current scope is not a collection.
+    pub fn elem_count(&self) -> Result<usize, MetaCursorError> {
+        if !self.is_collection() {
+            return Err(MetaCursorError::OperationFailed("elem_count"));
+        }
+
+        let depth = self.cursor.depth as usize;
+        let scope = &self.cursor.scope[depth];
+
+        let count = if !scope.vector.is_null() {
+            unsafe { ecs_vec_count(scope.vector) }
+        } else if let Some(count) = unsafe { scope.opaque.as_ref() }.and_then(|o| o.count) {
+            // the collection of an opaque type is owned by the value in the parent scope
+            unsafe { count(self.cursor.scope[depth - 1].ptr) as i32 }
+        } else {
+            self.current_op()
+                .map(|op| op.count)
+                .ok_or(MetaCursorError::OperationFailed("elem_count"))?
+        };
+
+        Ok(count.max(0) as usize)
+    }
+
+    /// Visit all elements of the current collection.
+    ///
+    /// The cursor is moved to each element before `func` is invoked.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function to invoke for each element, errors stop the iteration.
+    pub fn each_elem(
+        &mut self,
+        mut func: impl FnMut(&mut Self) -> Result<(), MetaCursorError>,
+    ) -> Result<(), MetaCursorError> {
+        for index in 0..self.elem_count()? {
+            self.elem(index)?;
+            func(self)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the type of the value at the cursor.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::get_type`
+    #[doc(alias = "cursor::get_type")]
+    pub fn get_type(&self) -> EntityT {
+        if self.current_op().is_none() {
+            return 0;
+        }
+        unsafe { ecs_meta_get_type(&self.cursor) }
+    }
+
+    /// Returns the unit of the value at the cursor, 0 if it has none.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::get_unit`
+    #[doc(alias = "cursor::get_unit")]
+    pub fn get_unit(&self) -> EntityT {
+        if self.current_op().is_none() {
+            return 0;
+        }
+        unsafe { ecs_meta_get_unit(&self.cursor) }
+    }
+
+    /// Returns the name of the member at the cursor, `None` if the cursor is not at a member.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::get_member`
+    #[doc(alias = "cursor::get_member")]
+    pub fn get_member(&self) -> Option<&str> {
+        self.current_op()?;
+        let name = unsafe { ecs_meta_get_member(&self.cursor) };
+        if name.is_null() {
+            None
+        } else {
+            unsafe { CStr::from_ptr(name) }.to_str().ok()
+        }
+    }
+
+    /// Assign a boolean to the value at the cursor.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::set_bool`
+    #[doc(alias = "cursor::set_bool")]
+    pub fn set_bool(&mut self, value: bool) -> Result<(), MetaCursorError> {
+        self.check_position("set_bool")?;
+        self.apply("set_bool", |cursor| unsafe {
+            ecs_meta_set_bool(cursor, value)
+        })
+    }
+
+    /// Assign an ASCII character to the value at the cursor.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::set_char`
+    #[doc(alias = "cursor::set_char")]
+    pub fn set_char(&mut self, value: char) -> Result<(), MetaCursorError> {
+        if !value.is_ascii() {
+            return Err(MetaCursorError::InvalidConversion("char"));
+        }
+        self.check_position("set_char")?;
+        self.apply("set_char", |cursor| unsafe {
+            ecs_meta_set_char(cursor, value as c_char)
+        })
+    }
+
+    /// Assign a signed integer to the value at the cursor.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::set_int`
+    #[doc(alias = "cursor::set_int")]
+    pub fn set_int(&mut self, value: i64) -> Result<(), MetaCursorError> {
+        self.check_position("set_int")?;
+        self.apply("set_int", |cursor| unsafe {
+            ecs_meta_set_int(cursor, value)
+        })
+    }
+
+    /// Assign an unsigned integer to the value at the cursor.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::set_uint`
+    #[doc(alias = "cursor::set_uint")]
+    pub fn set_uint(&mut self, value: u64) -> Result<(), MetaCursorError> {
+        self.check_position("set_uint")?;
+        self.apply("set_uint", |cursor| unsafe {
+            ecs_meta_set_uint(cursor, value)
+        })
+    }
+
+    /// Assign a floating point number to the value at the cursor.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::set_float`
+    #[doc(alias = "cursor::set_float")]
+    pub fn set_float(&mut self, value: f64) -> Result<(), MetaCursorError> {
+        self.check_position("set_float")?;
+        self.apply("set_float", |cursor| unsafe {
+            ecs_meta_set_float(cursor, value)
+        })
+    }
+
+    /// Assign a string to the value at the cursor.
+    ///
+    /// Besides string members this parses numbers, booleans, enum constants and entity paths.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::set_string`
+    #[doc(alias = "cursor::set_string")]
+    pub fn set_string(&mut self, value: &str) -> Result<(), MetaCursorError> {
+        let value = CString::new(value).map_err(|_| MetaCursorError::InvalidString)?;
+        self.check_position("set_string")?;
+        self.apply("set_string", |cursor| unsafe {
+            ecs_meta_set_string(cursor, value.as_ptr())
+        })
+    }
+
+    /// Assign an entity to the value at the cursor.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::set_entity`
+    #[doc(alias = "cursor::set_entity")]
+    pub fn set_entity(&mut self, value: impl IntoEntityId) -> Result<(), MetaCursorError> {
+        let value = value.get_id();
+        self.check_position("set_entity")?;
+        self.apply("set_entity", |cursor| unsafe {
+            ecs_meta_set_entity(cursor, value)
+        })
+    }
+
+    /// Assign null to the value at the cursor.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::set_null`
+    #[doc(alias = "cursor::set_null")]
+    pub fn set_null(&mut self) -> Result<(), MetaCursorError> {
+        self.check_position("set_null")?;
+        self.apply("set_null", |cursor| unsafe { ecs_meta_set_null(cursor) })
+    }
+
+    /// Read the value at the cursor as a boolean.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::get_bool`
+    #[doc(alias = "cursor::get_bool")]
+    pub fn get_bool(&self) -> Result<bool, MetaCursorError> {
+        self.check_kind("bool", |kind| {
+            is_scalar(kind) || kind == ecs_meta_type_op_kind_t_EcsOpEntity
+        })?;
+        Ok(unsafe { ecs_meta_get_bool(&self.cursor) })
+    }
+
+    /// Read the value at the cursor as a character.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::get_char`
+    #[doc(alias = "cursor::get_char")]
+    pub fn get_char(&self) -> Result<char, MetaCursorError> {
+        self.check_kind("char", |kind| kind == ecs_meta_type_op_kind_t_EcsOpChar)?;
+        Ok(unsafe { ecs_meta_get_char(&self.cursor) } as u8 as char)
+    }
+
+    /// Read the value at the cursor as a signed integer.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::get_int`
+    #[doc(alias = "cursor::get_int")]
+    pub fn get_int(&self) -> Result<i64, MetaCursorError> {
+        self.check_kind("int", is_number)?;
+        // flecs asserts on values that don't fit, so let Rust do the range check
+        if self.current_op().is_some_and(|op| is_unsigned(op.kind)) {
+            let value = unsafe { ecs_meta_get_uint(&self.cursor) };
+            return i64::try_from(value).map_err(|_| MetaCursorError::OutOfRange);
+        }
+        Ok(unsafe { ecs_meta_get_int(&self.cursor) })
+    }
+
+    /// Read the value at the cursor as an unsigned integer.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::get_uint`
+    #[doc(alias = "cursor::get_uint")]
+    pub fn get_uint(&self) -> Result<u64, MetaCursorError> {
+        self.check_kind("uint", is_number)?;
+        // flecs asserts on values that don't fit, so let Rust do the range check
+        if self.current_op().is_some_and(|op| !is_unsigned(op.kind)) {
+            let value = unsafe { ecs_meta_get_int(&self.cursor) };
+            return u64::try_from(value).map_err(|_| MetaCursorError::OutOfRange);
+        }
+        Ok(unsafe { ecs_meta_get_uint(&self.cursor) })
+    }
+
+    /// Read the value at the cursor as a floating point number.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::get_float`
+    #[doc(alias = "cursor::get_float")]
+    pub fn get_float(&self) -> Result<f64, MetaCursorError> {
+        self.check_kind("float", is_number)?;
+        Ok(unsafe { ecs_meta_get_float(&self.cursor) })
+    }
+
+    /// Read the value at the cursor as a string.
+    ///
+    /// # Returns
+    ///
+    /// The string, `None` if the string member is not set.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::get_string`
+    #[doc(alias = "cursor::get_string")]
+    pub fn get_string(&self) -> Result<Option<&str>, MetaCursorError> {
+        self.check_kind("string", |kind| kind == ecs_meta_type_op_kind_t_EcsOpString)?;
+        let value = unsafe { ecs_meta_get_string(&self.cursor) };
+        if value.is_null() {
+            return Ok(None);
+        }
+        unsafe { CStr::from_ptr(value) }
+            .to_str()
+            .map(Some)
+            .map_err(|_| MetaCursorError::InvalidConversion("string"))
+    }
+
+    /// Read the value at the cursor as an entity.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `cursor::get_entity`
+    #[doc(alias = "cursor::get_entity")]
+    pub fn get_entity(&self) -> Result<Entity, MetaCursorError> {
+        self.check_kind("entity", |kind| kind == ecs_meta_type_op_kind_t_EcsOpEntity)?;
+        let entity = unsafe { ecs_meta_get_entity(&self.cursor) };
+        Ok(Entity::new_from_existing(
+            Some(self.cursor.world as *mut WorldT),
+            entity,
+        ))
+    }
+
+    /// Assign a Rust value to the value at the cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to assign, see [`MetaValue`] for the supported types.
+    pub fn set<V: MetaValue>(&mut self, value: V) -> Result<(), MetaCursorError> {
+        value.assign_to(self)
+    }
+
+    /// Read the value at the cursor as a Rust value.
+    ///
+    /// # Returns
+    ///
+    /// The value, or `MetaCursorError::OutOfRange` if it does not fit in `V`.
+    pub fn get<V: MetaValue>(&self) -> Result<V, MetaCursorError> {
+        V::read_from(self)
+    }
+}
+
+/// Kinds that hold a single primitive value.
+fn is_scalar(kind: ecs_meta_type_op_kind_t) -> bool {
+    kind == ecs_meta_type_op_kind_t_EcsOpEnum
+        || kind == ecs_meta_type_op_kind_t_EcsOpBitmask
+        || (ecs_meta_type_op_kind_t_EcsOpBool..=ecs_meta_type_op_kind_t_EcsOpId).contains(&kind)
+}
+
+/// Kinds that can be converted to a number without parsing.
+fn is_number(kind: ecs_meta_type_op_kind_t) -> bool {
+    is_scalar(kind)
+        && kind != ecs_meta_type_op_kind_t_EcsOpString
+        && kind != ecs_meta_type_op_kind_t_EcsOpEntity
+        && kind != ecs_meta_type_op_kind_t_EcsOpId
+}
+
+/// Kinds that hold an unsigned number.
+fn is_unsigned(kind: ecs_meta_type_op_kind_t) -> bool {
+    kind == ecs_meta_type_op_kind_t_EcsOpBitmask
+        || kind == ecs_meta_type_op_kind_t_EcsOpUPtr
+        || (ecs_meta_type_op_kind_t_EcsOpBool..=ecs_meta_type_op_kind_t_EcsOpU64).contains(&kind)
+}
+
+/// Rust types that can be assigned to and read from a [`MetaCursor`].
+pub trait MetaValue: Sized {
+    /// Assign the value to the value at the cursor.
+    fn assign_to(self, cursor: &mut MetaCursor) -> Result<(), MetaCursorError>;
+
+    /// Read the value at the cursor.
+    fn read_from(cursor: &MetaCursor) -> Result<Self, MetaCursorError>;
+}
+
+impl MetaValue for bool {
+    fn assign_to(self, cursor: &mut MetaCursor) -> Result<(), MetaCursorError> {
+        cursor.set_bool(self)
+    }
+
+    fn read_from(cursor: &MetaCursor) -> Result<Self, MetaCursorError> {
+        cursor.get_bool()
+    }
+}
+
+impl MetaValue for char {
+    fn assign_to(self, cursor: &mut MetaCursor) -> Result<(), MetaCursorError> {
+        cursor.set_char(self)
+    }
+
+    fn read_from(cursor: &MetaCursor) -> Result<Self, MetaCursorError> {
+        cursor.get_char()
+    }
+}
+
+macro_rules! impl_meta_value_int {
+    ($set:ident, $get:ident, $wide:ty => $($ty:ty),*) => {
+        $(
+            impl MetaValue for $ty {
+                fn assign_to(self, cursor: &mut MetaCursor) -> Result<(), MetaCursorError> {
+                    cursor.$set(self as $wide)
+                }
+
+                fn read_from(cursor: &MetaCursor) -> Result<Self, MetaCursorError> {
+                    <$ty>::try_from(cursor.$get()?).map_err(|_| MetaCursorError::OutOfRange)
+                }
+            }
+        )*
+    };
+}
+
+impl_meta_value_int!(set_int, get_int, i64 => i8, i16, i32, i64, isize);
+impl_meta_value_int!(set_uint, get_uint, u64 => u8, u16, u32, u64, usize);
+
+impl MetaValue for f32 {
+    fn assign_to(self, cursor: &mut MetaCursor) -> Result<(), MetaCursorError> {
+        cursor.set_float(self as f64)
+    }
+
+    fn read_from(cursor: &MetaCursor) -> Result<Self, MetaCursorError> {
+        Ok(cursor.get_float()? as f32)
+    }
+}
+
+impl MetaValue for f64 {
+    fn assign_to(self, cursor: &mut MetaCursor) -> Result<(), MetaCursorError> {
+        cursor.set_float(self)
+    }
+
+    fn read_from(cursor: &MetaCursor) -> Result<Self, MetaCursorError> {
+        cursor.get_float()
+    }
+}
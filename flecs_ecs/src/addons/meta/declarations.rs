@@ -2,7 +2,7 @@ use crate::{
     core::c_types::{
         EntityT, ECS_BOOL_T, ECS_BYTE_T, ECS_CHAR_T, ECS_CONSTANT, ECS_ENTITY_T, ECS_F32_T,
         ECS_F64_T, ECS_I16_T, ECS_I32_T, ECS_I64_T, ECS_I8_T, ECS_IPTR_T, ECS_QUANTITY,
-        ECS_STRING_T, ECS_U32_T, ECS_U64_T, ECS_UPTR_T,
+        ECS_STRING_T, ECS_U16_T, ECS_U32_T, ECS_U64_T, ECS_U8_T, ECS_UPTR_T,
     },
     sys::{
         ecs_bitmask_constant_t, ecs_bool_t, ecs_char_t, ecs_enum_constant_t, ecs_f32_t, ecs_f64_t,
@@ -53,6 +53,8 @@ pub struct EcsBitmask {
 pub const BOOL: EntityT = ECS_BOOL_T;
 pub const CHAR: EntityT = ECS_CHAR_T;
 pub const BYTE: EntityT = ECS_BYTE_T;
+pub const U8: EntityT = ECS_U8_T;
+pub const U16: EntityT = ECS_U16_T;
 pub const U32: EntityT = ECS_U32_T;
 pub const U64: EntityT = ECS_U64_T;
 pub const U_PTR: EntityT = ECS_UPTR_T;
@@ -0,0 +1,199 @@
+//! Registration of struct members with the meta addon.
+//!
+//! This is used by `#[derive(Component)]` when the type is annotated with `#[meta]`,
+//! but can also be used to describe the members of a type by hand.
+
+use std::{ffi::CStr, os::raw::c_void};
+
+#[cfg(any(debug_assertions, feature = "flecs_force_enable_ecs_asserts"))]
+use crate::core::FlecsErrorCode;
+use crate::{
+    core::{ComponentId, EntityT, IntoWorld},
+    ecs_assert,
+    sys::{
+        ecs_get_mut_id, ecs_lookup_child, ecs_member_t, ecs_set_id, ecs_struct_desc_t,
+        ecs_struct_init, EcsMember, EcsStruct, FLECS_IDEcsMemberID_, FLECS_IDEcsStructID_,
+    },
+};
+
+use super::{BOOL, F32, F64, I16, I32, I64, I8, I_PTR, U16, U32, U64, U8, U_PTR};
+
+/// Maximum number of members a struct can describe, as defined by `ecs_struct_desc_t`.
+pub const MAX_STRUCT_MEMBERS: usize = 32;
+
+/// Types that can be used as the type of a reflected struct member.
+///
+/// Implemented for the primitive types that have a flecs equivalent, for fixed size arrays
+/// of those and for every type implementing `ComponentId`, which allows nesting components.
+pub trait MetaMemberType {
+    /// Number of elements of the member, 0 for non-array members.
+    const COUNT: i32 = 0;
+
+    /// Returns the entity of the type that describes the member in the world.
+    fn meta_type_id(world: impl IntoWorld) -> EntityT;
+}
+
+macro_rules! impl_meta_member_type {
+    ($($ty:ty => $id:expr),* $(,)?) => {
+        $(
+            impl MetaMemberType for $ty {
+                #[inline(always)]
+                fn meta_type_id(_world: impl IntoWorld) -> EntityT {
+                    $id
+                }
+            }
+        )*
+    };
+}
+
+impl_meta_member_type!(
+    bool => BOOL,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    usize => U_PTR,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    isize => I_PTR,
+    f32 => F32,
+    f64 => F64,
+);
+
+impl<T: ComponentId> MetaMemberType for T {
+    #[inline(always)]
+    fn meta_type_id(world: impl IntoWorld) -> EntityT {
+        T::get_id(world)
+    }
+}
+
+impl<T: MetaMemberType, const N: usize> MetaMemberType for [T; N] {
+    const COUNT: i32 = N as i32;
+
+    #[inline(always)]
+    fn meta_type_id(world: impl IntoWorld) -> EntityT {
+        T::meta_type_id(world)
+    }
+}
+
+/// Description of a single struct member.
+#[derive(Debug, Clone, Copy)]
+pub struct MetaMember {
+    pub name: &'static CStr,
+    pub type_id: EntityT,
+    pub count: i32,
+    pub offset: usize,
+}
+
+impl MetaMember {
+    /// Create a member description for a field of type `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to resolve the member type in.
+    /// * `name` - The name of the member.
+    /// * `offset` - The offset of the member in the struct, usually obtained with `std::mem::offset_of!`.
+    pub fn new<T: MetaMemberType>(
+        world: impl IntoWorld,
+        name: &'static CStr,
+        offset: usize,
+    ) -> Self {
+        Self {
+            name,
+            type_id: T::meta_type_id(world),
+            count: T::COUNT,
+            offset,
+        }
+    }
+}
+
+/// Register the members of a struct type with the meta addon.
+///
+/// Members are registered in the order of `members`, which is the declaration order for
+/// `#[meta]` structs.
+///
+/// # Arguments
+///
+/// * `world` - The world to register the members in.
+/// * `id` - The (component) entity of the struct type.
+/// * `members` - The members of the struct.
+///
+/// # Returns
+///
+/// The entity of the struct type, 0 if flecs rejected the description.
+///
+/// # See also
+///
+/// * C API: `ecs_struct_init`
+#[doc(alias = "ecs_struct_init")]
+pub fn struct_init(world: impl IntoWorld, id: EntityT, members: &[MetaMember]) -> EntityT {
+    ecs_assert!(
+        members.len() <= MAX_STRUCT_MEMBERS,
+        FlecsErrorCode::OutOfRange,
+        "struct can describe at most {} members",
+        MAX_STRUCT_MEMBERS
+    );
+
+    let world = world.world_ptr_mut();
+
+    let mut desc = ecs_struct_desc_t {
+        entity: id,
+        ..Default::default()
+    };
+
+    for (desc_member, member) in desc.members.iter_mut().zip(members) {
+        *desc_member = ecs_member_t {
+            name: member.name.as_ptr(),
+            type_: member.type_id,
+            count: member.count,
+            offset: member.offset as i32,
+            ..Default::default()
+        };
+    }
+
+    let entity = unsafe { ecs_struct_init(world, &desc) };
+    if entity == 0 {
+        return 0;
+    }
+
+    // flecs treats a member with offset 0 as a request to lay out the members registered so far
+    // in order. If that member isn't declared first, its offset is reset and the explicit offsets
+    // of the members declared before it are set again, which also updates the type of the struct.
+    let zero_offset = members
+        .iter()
+        .position(|member| member.offset == 0)
+        .unwrap_or(0);
+    if zero_offset != 0 {
+        unsafe {
+            let struct_ = ecs_get_mut_id(world, entity, FLECS_IDEcsStructID_) as *mut EcsStruct;
+            let registered = (*struct_).members.array as *mut ecs_member_t;
+            let registered = &mut *registered.add(zero_offset);
+            registered.offset = 0;
+            let member = ecs_get_mut_id(world, registered.member, FLECS_IDEcsMemberID_);
+            (*(member as *mut EcsMember)).offset = 0;
+        }
+    }
+
+    for member in &members[..zero_offset] {
+        let value = EcsMember {
+            type_: member.type_id,
+            count: member.count,
+            unit: 0,
+            offset: member.offset as i32,
+        };
+        unsafe {
+            let member_entity = ecs_lookup_child(world, entity, member.name.as_ptr());
+            ecs_set_id(
+                world,
+                member_entity,
+                FLECS_IDEcsMemberID_,
+                std::mem::size_of::<EcsMember>(),
+                &value as *const EcsMember as *const c_void,
+            );
+        }
+    }
+
+    entity
+}
@@ -1,5 +1,9 @@
+mod cursor;
 mod declarations;
+mod members;
 mod opaque;
 
+pub use cursor::*;
 pub use declarations::*;
+pub use members::*;
 pub use opaque::*;
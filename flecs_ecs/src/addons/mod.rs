@@ -1,9 +1,15 @@
 #[cfg(feature = "flecs_app")]
 pub mod app;
 
+#[cfg(feature = "flecs_json")]
+pub mod json;
+
 #[cfg(feature = "flecs_meta")]
 pub mod meta;
 
+#[cfg(feature = "flecs_module")]
+pub mod module;
+
 #[cfg(feature = "flecs_system")]
 pub mod system;
 
@@ -12,3 +18,9 @@ pub mod pipeline;
 
 #[cfg(feature = "flecs_rules")]
 pub mod rules;
+
+#[cfg(feature = "flecs_snapshot")]
+pub mod snapshot;
+
+#[cfg(feature = "flecs_timer")]
+pub mod timer;
@@ -0,0 +1,110 @@
+//! Modules organize components, systems and more in reusable units of code.
+
+use std::cell::RefCell;
+
+use crate::{
+    core::{create_component_desc, world::World, ComponentId, EntityT, WorldT},
+    sys::{ecs_get_world, ecs_module_init, ecs_set_scope, ecs_set_with},
+};
+
+/// A module is a type that registers a set of components, systems and other entities with a world.
+///
+/// Modules are imported with [`World::import`]. The module type itself is registered as a
+/// component and its entity is named after the Rust path of the type. While the module is being
+/// imported the module entity is set as the scope of the world, so the components registered and
+/// the named entities (such as systems) created in [`Module::module`] are nested under the module
+/// path. Components registered there are named after the last segment of their Rust path.
+///
+/// The `Module` derive macro implements this trait for modules that import other modules and
+/// register a list of components.
+///
+/// # Example
+///
+#[cfg_attr(doctest, doc = " ````no_test")]
+/// ```
+///     #[derive(Component)]
+///     struct Physics;
+///
+///     impl Module for Physics {
+///         fn module(world: &World) {
+///             world.component::<Position>();
+///             world.component::<Velocity>();
+///         }
+///     }
+///
+///     let physics = world.import::<Physics>();
+/// ```
+///
+/// # See also
+///
+/// * C++ API: `world::module`
+#[doc(alias = "world::module")]
+pub trait Module: ComponentId {
+    /// Registers the contents of the module with the world.
+    ///
+    /// This is invoked once per world, the first time the module is imported.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to register the module contents with.
+    fn module(world: &World);
+}
+
+thread_local! {
+    /// worlds that are importing a module on this thread, innermost last. The flag is set while
+    /// the contents of a module are registered, and cleared while the module type itself is.
+    static IMPORTING: RefCell<Vec<(*mut WorldT, bool)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Test whether the contents of a module are being registered in the world on this thread.
+pub(crate) fn is_importing(world: *mut WorldT) -> bool {
+    let world = unsafe { ecs_get_world(world as *const _) as *mut WorldT };
+    IMPORTING.with(|importing| {
+        importing
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(importing_world, _)| *importing_world == world)
+            .is_some_and(|(_, is_importing)| *is_importing)
+    })
+}
+
+fn with_importing<R>(world: *mut WorldT, is_importing: bool, func: impl FnOnce() -> R) -> R {
+    let world = unsafe { ecs_get_world(world as *const _) as *mut WorldT };
+    IMPORTING.with(|importing| importing.borrow_mut().push((world, is_importing)));
+    let result = func();
+    IMPORTING.with(|importing| importing.borrow_mut().pop());
+    result
+}
+
+/// Register `M` as module with `ecs_module_init` and register its contents under it.
+///
+/// This follows the C++ import instead of calling `ecs_import`, which looks the module up by a
+/// path converted from a C name and doesn't find modules named after CamelCase Rust types.
+pub(crate) fn import<M: Module>(world: &World) -> EntityT {
+    let raw_world = world.raw_world;
+
+    unsafe {
+        let prev_scope = ecs_set_scope(raw_world, 0);
+        let prev_with = ecs_set_with(raw_world, 0);
+
+        // the module type is registered at its full path, even when imported by another module
+        let id = with_importing(raw_world, false, || M::get_id(raw_world));
+        let desc = create_component_desc(id, std::mem::zeroed());
+        let module = ecs_module_init(raw_world, std::ptr::null(), &desc);
+
+        ecs_set_scope(raw_world, module);
+        let result = with_importing(raw_world, true, || {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| M::module(world)))
+        });
+
+        ecs_set_with(raw_world, prev_with);
+        ecs_set_scope(raw_world, prev_scope);
+
+        if let Err(panic) = result {
+            std::panic::resume_unwind(panic);
+        }
+
+        module
+    }
+}
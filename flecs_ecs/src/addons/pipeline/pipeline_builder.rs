@@ -1,14 +1,12 @@
-use std::{
-    ffi::CStr,
-    ops::{Deref, DerefMut},
-};
+use std::ops::{Deref, DerefMut};
 
 use flecs_ecs_sys::{ecs_filter_desc_t, ecs_query_desc_t};
 
 use crate::{
     core::{
-        Builder, EntityT, FilterBuilderImpl, Filterable, Iterable, QueryBuilder, QueryBuilderImpl,
-        Term, TermBuilder, TermIdT, TermT, World, WorldT, SEPARATOR,
+        check_flecs_iterated_query, free_query_ctx, release_query_ctx, Builder, EntityT,
+        FilterBuilderImpl, Filterable, IntoCStr, Iterable, QueryBuilder, QueryBuilderImpl, Term,
+        TermBuilder, TermIdT, TermT, World, WorldT, SEPARATOR,
     },
     sys::{ecs_entity_desc_t, ecs_entity_init, ecs_pipeline_desc_t},
 };
@@ -115,7 +113,7 @@ where
     }
 
     //TODO fix this - not working as intended most likely
-    pub fn new_named(world: &World, name: &CStr) -> Self {
+    pub fn new_named(world: &World, name: impl IntoCStr) -> Self {
         let mut desc = Default::default();
         let mut obj = Self {
             desc,
@@ -123,13 +121,14 @@ where
             is_instanced: false,
         };
 
-        let entity_desc: ecs_entity_desc_t = ecs_entity_desc_t {
-            name: name.as_ptr(),
-            sep: SEPARATOR.as_ptr(),
-            ..Default::default()
-        };
-
-        obj.desc.entity = unsafe { ecs_entity_init(obj.world.raw_world, &entity_desc) };
+        obj.desc.entity = name.with_cstr(|name| {
+            let entity_desc = ecs_entity_desc_t {
+                name: name.as_ptr(),
+                sep: SEPARATOR.as_ptr(),
+                ..Default::default()
+            };
+            unsafe { ecs_entity_init(obj.world.raw_world, &entity_desc) }
+        });
         T::populate(&mut obj);
         obj
     }
@@ -179,7 +178,20 @@ where
     type BuiltType = Pipeline<'a, T>;
 
     fn build(&mut self) -> Self::BuiltType {
-        Pipeline::<T>::new(&self.world, self.desc)
+        check_flecs_iterated_query(&self.desc.query);
+
+        let pipeline = Pipeline::<T>::new(&self.world, self.desc);
+        release_query_ctx(&mut self.desc.query);
+        pipeline
+    }
+}
+
+impl<'a, T> Drop for PipelineBuilder<'a, T>
+where
+    T: Iterable<'a>,
+{
+    fn drop(&mut self) {
+        free_query_ctx(&mut self.desc.query);
     }
 }
 
@@ -1,18 +1,21 @@
-use std::{ffi::CStr, ops::Deref, os::raw::c_void};
+use std::{ops::Deref, os::raw::c_void};
 
 use flecs_ecs_sys::{
     ecs_filter_desc_t, ecs_get_entity, ecs_os_api, ecs_rule_find_var, ecs_rule_fini,
     ecs_rule_get_filter, ecs_rule_init, ecs_rule_iter, ecs_rule_next, ecs_rule_str, ecs_rule_t,
 };
 
-use crate::core::{Entity, FilterView, IntoWorld, IterAPI, IterOperations, Iterable, World};
+use crate::core::{
+    capture_log, Entity, FilterView, FlecsError, FlecsErrorCode, IntoCStr, IntoWorld, IterAPI,
+    IterOperations, Iterable, World, WorldRef,
+};
 
 pub struct Rule<'a, T>
 where
     T: Iterable<'a>,
 {
     rule: *mut ecs_rule_t,
-    pub world: World,
+    pub world: WorldRef,
     _phantom: std::marker::PhantomData<&'a T>,
 }
 
@@ -58,7 +61,7 @@ where
     #[doc(alias = "rule_base::rule")]
     pub fn new(world: &World, rule: *mut ecs_rule_t) -> Self {
         Self {
-            world: world.clone(),
+            world: world.handle(),
             rule,
             _phantom: std::marker::PhantomData,
         }
@@ -77,7 +80,7 @@ where
     #[doc(alias = "rule_base::rule")]
     pub fn new_from_desc(world: &World, desc: &mut ecs_filter_desc_t) -> Self {
         let obj = Self {
-            world: world.clone(),
+            world: world.handle(),
             rule: unsafe { ecs_rule_init(world.raw_world, desc) },
             _phantom: std::marker::PhantomData,
         };
@@ -91,6 +94,37 @@ where
         obj
     }
 
+    /// Create a new rule from a rule descriptor, returning an error instead of an invalid rule
+    /// when the descriptor is invalid, for example when its expression fails to parse.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to create the rule in
+    /// * `desc` - The rule descriptor to create the rule from
+    ///
+    /// # Returns
+    ///
+    /// The rule, or an error carrying the messages flecs logged while creating it.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `rule_base::rule`
+    #[doc(alias = "rule_base::rule")]
+    pub fn try_new_from_desc(
+        world: &World,
+        desc: &mut ecs_filter_desc_t,
+    ) -> Result<Self, FlecsError> {
+        let (rule, log) = capture_log(|| Self::new_from_desc(world, desc));
+        if rule.is_valid() {
+            Ok(rule)
+        } else {
+            Err(
+                FlecsError::new(FlecsErrorCode::InvalidParameter, "failed to create rule")
+                    .with_log(log),
+            )
+        }
+    }
+
     /// Returns whether the rule is valid
     ///
     /// # See also
@@ -139,8 +173,8 @@ where
         rust_string
     }
 
-    pub fn find_var(&self, name: &CStr) -> i32 {
-        unsafe { ecs_rule_find_var(self.rule, name.as_ptr()) }
+    pub fn find_var(&self, name: impl IntoCStr) -> i32 {
+        name.with_cstr(|name| unsafe { ecs_rule_find_var(self.rule, name.as_ptr()) })
     }
 }
 
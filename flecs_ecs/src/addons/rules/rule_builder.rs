@@ -1,10 +1,10 @@
-use std::{ffi::CStr, ops::Deref};
+use std::ops::Deref;
 
 use flecs_ecs_sys::{ecs_entity_desc_t, ecs_entity_init, ecs_filter_desc_t};
 
 use crate::core::{
-    Builder, FilterBuilder, FilterBuilderImpl, Filterable, Iterable, Term, TermBuilder, TermIdT,
-    TermT, World, WorldT, SEPARATOR,
+    Builder, FilterBuilder, FilterBuilderImpl, Filterable, FlecsError, IntoCStr, Iterable, Term,
+    TermBuilder, TermIdT, TermT, World, WorldT, SEPARATOR,
 };
 
 use super::Rule;
@@ -44,7 +44,7 @@ where
     #[doc(alias = "builder::builder")]
     pub fn new(world: &World) -> Self {
         let mut obj = Self {
-            filter_builder: FilterBuilder::new(world),
+            filter_builder: FilterBuilder::new_from_desc(world, &mut Default::default(), 0),
         };
 
         let entity_desc = ecs_entity_desc_t {
@@ -70,22 +70,33 @@ where
     ///
     /// * C++ API: `query_builder::query_builder`
     #[doc(alias = "query_builder::query_builder")]
-    pub fn new_named(world: &World, name: &CStr) -> Self {
+    pub fn new_named(world: &World, name: impl IntoCStr) -> Self {
         let mut obj = Self {
-            filter_builder: FilterBuilder::new_named(world, name),
+            filter_builder: FilterBuilder::new_from_desc(world, &mut Default::default(), 0),
         };
 
-        let entity_desc = ecs_entity_desc_t {
-            name: name.as_ptr(),
-            sep: SEPARATOR.as_ptr(),
-            root_sep: SEPARATOR.as_ptr(),
-            ..Default::default()
-        };
-
-        obj.filter_builder.desc.entity = unsafe { ecs_entity_init(world.raw_world, &entity_desc) };
+        obj.filter_builder.desc.entity = name.with_cstr(|name| {
+            let entity_desc = ecs_entity_desc_t {
+                name: name.as_ptr(),
+                sep: SEPARATOR.as_ptr(),
+                root_sep: SEPARATOR.as_ptr(),
+                ..Default::default()
+            };
+            unsafe { ecs_entity_init(world.raw_world, &entity_desc) }
+        });
         T::populate(&mut obj);
         obj
     }
+
+    /// Build the rule, returning an error instead of aborting when the builder describes an
+    /// invalid rule, for example an expression that fails to parse.
+    ///
+    /// # Returns
+    ///
+    /// The rule, or an error carrying the messages flecs logged while creating it.
+    pub fn try_build(&mut self) -> Result<Rule<'a, T>, FlecsError> {
+        Rule::<T>::try_new_from_desc(&self.filter_builder.world, &mut self.filter_builder.desc)
+    }
 }
 
 impl<'a, T> Filterable for RuleBuilder<'a, T>
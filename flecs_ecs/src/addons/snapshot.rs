@@ -0,0 +1,249 @@
+//! Snapshots make a copy of the world state that can be restored later.
+//!
+//! A snapshot stores the data of all (or a filtered set of) tables in the world. Restoring a snapshot
+//! replaces the current world data with the stored data, which makes snapshots useful for rollback and undo.
+
+use std::mem::MaybeUninit;
+
+use crate::{
+    core::{FlecsError, FlecsErrorCode, Iter, IterOperations, World},
+    sys::{
+        ecs_filter_desc_t, ecs_filter_fini, ecs_filter_init, ecs_filter_iter, ecs_filter_next,
+        ecs_filter_t, ecs_iter_t, ecs_snapshot_free, ecs_snapshot_iter, ecs_snapshot_next,
+        ecs_snapshot_restore, ecs_snapshot_t, ecs_snapshot_take, ecs_snapshot_take_w_iter,
+        ecs_table_has_flags, EcsAny, EcsFilterMatchDisabled, EcsFilterMatchPrefab,
+        EcsTableHasToggle, EcsTableHasUnion, ECS_FILTER_INIT,
+    },
+};
+
+/// A copy of the state of a world.
+///
+/// The snapshot is freed when it is dropped, unless it was restored.
+///
+/// # Limitations
+///
+/// Flecs resets the number of relationship targets (e.g. parents) it counts for a table when it
+/// frees or restores the snapshot data of the table. Deleting one of these entities afterwards,
+/// which also happens when the world is destroyed, fails a flecs assert. This includes the parents
+/// that flecs creates for the module path of a component, so snapshots of the entire world are
+/// affected in debug builds.
+///
+/// # See also
+///
+/// * C++ API: `snapshot`
+#[doc(alias = "snapshot")]
+pub struct Snapshot<'a> {
+    world: &'a World,
+    snapshot: *mut ecs_snapshot_t,
+}
+
+impl<'a> Snapshot<'a> {
+    /// Take a snapshot of the entire world.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to snapshot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world contains entities with union relationships or toggled components, which
+    /// snapshots do not store. Use [`Snapshot::try_new`] to handle this case.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `snapshot::take`
+    #[doc(alias = "snapshot::take")]
+    pub fn new(world: &'a World) -> Self {
+        Self::try_new(world).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Take a snapshot of the entire world, returning an error if the world contains entities that
+    /// can't be stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to snapshot.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot, or an error if the world contains entities with union relationships or toggled
+    /// components, which snapshots do not store.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `snapshot::take`
+    #[doc(alias = "snapshot::take")]
+    pub fn try_new(world: &'a World) -> Result<Self, FlecsError> {
+        unsafe {
+            let mut filter: ecs_filter_t = ECS_FILTER_INIT;
+            let mut desc: ecs_filter_desc_t = MaybeUninit::zeroed().assume_init();
+            desc.terms[0].id = EcsAny;
+            desc.flags = EcsFilterMatchPrefab | EcsFilterMatchDisabled;
+            desc.storage = &mut filter;
+
+            if ecs_filter_init(world.raw_world, &desc).is_null() {
+                return Err(FlecsError::new(
+                    FlecsErrorCode::InternalError,
+                    "failed to create the filter that checks the tables of the world",
+                ));
+            }
+            let mut iter = ecs_filter_iter(world.raw_world, &filter);
+            let unsupported = has_unsupported_table(&mut iter, ecs_filter_next);
+            ecs_filter_fini(&mut filter);
+            unsupported?;
+
+            Ok(Self {
+                world,
+                snapshot: ecs_snapshot_take(world.raw_world),
+            })
+        }
+    }
+
+    /// Take a snapshot of the tables matched by a filter, query or rule.
+    ///
+    /// Restoring a filtered snapshot only restores the data of the stored tables. Entities that
+    /// were created after the snapshot was taken are not deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to snapshot.
+    /// * `filter` - The filter, query or rule that selects the tables to store.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matched tables contain union relationships or toggled components, which
+    /// snapshots do not store. Use [`Snapshot::try_new_filtered`] to handle this case.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `snapshot::take`
+    #[doc(alias = "snapshot::take")]
+    pub fn new_filtered(world: &'a World, filter: &impl IterOperations) -> Self {
+        Self::try_new_filtered(world, filter).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Take a snapshot of the tables matched by a filter, query or rule, returning an error if the
+    /// tables contain entities that can't be stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to snapshot.
+    /// * `filter` - The filter, query or rule that selects the tables to store.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot, or an error if the matched tables contain union relationships or toggled
+    /// components, which snapshots do not store.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `snapshot::take`
+    #[doc(alias = "snapshot::take")]
+    pub fn try_new_filtered(
+        world: &'a World,
+        filter: &impl IterOperations,
+    ) -> Result<Self, FlecsError> {
+        unsafe {
+            let mut iter = filter.retrieve_iter();
+            has_unsupported_table(&mut iter, filter.iter_next_func())?;
+
+            let mut iter = filter.retrieve_iter();
+            Ok(Self {
+                world,
+                snapshot: ecs_snapshot_take_w_iter(&mut iter),
+            })
+        }
+    }
+
+    /// Restore the snapshot.
+    ///
+    /// This restores the world to the state it was in when the snapshot was taken. A snapshot can
+    /// only be restored once, as its data is moved back into the world.
+    ///
+    /// Entities created after the snapshot was taken are removed, but they must not have been used
+    /// as component, tag or relationship target (e.g. as parent). Register components before taking
+    /// the snapshot.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `snapshot::restore`
+    #[doc(alias = "snapshot::restore")]
+    pub fn restore(mut self) {
+        let world = self.world.raw_world;
+        unsafe {
+            ecs_snapshot_restore(world, self.snapshot);
+        }
+        // the snapshot is freed by restoring it
+        self.snapshot = std::ptr::null_mut();
+    }
+
+    /// Iterate the tables stored in the snapshot.
+    ///
+    /// The function is invoked once for each stored table. The iterator provides the table and
+    /// the entities that were stored for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function to invoke for each table.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `snapshot::iter`
+    #[doc(alias = "snapshot::iter")]
+    pub fn iter(&self, mut func: impl FnMut(&mut Iter)) {
+        self.iter_raw(|iter| {
+            let mut iter_t = unsafe { Iter::new(iter) };
+            func(&mut iter_t);
+        });
+    }
+
+    fn iter_raw(&self, mut func: impl FnMut(&mut ecs_iter_t)) {
+        unsafe {
+            let mut iter = ecs_snapshot_iter(self.snapshot);
+            iter.real_world = iter.world;
+
+            while ecs_snapshot_next(&mut iter) {
+                func(&mut iter);
+            }
+        }
+    }
+}
+
+/// Check that the tables returned by an iterator can be stored in a snapshot.
+///
+/// Freeing or restoring snapshot data also frees the union and toggle columns of the world table,
+/// so tables with such columns are rejected before a snapshot is taken. The iterator is
+/// exhausted.
+unsafe fn has_unsupported_table(
+    iter: &mut ecs_iter_t,
+    iter_next: unsafe extern "C" fn(*mut ecs_iter_t) -> bool,
+) -> Result<(), FlecsError> {
+    let mut unsupported = false;
+    while iter_next(iter) {
+        unsupported |= iter.count > 0
+            && (ecs_table_has_flags(iter.table, EcsTableHasUnion)
+                || ecs_table_has_flags(iter.table, EcsTableHasToggle));
+    }
+
+    if unsupported {
+        return Err(FlecsError::new(
+            FlecsErrorCode::Unsupported,
+            "snapshots do not support union relationships or toggled components",
+        ));
+    }
+    Ok(())
+}
+
+impl<'a> Drop for Snapshot<'a> {
+    /// Free the snapshot if it was not restored.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `snapshot::~snapshot`
+    #[doc(alias = "snapshot::~snapshot")]
+    fn drop(&mut self) {
+        if !self.snapshot.is_null() {
+            unsafe { ecs_snapshot_free(self.snapshot) };
+        }
+    }
+}
@@ -12,8 +12,10 @@ use std::{ffi::CStr, ops::Deref, os::raw::c_void};
 pub use system_builder::*;
 pub use system_runner_fluent::*;
 
+#[cfg(feature = "flecs_timer")]
+use crate::{addons::timer::Timer, core::IntoEntityId, sys::ecs_set_tick_source};
 use crate::{
-    core::{Entity, FTime, Query, TickSource, World},
+    core::{Entity, FTime, Query, TickSource, World, WorldRef},
     sys::{
         ecs_os_api, ecs_system_desc_t, ecs_system_get_ctx, ecs_system_get_query, ecs_system_init,
     },
@@ -22,7 +24,7 @@ use crate::{
 #[derive(Clone)]
 pub struct System {
     pub entity: Entity,
-    world: World,
+    world: WorldRef,
 }
 
 impl Deref for System {
@@ -65,7 +67,7 @@ impl System {
 
         Self {
             entity,
-            world: world.clone(),
+            world: world.handle(),
         }
     }
 
@@ -82,7 +84,7 @@ impl System {
     #[doc(alias = "system::system")]
     pub fn new_from_existing(world: &World, system_entity: Entity) -> Self {
         Self {
-            world: world.clone(),
+            world: world.handle(),
             entity: system_entity,
         }
     }
@@ -159,7 +161,7 @@ impl System {
     /// * C++ API: `system::run`
     #[doc(alias = "system::run")]
     #[inline]
-    pub fn run_dt_param(&self, delta_time: FTime, param: *mut c_void) -> SystemRunnerFluent {
+    pub fn run_dt_param(&self, delta_time: FTime, param: *mut c_void) -> SystemRunnerFluent<'_> {
         SystemRunnerFluent::new(&self.world, self.raw_id, 0, 0, delta_time, param)
     }
 
@@ -174,7 +176,7 @@ impl System {
     /// * C++ API: `system::run`
     #[doc(alias = "system::run")]
     #[inline]
-    pub fn run_dt(&self, delta_time: FTime) -> SystemRunnerFluent {
+    pub fn run_dt(&self, delta_time: FTime) -> SystemRunnerFluent<'_> {
         self.run_dt_param(delta_time, std::ptr::null_mut())
     }
 
@@ -185,7 +187,7 @@ impl System {
     /// * C++ API: `system::run`
     #[doc(alias = "system::run")]
     #[inline]
-    pub fn run(&self) -> SystemRunnerFluent {
+    pub fn run(&self) -> SystemRunnerFluent<'_> {
         self.run_dt_param(0.0, std::ptr::null_mut())
     }
 
@@ -208,7 +210,7 @@ impl System {
         stage_count: i32,
         delta_time: FTime,
         param: *mut c_void,
-    ) -> SystemRunnerFluent {
+    ) -> SystemRunnerFluent<'_> {
         SystemRunnerFluent::new(
             &self.world,
             self.raw_id,
@@ -219,3 +221,112 @@ impl System {
         )
     }
 }
+
+/// Timer mixin implementation
+#[cfg(feature = "flecs_timer")]
+impl System {
+    /// Set the interval of the system.
+    /// The system will run every time the interval expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The interval in seconds.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `system::interval`
+    #[doc(alias = "system::interval")]
+    pub fn set_interval(&self, interval: FTime) {
+        self.as_timer().interval(interval);
+    }
+
+    /// Get the interval of the system.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `system::interval`
+    #[doc(alias = "system::interval")]
+    pub fn get_interval(&self) -> FTime {
+        self.as_timer().get_interval()
+    }
+
+    /// Set the timeout of the system.
+    /// The system will run once when the timeout expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The timeout in seconds.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `system::timeout`
+    #[doc(alias = "system::timeout")]
+    pub fn set_timeout(&self, timeout: FTime) {
+        self.as_timer().timeout(timeout);
+    }
+
+    /// Get the timeout of the system.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `system::timeout`
+    #[doc(alias = "system::timeout")]
+    pub fn get_timeout(&self) -> FTime {
+        self.as_timer().get_timeout()
+    }
+
+    /// Set the rate of the system.
+    /// The system will run at a multiple of the provided tick source.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - The multiple of the tick source at which to run the system.
+    /// * `tick_source` - The tick source. Use 0 to run at a multiple of the frame rate.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `system::rate`
+    #[doc(alias = "system::rate")]
+    pub fn set_rate(&self, rate: i32, tick_source: impl IntoEntityId) {
+        self.as_timer().rate(rate, tick_source);
+    }
+
+    /// Start the timer of the system.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `system::start`
+    #[doc(alias = "system::start")]
+    pub fn start(&self) {
+        self.as_timer().start();
+    }
+
+    /// Stop the timer of the system.
+    /// A stopped system does not run until it is started again.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `system::stop`
+    #[doc(alias = "system::stop")]
+    pub fn stop(&self) {
+        self.as_timer().stop();
+    }
+
+    /// Set an external tick source for the system.
+    ///
+    /// # Arguments
+    ///
+    /// * `tick_source` - The tick source, such as a timer.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `system::set_tick_source`
+    #[doc(alias = "system::set_tick_source")]
+    pub fn set_tick_source(&self, tick_source: impl IntoEntityId) {
+        unsafe { ecs_set_tick_source(self.world.raw_world, self.raw_id, tick_source.get_id()) };
+    }
+
+    fn as_timer(&self) -> Timer {
+        Timer::new_from_existing(&self.world, self.raw_id)
+    }
+}
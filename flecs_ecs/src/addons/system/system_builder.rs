@@ -1,6 +1,5 @@
 //! Systems are a query + function that can be ran manually or by a pipeline.
 use std::{
-    ffi::CStr,
     ops::{Deref, DerefMut},
     os::raw::c_void,
 };
@@ -14,10 +13,13 @@ use crate::{
         implement_reactor_api,
         iterable::{Filterable, Iterable},
         private::internal_ReactorAPI,
-        query_builder::{QueryBuilder, QueryBuilderImpl},
+        query_builder::{
+            check_flecs_iterated_query, free_query_ctx, release_query_ctx, QueryBuilder,
+            QueryBuilderImpl,
+        },
         term::{Term, TermBuilder},
         world::World,
-        Builder, IntoEntityId, ReactorAPI, ECS_ON_UPDATE,
+        Builder, IntoCStr, IntoEntityId, ReactorAPI, ECS_ON_UPDATE,
     },
     sys::{
         ecs_add_id, ecs_entity_desc_t, ecs_entity_init, ecs_filter_desc_t, ecs_get_target,
@@ -123,20 +125,22 @@ where
         obj
     }
 
-    pub fn new_named(world: &World, name: &CStr) -> Self {
+    pub fn new_named(world: &World, name: impl IntoCStr) -> Self {
         let mut desc = Default::default();
         let mut obj = Self {
             desc,
             query_builder: QueryBuilder::<T>::new_from_desc(world, &mut desc.query),
             is_instanced: false,
         };
-        let entity_desc: ecs_entity_desc_t = ecs_entity_desc_t {
-            name: name.as_ptr(),
-            sep: SEPARATOR.as_ptr(),
-            root_sep: SEPARATOR.as_ptr(),
-            ..Default::default()
-        };
-        obj.desc.entity = unsafe { ecs_entity_init(obj.world.raw_world, &entity_desc) };
+        obj.desc.entity = name.with_cstr(|name| {
+            let entity_desc = ecs_entity_desc_t {
+                name: name.as_ptr(),
+                sep: SEPARATOR.as_ptr(),
+                root_sep: SEPARATOR.as_ptr(),
+                ..Default::default()
+            };
+            unsafe { ecs_entity_init(obj.world.raw_world, &entity_desc) }
+        });
         T::populate(&mut obj);
 
         #[cfg(feature = "flecs_pipeline")]
@@ -400,7 +404,22 @@ where
     /// * C++ API: `node_builder::build`
     #[doc(alias = "node_builder::build")]
     fn build(&mut self) -> Self::BuiltType {
-        System::new(&self.world, self.desc, self.is_instanced)
+        check_flecs_iterated_query(&self.desc.query);
+
+        let system = System::new(&self.world, self.desc, self.is_instanced);
+        if system.raw_id != 0 {
+            release_query_ctx(&mut self.desc.query);
+        }
+        system
+    }
+}
+
+impl<'a, T> Drop for SystemBuilder<'a, T>
+where
+    T: Iterable<'a>,
+{
+    fn drop(&mut self) {
+        free_query_ctx(&mut self.desc.query);
     }
 }
 
@@ -1,12 +1,12 @@
-use std::os::raw::c_void;
+use std::{marker::PhantomData, os::raw::c_void};
 
 use crate::{
-    core::{c_types::EntityT, world::World, FTime},
+    core::{c_types::EntityT, world::World, world_ref::WorldRef, FTime, IntoWorld, Stage},
     sys::{ecs_run_w_filter, ecs_run_worker},
 };
 
-pub struct SystemRunnerFluent {
-    stage: World,
+pub struct SystemRunnerFluent<'a> {
+    stage: WorldRef,
     id: EntityT,
     stage_current: i32,
     stage_count: i32,
@@ -14,9 +14,10 @@ pub struct SystemRunnerFluent {
     limit: i32,
     delta_time: FTime,
     param: *mut c_void,
+    _stage: PhantomData<&'a Stage<'a>>,
 }
 
-impl SystemRunnerFluent {
+impl<'a> SystemRunnerFluent<'a> {
     pub fn new(
         world: &World,
         id: EntityT,
@@ -26,7 +27,7 @@ impl SystemRunnerFluent {
         param: *mut c_void,
     ) -> Self {
         Self {
-            stage: world.clone(),
+            stage: world.handle(),
             id,
             stage_current,
             stage_count,
@@ -34,6 +35,7 @@ impl SystemRunnerFluent {
             limit: 0,
             delta_time,
             param,
+            _stage: PhantomData,
         }
     }
 
@@ -47,13 +49,14 @@ impl SystemRunnerFluent {
         self
     }
 
-    pub fn stage(&mut self, stage: &mut World) -> &mut Self {
-        self.stage = stage.clone();
+    /// Run the system on a stage.
+    pub fn stage(&mut self, stage: &'a Stage<'a>) -> &mut Self {
+        self.stage = World::new_wrap_raw_world(stage.world_ptr_mut());
         self
     }
 }
 
-impl Drop for SystemRunnerFluent {
+impl Drop for SystemRunnerFluent<'_> {
     fn drop(&mut self) {
         if self.stage_count != 0 {
             unsafe {
@@ -0,0 +1,197 @@
+//! Timers and rate filters are tick sources which can be used to run systems at a configurable interval or rate.
+//!
+//! A timer is an entity with an `EcsTimer` component and a `TickSource` component. Systems that use the timer as
+//! their tick source only run when the timer ticks.
+
+use std::ops::Deref;
+
+use crate::{
+    core::{
+        c_types::{EntityT, FTimeT},
+        Entity, IntoEntityId, IntoEntityIdExt, IntoWorld,
+    },
+    sys::{
+        ecs_get_interval, ecs_get_timeout, ecs_reset_timer, ecs_set_interval, ecs_set_rate,
+        ecs_set_timeout, ecs_start_timer, ecs_stop_timer,
+    },
+};
+
+/// A timer or rate filter which can be used as tick source for systems.
+#[derive(Clone, Copy)]
+pub struct Timer {
+    pub entity: Entity,
+}
+
+impl Deref for Timer {
+    type Target = Entity;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.entity
+    }
+}
+
+impl Timer {
+    /// Create a new timer entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to create the timer in.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `timer::timer`
+    #[doc(alias = "timer::timer")]
+    pub fn new(world: impl IntoWorld) -> Self {
+        Self {
+            entity: Entity::new(world),
+        }
+    }
+
+    /// Wrap an existing entity in a timer object.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world the entity is in.
+    /// * `id` - The id of the entity to use as timer.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `timer::timer`
+    #[doc(alias = "timer::timer")]
+    pub fn new_from_existing(world: impl IntoWorld, id: impl IntoEntityId) -> Self {
+        Self {
+            entity: Entity::new_from_existing_raw(world, id.get_id()),
+        }
+    }
+
+    /// Set the timer interval.
+    /// The timer will tick every time the interval expires. If the entity already has a timer,
+    /// the time value of the timer is reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The interval in seconds.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `timer::interval`
+    #[doc(alias = "timer::interval")]
+    pub fn interval(self, interval: FTimeT) -> Self {
+        unsafe { ecs_set_interval(self.world, self.raw_id, interval) };
+        self
+    }
+
+    /// Get the timer interval.
+    ///
+    /// # Returns
+    ///
+    /// The interval in seconds, or 0 if the entity is not a timer.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `timer::interval`
+    #[doc(alias = "timer::interval")]
+    pub fn get_interval(&self) -> FTimeT {
+        unsafe { ecs_get_interval(self.world, self.raw_id) }
+    }
+
+    /// Set the timer timeout.
+    /// The timer will tick once when the timeout expires, after which it stops.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The timeout in seconds.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `timer::timeout`
+    #[doc(alias = "timer::timeout")]
+    pub fn timeout(self, timeout: FTimeT) -> Self {
+        unsafe { ecs_set_timeout(self.world, self.raw_id, timeout) };
+        self
+    }
+
+    /// Get the timer timeout.
+    ///
+    /// # Returns
+    ///
+    /// The timeout in seconds, or 0 if the entity is not a timer or the timeout already expired.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `timer::timeout`
+    #[doc(alias = "timer::timeout")]
+    pub fn get_timeout(&self) -> FTimeT {
+        unsafe { ecs_get_timeout(self.world, self.raw_id) }
+    }
+
+    /// Turn the timer into a rate filter.
+    /// A rate filter ticks at a multiple of its source, which can be any tick source, including
+    /// another timer or a system.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - The multiple of the source at which to tick.
+    /// * `tick_source` - The tick source to sample. Use 0 to sample frames.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `timer::rate`
+    #[doc(alias = "timer::rate")]
+    pub fn rate(self, rate: i32, tick_source: impl IntoEntityId) -> Self {
+        unsafe { ecs_set_rate(self.world, self.raw_id, rate, tick_source.get_id()) };
+        self
+    }
+
+    /// Start the timer.
+    /// This resets the time value of the timer and (re)starts it.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `timer::start`
+    #[doc(alias = "timer::start")]
+    pub fn start(self) -> Self {
+        unsafe { ecs_start_timer(self.world, self.raw_id) };
+        self
+    }
+
+    /// Stop the timer.
+    /// A stopped timer does not tick until it is started again.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `timer::stop`
+    #[doc(alias = "timer::stop")]
+    pub fn stop(self) -> Self {
+        unsafe { ecs_stop_timer(self.world, self.raw_id) };
+        self
+    }
+
+    /// Reset the time value of the timer to 0.
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_reset_timer`
+    #[doc(alias = "ecs_reset_timer")]
+    pub fn reset(self) -> Self {
+        unsafe { ecs_reset_timer(self.world, self.raw_id) };
+        self
+    }
+}
+
+impl IntoEntityId for Timer {
+    #[inline]
+    fn get_id(&self) -> EntityT {
+        self.entity.raw_id
+    }
+}
+
+impl IntoEntityIdExt for Timer {
+    const IS_PAIR: bool = false;
+
+    #[inline]
+    fn get_id(&self) -> EntityT {
+        self.entity.raw_id
+    }
+}
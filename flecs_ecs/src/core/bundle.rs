@@ -0,0 +1,545 @@
+//! Bundles of components that are added to an entity together.
+
+use std::alloc::{self, Layout};
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::{Arc, Mutex};
+
+use crate::sys::{
+    ecs_add_id, ecs_bulk_desc_t, ecs_bulk_init, ecs_commit, ecs_defer_begin, ecs_defer_end,
+    ecs_delete, ecs_get_mut_id, ecs_get_name, ecs_get_table, ecs_get_type_info, ecs_get_world,
+    ecs_get_world_info, ecs_is_deferred, ecs_modified_id, ecs_new_id, ecs_remove_id,
+    ecs_table_find, ecs_table_get_type, ecs_type_t,
+};
+
+use super::{
+    c_types::{EntityT, IdT, TableT, WorldT},
+    component_registration::{bundle_info, ComponentId},
+    set_helper,
+};
+
+/// A set of components that is added to an entity in a single operation.
+///
+/// Adding a bundle moves the entity to the table with all components of the bundle at once,
+/// instead of moving it once for every component like chaining `set` calls does. Bundles are
+/// implemented for tuples of up to 12 components, and can be derived for structs whose fields
+/// are components with `#[derive(Bundle)]`. Fields marked with `#[bundle]` are nested bundles.
+///
+/// Empty types in a bundle are added as tags. When the world is deferred the components are
+/// added one by one, as the entity is only moved when the commands are merged. A bundle can
+/// contain a component only once, using a bundle with duplicate components panics.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(Bundle)]
+/// struct Particle {
+///     position: Position,
+///     velocity: Velocity,
+/// }
+///
+/// let entity = world.spawn(Particle { position, velocity });
+/// entity.insert((Mass { value: 1.0 }, Visible));
+/// entity.remove_bundle::<Particle>();
+/// ```
+pub trait Bundle: 'static {
+    /// Register the components of the bundle and append their ids to `ids`.
+    #[doc(hidden)]
+    fn append_ids(world: *mut WorldT, ids: &mut Vec<IdT>);
+
+    /// Move the values of the bundle into the components of the entity.
+    ///
+    /// # Safety
+    ///
+    /// Unless the world is deferred, the entity must have all components of the bundle.
+    #[doc(hidden)]
+    unsafe fn write_values(self, world: *mut WorldT, entity: EntityT);
+
+    /// Store a pointer to every component value of the bundle in `data`, at the index of its id in
+    /// the sorted `ids`. Tags are skipped.
+    #[doc(hidden)]
+    fn component_ptrs(&mut self, world: *mut WorldT, ids: &[IdT], data: &mut [*mut c_void]);
+}
+
+/// Move a component value of a bundle into the entity.
+///
+/// Used by implementations of [`Bundle::write_values`].
+///
+/// # Safety
+///
+/// Unless the world is deferred, the entity must have the component.
+#[doc(hidden)]
+#[inline]
+pub unsafe fn write_bundle_component<T: ComponentId>(
+    world: *mut WorldT,
+    entity: EntityT,
+    value: T,
+) {
+    let id = T::get_id(world);
+    if std::mem::size_of::<T>() == 0 {
+        if ecs_is_deferred(world) {
+            ecs_add_id(world, entity, id);
+        }
+    } else if ecs_is_deferred(world) {
+        set_helper(world, entity, value, id);
+    } else {
+        // replaces the value constructed when the entity moved to its table
+        *(ecs_get_mut_id(world, entity, id) as *mut T) = value;
+        ecs_modified_id(world, entity, id);
+    }
+}
+
+/// Store a pointer to a component value of a bundle in `data`, at the index of its id in `ids`.
+///
+/// Used by implementations of [`Bundle::component_ptrs`].
+#[doc(hidden)]
+#[inline]
+pub fn bundle_component_ptr<T: ComponentId>(
+    world: *mut WorldT,
+    value: &mut T,
+    ids: &[IdT],
+    data: &mut [*mut c_void],
+) {
+    if std::mem::size_of::<T>() == 0 {
+        return;
+    }
+    if let Ok(index) = ids.binary_search(&T::get_id(world)) {
+        data[index] = value as *mut T as *mut c_void;
+    }
+}
+
+/// The components of a bundle type in a world, with the tables an entity moves to when they are
+/// added or removed.
+pub(crate) struct BundleInfo {
+    /// the sorted ids of the components.
+    ids: Box<[IdT]>,
+    tables: Mutex<BundleTables>,
+}
+
+#[derive(Default)]
+struct BundleTables {
+    /// the number of tables the world had deleted when the tables were cached, as a new table can
+    /// reuse the address of a deleted one.
+    deleted_tables: i64,
+    /// the table with the components added and the added ids, by source table.
+    added: HashMap<usize, (usize, Arc<[IdT]>)>,
+    /// the table without the components and the removed ids, by source table.
+    removed: HashMap<usize, (usize, Arc<[IdT]>)>,
+}
+
+impl BundleInfo {
+    /// Collect the components of the bundle type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bundle contains a component more than once, as only one of the values could
+    /// be moved into the entity.
+    pub(crate) fn new<B: Bundle>(world: *mut WorldT) -> Self {
+        let mut ids = Vec::new();
+        B::append_ids(world, &mut ids);
+        ids.sort_unstable();
+        if let Some(pair) = ids.windows(2).find(|pair| pair[0] == pair[1]) {
+            let name = unsafe { ecs_get_name(world, pair[0]) };
+            let name = if name.is_null() {
+                pair[0].to_string()
+            } else {
+                unsafe { CStr::from_ptr(name) }
+                    .to_string_lossy()
+                    .into_owned()
+            };
+            panic!(
+                "bundle `{}` contains component `{}` more than once",
+                std::any::type_name::<B>(),
+                name
+            );
+        }
+        Self {
+            ids: ids.into(),
+            tables: Mutex::default(),
+        }
+    }
+
+    /// Find the table an entity in `table` moves to when the components are added, and the ids
+    /// that are added.
+    fn table_with(&self, world: *mut WorldT, table: *mut TableT) -> (*mut TableT, Arc<[IdT]>) {
+        self.cached_table(world, table, true)
+    }
+
+    /// Find the table an entity in `table` moves to when the components are removed, and the ids
+    /// that are removed.
+    fn table_without(&self, world: *mut WorldT, table: *mut TableT) -> (*mut TableT, Arc<[IdT]>) {
+        self.cached_table(world, table, false)
+    }
+
+    fn cached_table(
+        &self,
+        world: *mut WorldT,
+        table: *mut TableT,
+        add: bool,
+    ) -> (*mut TableT, Arc<[IdT]>) {
+        let deleted_tables = unsafe {
+            (*ecs_get_world_info(ecs_get_world(world as *const c_void))).table_delete_total
+        };
+        let mut tables = self.tables.lock().unwrap();
+        if tables.deleted_tables != deleted_tables {
+            tables.added.clear();
+            tables.removed.clear();
+            tables.deleted_tables = deleted_tables;
+        }
+
+        let cache = if add {
+            &mut tables.added
+        } else {
+            &mut tables.removed
+        };
+        let (target, ids) = cache.entry(table as usize).or_insert_with(|| {
+            let (target, ids) = if add {
+                table_with_ids(world, table, &self.ids)
+            } else {
+                table_without_ids(world, table, &self.ids)
+            };
+            (target as usize, ids.into())
+        });
+        (*target as *mut TableT, ids.clone())
+    }
+}
+
+/// Get the ids of the table type.
+fn table_type_ids<'a>(table: *mut TableT) -> &'a [IdT] {
+    if table.is_null() {
+        return &[];
+    }
+    unsafe {
+        let table_type = &*ecs_table_get_type(table);
+        if table_type.count == 0 {
+            &[]
+        } else {
+            std::slice::from_raw_parts(table_type.array, table_type.count as usize)
+        }
+    }
+}
+
+/// Find the table with the ids of `table` and `ids`, and the ids `table` doesn't have yet.
+///
+/// The table is looked up by its full type, so no tables are created for the intermediate
+/// combinations of components.
+fn table_with_ids(world: *mut WorldT, table: *mut TableT, ids: &[IdT]) -> (*mut TableT, Vec<IdT>) {
+    let current = table_type_ids(table);
+    let mut added: Vec<IdT> = ids
+        .iter()
+        .copied()
+        .filter(|id| !current.contains(id))
+        .collect();
+    added.sort_unstable();
+    added.dedup();
+    if added.is_empty() {
+        return (table, added);
+    }
+
+    let mut type_ids = [current, &added].concat();
+    type_ids.sort_unstable();
+    let table = unsafe { ecs_table_find(world, type_ids.as_ptr(), type_ids.len() as i32) };
+    (table, added)
+}
+
+/// Find the table with the ids of `table` except `ids`, and the ids removed from `table`.
+fn table_without_ids(
+    world: *mut WorldT,
+    table: *mut TableT,
+    ids: &[IdT],
+) -> (*mut TableT, Vec<IdT>) {
+    let current = table_type_ids(table);
+    let (mut removed, remaining): (Vec<IdT>, Vec<IdT>) =
+        current.iter().partition(|id| ids.contains(id));
+    if removed.is_empty() {
+        return (table, removed);
+    }
+
+    removed.dedup();
+    let table = unsafe { ecs_table_find(world, remaining.as_ptr(), remaining.len() as i32) };
+    (table, removed)
+}
+
+fn type_from_ids(ids: &[IdT]) -> ecs_type_t {
+    ecs_type_t {
+        // flecs doesn't modify the ids of a type it is passed
+        array: ids.as_ptr() as *mut IdT,
+        count: ids.len() as i32,
+    }
+}
+
+/// Test whether entities can be created with [`ecs_bulk_init`], which requires the actual world
+/// outside of deferred mode.
+fn can_bulk_init(world: *mut WorldT) -> bool {
+    ptr::eq(unsafe { ecs_get_world(world as *const c_void) }, world)
+        && !unsafe { ecs_is_deferred(world) }
+}
+
+/// Destruct the component values in `data` after [`ecs_bulk_init`] moved `count` values out of
+/// every column.
+///
+/// Components with a move hook leave a value behind that must still be destructed, the values of
+/// other components were copied bitwise and are owned by the table.
+///
+/// # Safety
+///
+/// Every non-null pointer in `data` must point to `count` moved values of the component with the
+/// id at the same index in `ids`.
+unsafe fn destruct_moved_values(world: *mut WorldT, ids: &[IdT], data: &[*mut c_void], count: i32) {
+    for (&id, &values) in ids.iter().zip(data) {
+        if values.is_null() {
+            continue;
+        }
+        let type_info = ecs_get_type_info(world, id);
+        if let (Some(_), Some(dtor)) = ((*type_info).hooks.move_, (*type_info).hooks.dtor) {
+            dtor(values, count, type_info);
+        }
+    }
+}
+
+/// The number of component pointers of a bundle that are stored without allocating.
+const INLINE_BUNDLE_COMPONENTS: usize = 32;
+
+/// Create an entity with the components of a bundle.
+///
+/// Outside of deferred mode the entity is created in the table with all components of the bundle,
+/// and the values are moved into the table by flecs, which emits a single `OnSet` per component.
+pub(crate) fn spawn_bundle<B: Bundle>(world: *mut WorldT, bundle: B) -> EntityT {
+    let info = bundle_info::<B>(world);
+    let table = if can_bulk_init(world) {
+        info.table_with(world, ptr::null_mut()).0
+    } else {
+        ptr::null_mut()
+    };
+
+    if table.is_null() {
+        unsafe {
+            let entity = ecs_new_id(world);
+            bundle.write_values(world, entity);
+            return entity;
+        }
+    }
+
+    let ids = table_type_ids(table);
+    let mut inline_data = [ptr::null_mut(); INLINE_BUNDLE_COMPONENTS];
+    let mut heap_data = Vec::new();
+    let data = if ids.len() <= INLINE_BUNDLE_COMPONENTS {
+        &mut inline_data[..ids.len()]
+    } else {
+        heap_data.resize(ids.len(), ptr::null_mut());
+        &mut heap_data[..]
+    };
+
+    // the values are moved out of the bundle by flecs
+    let mut bundle = std::mem::ManuallyDrop::new(bundle);
+    bundle.component_ptrs(world, ids, data);
+    let desc = ecs_bulk_desc_t {
+        count: 1,
+        table,
+        data: data.as_mut_ptr(),
+        ..Default::default()
+    };
+    unsafe {
+        let entity = *ecs_bulk_init(world, &desc);
+        destruct_moved_values(world, ids, data, 1);
+        entity
+    }
+}
+
+/// The values of one component for a batch of bundles, from which flecs moves them into the table.
+struct BundleColumn {
+    values: *mut u8,
+    size: usize,
+    layout: Layout,
+}
+
+impl BundleColumn {
+    /// Allocate a column for `count` values of the component `id`. Tags get no column.
+    fn new(world: *mut WorldT, id: IdT, count: usize) -> Self {
+        let type_info = unsafe { ecs_get_type_info(world, id) };
+        let size = if type_info.is_null() {
+            0
+        } else {
+            unsafe { (*type_info).size as usize }
+        };
+        if size == 0 {
+            return Self {
+                values: ptr::null_mut(),
+                size,
+                layout: Layout::new::<()>(),
+            };
+        }
+
+        let alignment = unsafe { (*type_info).alignment as usize };
+        let layout = Layout::from_size_align(size * count, alignment).unwrap();
+        let values = unsafe { alloc::alloc(layout) };
+        if values.is_null() {
+            alloc::handle_alloc_error(layout);
+        }
+        Self {
+            values,
+            size,
+            layout,
+        }
+    }
+}
+
+impl Drop for BundleColumn {
+    fn drop(&mut self) {
+        if !self.values.is_null() {
+            unsafe { alloc::dealloc(self.values, self.layout) };
+        }
+    }
+}
+
+/// Create an entity for every bundle.
+///
+/// The values of the bundles are moved into a column per component, after which all entities are
+/// created in the table with the components of the bundle at once, and flecs moves the columns
+/// into the table.
+pub(crate) fn spawn_bundle_batch<B: Bundle>(world: *mut WorldT, bundles: Vec<B>) -> Vec<EntityT> {
+    let table = if can_bulk_init(world) {
+        bundle_info::<B>(world).table_with(world, ptr::null_mut()).0
+    } else {
+        ptr::null_mut()
+    };
+    if table.is_null() || bundles.is_empty() {
+        return bundles
+            .into_iter()
+            .map(|bundle| spawn_bundle(world, bundle))
+            .collect();
+    }
+
+    let ids = table_type_ids(table);
+    let count = bundles.len();
+    let columns: Vec<BundleColumn> = ids
+        .iter()
+        .map(|&id| BundleColumn::new(world, id, count))
+        .collect();
+    let mut data = vec![ptr::null_mut(); ids.len()];
+    let mut values = vec![ptr::null_mut(); ids.len()];
+    for (row, bundle) in bundles.into_iter().enumerate() {
+        // the values are moved out of the bundle into the columns
+        let mut bundle = std::mem::ManuallyDrop::new(bundle);
+        bundle.component_ptrs(world, ids, &mut values);
+        for ((column, value), data) in columns.iter().zip(&values).zip(&mut data) {
+            if column.values.is_null() || value.is_null() {
+                continue;
+            }
+            unsafe {
+                let dst = column.values.add(row * column.size);
+                ptr::copy_nonoverlapping(*value as *const u8, dst, column.size);
+            }
+            *data = column.values as *mut c_void;
+        }
+    }
+
+    let desc = ecs_bulk_desc_t {
+        count: count as i32,
+        table,
+        data: data.as_mut_ptr(),
+        ..Default::default()
+    };
+    unsafe {
+        // the returned array is owned by flecs and can change when entities are created
+        let entities = std::slice::from_raw_parts(ecs_bulk_init(world, &desc), count).to_vec();
+        destruct_moved_values(world, ids, &data, count as i32);
+        entities
+    }
+}
+
+/// Delete entities with the operations deferred while the entities are visited.
+///
+/// The deletes are flushed together once all entities were visited, but flecs still deletes the
+/// entities one at a time.
+pub(crate) fn despawn_batch(world: *mut WorldT, entities: impl IntoIterator<Item = EntityT>) {
+    unsafe {
+        ecs_defer_begin(world);
+        for entity in entities {
+            ecs_delete(world, entity);
+        }
+        ecs_defer_end(world);
+    }
+}
+
+/// Add the components of a bundle to an existing entity.
+pub(crate) fn insert_bundle<B: Bundle>(world: *mut WorldT, entity: EntityT, bundle: B) {
+    let info = bundle_info::<B>(world);
+    unsafe {
+        if !ecs_is_deferred(world) {
+            let src = ecs_get_table(world, entity);
+            let (table, added) = info.table_with(world, src);
+            if table != src {
+                let added = type_from_ids(&added);
+                ecs_commit(world, entity, ptr::null_mut(), table, &added, ptr::null());
+            }
+        }
+        bundle.write_values(world, entity);
+    }
+}
+
+/// Remove the components of a bundle from an entity.
+pub(crate) fn remove_bundle<B: Bundle>(world: *mut WorldT, entity: EntityT) {
+    let info = bundle_info::<B>(world);
+    unsafe {
+        if ecs_is_deferred(world) {
+            for &id in info.ids.iter() {
+                ecs_remove_id(world, entity, id);
+            }
+            return;
+        }
+
+        let src = ecs_get_table(world, entity);
+        let (table, removed) = info.table_without(world, src);
+        if table != src {
+            let removed = type_from_ids(&removed);
+            ecs_commit(world, entity, ptr::null_mut(), table, ptr::null(), &removed);
+        }
+    }
+}
+
+impl Bundle for () {
+    fn append_ids(_world: *mut WorldT, _ids: &mut Vec<IdT>) {}
+
+    unsafe fn write_values(self, _world: *mut WorldT, _entity: EntityT) {}
+
+    fn component_ptrs(&mut self, _world: *mut WorldT, _ids: &[IdT], _data: &mut [*mut c_void]) {}
+}
+
+macro_rules! impl_bundle {
+    ($($t:ident: $index:tt),*) => {
+        impl<$($t: ComponentId + 'static),*> Bundle for ($($t,)*) {
+            fn append_ids(world: *mut WorldT, ids: &mut Vec<IdT>) {
+                $(ids.push($t::get_id(world));)*
+            }
+
+            unsafe fn write_values(self, world: *mut WorldT, entity: EntityT) {
+                $(write_bundle_component(world, entity, self.$index);)*
+            }
+
+            fn component_ptrs(
+                &mut self,
+                world: *mut WorldT,
+                ids: &[IdT],
+                data: &mut [*mut c_void],
+            ) {
+                $(bundle_component_ptr(world, &mut self.$index, ids, data);)*
+            }
+        }
+    }
+}
+
+impl_bundle!(A: 0); //size 1
+impl_bundle!(A: 0, B: 1); //size 2
+impl_bundle!(A: 0, B: 1, C: 2); //size 3
+impl_bundle!(A: 0, B: 1, C: 2, D: 3); //size 4
+impl_bundle!(A: 0, B: 1, C: 2, D: 3, E: 4); //size 5
+impl_bundle!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5); //size 6
+impl_bundle!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6); //size 7
+impl_bundle!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7); //size 8
+impl_bundle!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8); //size 9
+impl_bundle!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9); //size 10
+impl_bundle!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10); //size 11
+impl_bundle!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11); //size 12
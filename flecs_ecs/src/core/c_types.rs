@@ -21,7 +21,7 @@ use crate::sys::EcsTickSource;
 
 use std::{ffi::CStr, sync::OnceLock};
 
-use super::{ComponentInfo, EntityId, IntoWorld, NoneEnum, NotEmptyComponent};
+use super::{ComponentInfo, EntityId, FlecsIsNotATag, IntoWorld, NoneEnum, NotEmptyComponent};
 
 pub const RUST_ecs_id_FLAGS_MASK: u64 = 0xFF << 60;
 pub const RUST_ECS_COMPONENT_MASK: u64 = !RUST_ecs_id_FLAGS_MASK;
@@ -354,16 +354,6 @@ pub type Identifier = EcsIdentifier;
 pub type Poly = EcsPoly;
 pub type Target = EcsTarget;
 
-fn ecs_component_data() -> IdComponent {
-    IdComponent {
-        id: unsafe { FLECS_IDEcsComponentID_ },
-    }
-}
-
-fn ecs_poly_data() -> IdComponent {
-    IdComponent { id: ECS_POLY }
-}
-
 impl NotEmptyComponent for EcsComponent {}
 
 impl ComponentInfo for EcsComponent {
@@ -371,6 +361,7 @@ impl ComponentInfo for EcsComponent {
     const IS_TAG: bool = false;
     const IMPLS_CLONE: bool = true;
     const IMPLS_DEFAULT: bool = true;
+    type TagType = FlecsIsNotATag;
 }
 
 impl ComponentType<Struct> for EcsComponent {}
@@ -402,7 +393,7 @@ impl ComponentId for EcsComponent {
         unsafe { FLECS_IDEcsComponentID_ }
     }
 
-    unsafe fn get_id_unchecked() -> IdT {
+    unsafe fn get_id_unchecked(_world: impl IntoWorld) -> IdT {
         FLECS_IDEcsComponentID_
     }
 
@@ -417,6 +408,7 @@ impl ComponentInfo for Poly {
     const IS_TAG: bool = false;
     const IMPLS_CLONE: bool = true;
     const IMPLS_DEFAULT: bool = true;
+    type TagType = FlecsIsNotATag;
 }
 
 impl NotEmptyComponent for Poly {}
@@ -450,7 +442,7 @@ impl ComponentId for Poly {
         ECS_POLY
     }
 
-    unsafe fn get_id_unchecked() -> IdT {
+    unsafe fn get_id_unchecked(_world: impl IntoWorld) -> IdT {
         ECS_POLY
     }
 
@@ -466,6 +458,7 @@ impl ComponentInfo for TickSource {
     const IS_ENUM: bool = false;
     const IMPLS_CLONE: bool = true;
     const IMPLS_DEFAULT: bool = true;
+    type TagType = FlecsIsNotATag;
 }
 
 #[cfg(feature = "flecs_system")]
@@ -492,7 +485,7 @@ impl ComponentId for TickSource {
         true
     }
 
-    unsafe fn get_id_unchecked() -> IdT {
+    unsafe fn get_id_unchecked(_world: impl IntoWorld) -> IdT {
         ECS_TICK_SOURCE
     }
 
@@ -511,6 +504,7 @@ impl ComponentInfo for EntityId {
     const IS_TAG: bool = false;
     const IMPLS_CLONE: bool = true;
     const IMPLS_DEFAULT: bool = false;
+    type TagType = FlecsIsNotATag;
 }
 
 impl ComponentId for EntityId {
@@ -535,7 +529,7 @@ impl ComponentId for EntityId {
         true
     }
 
-    unsafe fn get_id_unchecked() -> IdT {
+    unsafe fn get_id_unchecked(_world: impl IntoWorld) -> IdT {
         //this is safe because it's already registered in flecs_c / world
         flecs_ecs_sys::FLECS_IDecs_entity_tID_
     }
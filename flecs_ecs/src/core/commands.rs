@@ -0,0 +1,307 @@
+//! Buffers that record structural changes to apply to a world later.
+
+use super::{
+    c_types::{EntityT, WorldT, ECS_CHILD_OF},
+    component_registration::ComponentId,
+    Entity, IntoComponentId, IntoEntityId, IntoEntityIdExt, IntoWorld,
+};
+use super::{EmptyComponent, NotEmptyComponent};
+
+type Command = Box<dyn FnOnce(&mut CommandContext) + Send>;
+
+/// State shared by the commands of a buffer while it is applied.
+struct CommandContext {
+    world: *mut WorldT,
+    /// entities created by `spawn` commands, in the order they were recorded.
+    spawned: Vec<EntityT>,
+    /// index of the first entity spawned by the buffer that is currently applied.
+    spawned_base: usize,
+}
+
+impl CommandContext {
+    fn resolve(&self, entity: CommandEntity) -> EntityT {
+        match entity {
+            CommandEntity::Existing(id) => id,
+            CommandEntity::Spawned(index) => self.spawned[self.spawned_base + index],
+        }
+    }
+
+    fn entity(&self, entity: CommandEntity) -> Entity {
+        Entity::new_from_existing_raw(self.world, self.resolve(entity))
+    }
+}
+
+/// Entity targeted by a recorded command.
+///
+/// Entities spawned by a [`Commands`] buffer don't have an id until the buffer is applied, they
+/// are identified by the order in which they were spawned instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommandEntity {
+    /// An entity that already exists in the world.
+    Existing(EntityT),
+    /// The n-th entity spawned by the buffer.
+    Spawned(usize),
+}
+
+impl<T: IntoEntityId> From<T> for CommandEntity {
+    fn from(entity: T) -> Self {
+        CommandEntity::Existing(entity.get_id())
+    }
+}
+
+/// Buffer of structural changes to apply to a world or stage later.
+///
+/// Commands own the values they set and are `Send`, so they can be recorded on any thread, for
+/// example by parallel jobs or async tasks, without access to the world. Once applied, the
+/// commands run in the order they were recorded. Applying the buffer to a [`Stage`] records the
+/// operations in the stage, to be merged with the world together with the stage.
+///
+/// Components and events are looked up when the buffer is applied, so the world must be able to
+/// register them at that point, or already have them registered when applying to a stage.
+///
+/// [`Stage`]: super::Stage
+#[derive(Default)]
+pub struct Commands {
+    queue: Vec<Command>,
+    spawned: usize,
+}
+
+impl Commands {
+    /// Create an empty command buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of recorded commands.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Test whether the buffer has no recorded commands.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Discard all recorded commands.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.spawned = 0;
+    }
+
+    /// Record the creation of a new entity.
+    ///
+    /// # Returns
+    ///
+    /// A builder to record operations on the new entity.
+    pub fn spawn(&mut self) -> EntityCommands<'_> {
+        let index = self.spawned;
+        self.spawned += 1;
+        self.push(move |ctx| {
+            let entity = Entity::new(ctx.world);
+            debug_assert_eq!(ctx.spawned.len(), ctx.spawned_base + index);
+            ctx.spawned.push(entity.raw_id);
+        });
+        EntityCommands {
+            commands: self,
+            entity: CommandEntity::Spawned(index),
+        }
+    }
+
+    /// Record operations on an entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - An existing entity, or an entity spawned by this buffer.
+    ///
+    /// # Returns
+    ///
+    /// A builder to record operations on the entity.
+    pub fn entity(&mut self, entity: impl Into<CommandEntity>) -> EntityCommands<'_> {
+        EntityCommands {
+            commands: self,
+            entity: entity.into(),
+        }
+    }
+
+    /// Move the commands of another buffer to the end of this buffer.
+    ///
+    /// Entities spawned by `other` keep being identified by the [`CommandEntity`] values
+    /// returned by `other`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The buffer to append.
+    pub fn append(&mut self, other: Commands) {
+        let offset = self.spawned;
+        self.spawned += other.spawned;
+        self.push(move |ctx| {
+            let base = ctx.spawned_base;
+            ctx.spawned_base = base + offset;
+            for command in other.queue {
+                command(ctx);
+            }
+            ctx.spawned_base = base;
+        });
+    }
+
+    /// Apply the recorded commands, in the order they were recorded, and clear the buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world or stage to apply the commands to.
+    ///
+    /// # Returns
+    ///
+    /// The entities spawned by the buffer, indexed by [`CommandEntity::Spawned`].
+    pub fn apply(&mut self, world: impl IntoWorld) -> Vec<Entity> {
+        let mut ctx = CommandContext {
+            world: world.world_ptr_mut(),
+            spawned: Vec::with_capacity(self.spawned),
+            spawned_base: 0,
+        };
+        self.spawned = 0;
+        for command in self.queue.drain(..) {
+            command(&mut ctx);
+        }
+        ctx.spawned
+            .into_iter()
+            .map(|id| Entity::new_from_existing_raw(ctx.world, id))
+            .collect()
+    }
+
+    fn push(&mut self, command: impl FnOnce(&mut CommandContext) + Send + 'static) {
+        self.queue.push(Box::new(command));
+    }
+}
+
+/// Records operations on a single entity into a [`Commands`] buffer.
+pub struct EntityCommands<'a> {
+    commands: &'a mut Commands,
+    entity: CommandEntity,
+}
+
+impl EntityCommands<'_> {
+    /// Get the entity the operations are recorded for.
+    pub fn id(&self) -> CommandEntity {
+        self.entity
+    }
+
+    /// Record adding a component, tag or pair to the entity.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The component to add.
+    pub fn add<T: IntoComponentId>(&mut self) -> &mut Self {
+        let entity = self.entity;
+        self.commands.push(move |ctx| {
+            ctx.entity(entity).add::<T>();
+        });
+        self
+    }
+
+    /// Record adding an id to the entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id to add.
+    pub fn add_id(&mut self, id: impl IntoEntityIdExt) -> &mut Self {
+        let entity = self.entity;
+        let id = id.get_id();
+        self.commands.push(move |ctx| {
+            ctx.entity(entity).add_id(id);
+        });
+        self
+    }
+
+    /// Record setting a component value on the entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to set, which is moved into the world when the buffer is applied.
+    pub fn set<T: ComponentId + Send + 'static>(&mut self, value: T) -> &mut Self {
+        let entity = self.entity;
+        self.commands.push(move |ctx| {
+            ctx.entity(entity).set(value);
+        });
+        self
+    }
+
+    /// Record removing a component, tag or pair from the entity.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The component to remove.
+    pub fn remove<T: IntoComponentId>(&mut self) -> &mut Self {
+        let entity = self.entity;
+        self.commands.push(move |ctx| {
+            ctx.entity(entity).remove::<T>();
+        });
+        self
+    }
+
+    /// Record removing an id from the entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id to remove.
+    pub fn remove_id(&mut self, id: impl IntoEntityIdExt) -> &mut Self {
+        let entity = self.entity;
+        let id = id.get_id();
+        self.commands.push(move |ctx| {
+            ctx.entity(entity).remove_id(id);
+        });
+        self
+    }
+
+    /// Record making the entity a child of a parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - An existing entity, or an entity spawned by the same buffer.
+    pub fn child_of(&mut self, parent: impl Into<CommandEntity>) -> &mut Self {
+        let entity = self.entity;
+        let parent = parent.into();
+        self.commands.push(move |ctx| {
+            let parent = ctx.resolve(parent);
+            ctx.entity(entity).add_id((ECS_CHILD_OF, parent));
+        });
+        self
+    }
+
+    /// Record emitting an event for the entity.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The event to emit. Type must be empty.
+    pub fn emit<T: EmptyComponent + ComponentId>(&mut self) -> &mut Self {
+        let entity = self.entity;
+        self.commands.push(move |ctx| {
+            ctx.entity(entity).emit::<T>();
+        });
+        self
+    }
+
+    /// Record emitting an event with a payload for the entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The event payload, which is dropped after the event was emitted.
+    pub fn emit_payload<T: NotEmptyComponent + ComponentId + Send + 'static>(
+        &mut self,
+        mut payload: T,
+    ) -> &mut Self {
+        let entity = self.entity;
+        self.commands.push(move |ctx| {
+            ctx.entity(entity).emit_payload(&mut payload);
+        });
+        self
+    }
+
+    /// Record deleting the entity.
+    pub fn despawn(&mut self) {
+        let entity = self.entity;
+        self.commands.push(move |ctx| {
+            ctx.entity(entity).destruct();
+        });
+    }
+}
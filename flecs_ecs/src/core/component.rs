@@ -15,10 +15,10 @@ use super::{
     component_registration::ComponentId,
     ecs_field,
     entity::Entity,
-    IntoEntityId, IntoWorld,
+    IntoCStr, IntoEntityId, IntoWorld,
 };
 
-use std::{ffi::CStr, os::raw::c_void, ptr};
+use std::{os::raw::c_void, ptr};
 
 use std::{marker::PhantomData, ops::Deref};
 
@@ -167,7 +167,7 @@ impl<T: ComponentId> Component<T> {
         }
 
         Self {
-            base: UntypedComponent::new(world, unsafe { T::get_id_unchecked() }),
+            base: UntypedComponent::new(world, unsafe { T::get_id_unchecked(world) }),
             _marker: PhantomData,
         }
     }
@@ -183,14 +183,14 @@ impl<T: ComponentId> Component<T> {
     ///
     /// * C++ API: `component::component`
     #[doc(alias = "component::component")]
-    pub fn new_named(world: impl IntoWorld, name: &CStr) -> Self {
+    pub fn new_named(world: impl IntoWorld, name: impl IntoCStr) -> Self {
         let world = world.world_ptr_mut();
         if !T::is_registered_with_world(world) {
-            T::register_explicit_named(world, name);
+            name.with_cstr(|name| T::register_explicit_named(world, name));
         }
 
         Self {
-            base: UntypedComponent::new(world, unsafe { T::get_id_unchecked() }),
+            base: UntypedComponent::new(world, unsafe { T::get_id_unchecked(world) }),
             _marker: PhantomData,
         }
     }
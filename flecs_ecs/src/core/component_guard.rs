@@ -0,0 +1,149 @@
+//! Guards that keep borrowed component data valid.
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use crate::sys::{ecs_defer_begin, ecs_defer_end};
+
+use super::{EntityT, GuardBorrow, IdT, WorldT};
+
+/// Defers structural changes to the world for as long as it is alive.
+///
+/// Adding or removing components moves an entity to another table, which invalidates
+/// references to its component data. While deferred, such operations are queued and only
+/// applied once the last borrow of the world is released.
+struct DeferLock {
+    world: *mut WorldT,
+}
+
+impl DeferLock {
+    fn new(world: *mut WorldT) -> Self {
+        unsafe { ecs_defer_begin(world) };
+        Self { world }
+    }
+}
+
+impl Drop for DeferLock {
+    fn drop(&mut self) {
+        unsafe { ecs_defer_end(self.world) };
+    }
+}
+
+/// Shared borrow of component data.
+///
+/// Structural changes to the world, like adding or removing components, are deferred until the
+/// guard is dropped, so the component can't move while it is borrowed. The guard borrows the
+/// entity it was taken from. With the `flecs_borrow_tracking` feature, borrowing the component
+/// mutably while the guard exists panics.
+pub struct ComponentGuard<'a, T> {
+    value: *const T,
+    _borrow: GuardBorrow,
+    _lock: DeferLock,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> ComponentGuard<'a, T> {
+    /// Create a guard for component data of the world.
+    ///
+    /// # Safety
+    ///
+    /// `value` must point to the data of component `id` of `entity`, stored in `world`.
+    pub(crate) unsafe fn new(
+        world: *mut WorldT,
+        entity: EntityT,
+        id: IdT,
+        value: *const T,
+    ) -> Self {
+        Self {
+            _borrow: GuardBorrow::new(world, id, entity, false),
+            _lock: DeferLock::new(world),
+            value,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a guard if `value` is not null.
+    ///
+    /// # Safety
+    ///
+    /// `value` must be null or point to the data of component `id` of `entity`, stored in `world`.
+    pub(crate) unsafe fn from_ptr(
+        world: *mut WorldT,
+        entity: EntityT,
+        id: IdT,
+        value: *const T,
+    ) -> Option<Self> {
+        if value.is_null() {
+            None
+        } else {
+            Some(Self::new(world, entity, id, value))
+        }
+    }
+}
+
+impl<T> Deref for ComponentGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.value }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ComponentGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// Exclusive borrow of component data.
+///
+/// Structural changes to the world, like adding or removing components, are deferred until the
+/// guard is dropped, so the component can't move while it is borrowed. The guard borrows the
+/// entity it was taken from. With the `flecs_borrow_tracking` feature, borrowing the component
+/// again while the guard exists panics.
+pub struct ComponentGuardMut<'a, T> {
+    value: *mut T,
+    _borrow: GuardBorrow,
+    _lock: DeferLock,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> ComponentGuardMut<'a, T> {
+    /// Create a guard for component data of the world.
+    ///
+    /// # Safety
+    ///
+    /// `value` must point to the data of component `id` of `entity`, stored in `world`, that is
+    /// not borrowed elsewhere.
+    pub(crate) unsafe fn new(world: *mut WorldT, entity: EntityT, id: IdT, value: *mut T) -> Self {
+        Self {
+            _borrow: GuardBorrow::new(world, id, entity, true),
+            _lock: DeferLock::new(world),
+            value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for ComponentGuardMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.value }
+    }
+}
+
+impl<T> DerefMut for ComponentGuardMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.value }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ComponentGuardMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
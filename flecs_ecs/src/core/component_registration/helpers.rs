@@ -1,8 +1,6 @@
 use std::ffi::c_char;
 
-use flecs_ecs_sys::{ecs_exists, ecs_get_symbol, ecs_world_t};
-
-use crate::core::{EntityT, IdT, WorldT, SEPARATOR};
+use crate::core::{EntityT, SEPARATOR};
 
 use super::ComponentId;
 
@@ -64,39 +62,3 @@ pub(crate) fn create_entity_desc(
     };
     entity_desc
 }
-
-pub(crate) fn get_symbol_name(
-    id: IdT,
-    world: *mut ecs_world_t,
-    type_name_ptr: *const c_char,
-    is_comp_pre_registered_with_world: bool,
-) -> *const i8 {
-    if id != 0 {
-        let symbol_ptr = if is_comp_pre_registered_with_world {
-            unsafe { ecs_get_symbol(world, id) }
-        } else {
-            std::ptr::null()
-        };
-        if symbol_ptr.is_null() {
-            type_name_ptr
-        } else {
-            symbol_ptr
-        }
-    } else {
-        type_name_ptr
-    }
-}
-
-/// checks if the component is registered with a world.
-/// this function is unsafe because it assumes that the component is registered with a world, not necessarily the world passed in.
-pub(crate) unsafe fn is_component_registered_with_world<T>(world: *const WorldT) -> bool
-where
-    T: ComponentId,
-{
-    // we know this is safe because we checked if world is not null & if the component is registered
-    if !world.is_null() && unsafe { !ecs_exists(world, T::get_id_unchecked()) } {
-        return false;
-    }
-
-    true
-}
@@ -2,8 +2,10 @@ mod helpers;
 mod registration;
 pub mod registration_traits;
 pub mod registration_types;
+mod registry;
 
 pub(crate) use helpers::*;
 pub use registration::*;
 pub use registration_traits::*;
 pub use registration_types::*;
+pub(crate) use registry::*;
@@ -1,14 +1,25 @@
 use std::{ffi::CStr, sync::OnceLock};
 
 use crate::core::{
-    ConditionalTypeSelector, Entity, EntityT, Enum, FlecsNoneCloneDummy, FlecsNoneDefaultDummy,
-    IdComponent, IdT, IntoWorld, Struct, TypeHooksT,
+    ConditionalTypePairSelector, ConditionalTypeSelector, Entity, EntityT, Enum, FlecsIsATag,
+    FlecsIsNotATag, FlecsNoneCloneDummy, FlecsNoneDefaultDummy, IdComponent, IdT, IntoWorld,
+    Struct, TypeHooksT,
 };
 
 use super::{
-    is_component_registered_with_world, try_register_component, try_register_component_named,
+    registered_component_id, registered_variant_id, try_register_component,
+    try_register_component_impl, try_register_component_named,
 };
 
+#[cold]
+#[inline(never)]
+fn component_not_registered<T>() -> ! {
+    panic!(
+        "component {} is not registered with the world",
+        std::any::type_name::<T>()
+    )
+}
+
 pub trait EmptyComponent {}
 pub trait NotEmptyComponent {}
 
@@ -31,15 +42,12 @@ pub trait ComponentType<T: ECSComponentType> {}
 ///      }
 /// ```
 ///
-/// The `ComponentId` trait is designed to maintain component IDs for a Rust type
-/// in a manner that is consistent across different worlds (or instances).
-/// When a component is utilized, this trait will determine whether it has already been registered.
-/// If it hasn't, it registers the component with the current world.
+/// The `ComponentId` trait maintains the component IDs of a Rust type for every world it is used in.
+/// When a component is utilized, this trait will determine whether it has already been registered
+/// with the world. If it hasn't, it registers the component with that world.
 ///
-/// If the ID has been previously established, the trait ensures the world recognizes it.
-/// If the world doesn't, this implies the component was registered by a different world.
-/// In such a case, the component is registered with the present world using the pre-existing ID.
-/// If the ID is already known, the trait takes care of the component registration and checks for consistency in the input.
+/// Each world keeps its own registry of component IDs, so the same type can have a different ID
+/// in every world, regardless of the order in which worlds were created or components registered.
 pub trait ComponentId: Sized + ComponentInfo {
     type UnderlyingType: ComponentId;
     type UnderlyingEnumType: ComponentId + CachedEnumData;
@@ -54,45 +62,37 @@ pub trait ComponentId: Sized + ComponentInfo {
         try_register_component_named::<Self::UnderlyingType>(world, name)
     }
 
-    /// checks if the component is registered with a world.
+    /// checks if the component is registered with any world.
+    /// Use `is_registered_with_world` to check if the component is registered with a particular world.
     #[inline(always)]
     fn is_registered() -> bool {
         Self::__get_once_lock_data().get().is_some()
     }
 
-    /// checks if the component is registered with a world.
-    /// # Safety
-    /// This function is unsafe because it assumes world is not nullptr
-    /// this is highly unlikely a world would be nullptr, hence this function is not marked as unsafe.
-    /// this will be changed in the future where we get rid of the pointers.
-    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    /// checks if the component is registered with the world.
     #[inline(always)]
     fn is_registered_with_world(world: impl IntoWorld) -> bool {
-        if Self::is_registered() {
-            unsafe {
-                is_component_registered_with_world::<Self::UnderlyingType>(world.get_world_raw())
-            }
-        } else {
-            false
-        }
+        registered_component_id::<Self::UnderlyingType>(world.get_world_raw()).is_some()
     }
 
     /// returns the component id of the component. If the component is not registered, it will register it.
     fn get_id(world: impl IntoWorld) -> IdT {
-        try_register_component::<Self::UnderlyingType>(world);
-        unsafe { Self::get_id_unchecked() }
+        try_register_component_impl::<Self::UnderlyingType>(world, std::ptr::null())
     }
 
-    /// returns the component id of the component.
+    /// returns the component id of the component in the world, without registering it.
     /// # Safety
     /// safe version is `get_id`
-    /// this function is unsafe because it assumes that the component is registered,
-    /// the lock data being initialized is not checked and will panic if it's not.
-    /// does not check if the component is registered in the world, if not, it might cause problems depending on usage.
+    /// this function is unsafe because it assumes that the component is registered with the world.
     /// only use this if you know what you are doing and you are sure the component is registered in the world
+    /// # Panics
+    /// panics if the component is not registered with the world.
     #[inline(always)]
-    unsafe fn get_id_unchecked() -> IdT {
-        Self::__get_once_lock_data().get().unwrap_unchecked().id
+    unsafe fn get_id_unchecked(world: impl IntoWorld) -> IdT {
+        match registered_component_id::<Self::UnderlyingType>(world.get_world_raw()) {
+            Some(id) => id,
+            None => component_not_registered::<Self>(),
+        }
     }
 
     // Not public API.
@@ -101,14 +101,13 @@ pub trait ComponentId: Sized + ComponentInfo {
 
     // Not public API.
     #[doc(hidden)]
-    #[inline(always)]
-    fn __initialize<F: FnOnce() -> IdComponent>(f: F) -> &'static IdComponent {
-        Self::__get_once_lock_data().get_or_init(f)
-    }
+    fn __register_lifecycle_hooks(mut _type_hooks: &mut TypeHooksT) {}
 
     // Not public API.
+    /// Registers reflection data of the component, invoked once per world when the component is registered.
+    /// Implemented by the `Component` derive when the type is annotated with `#[meta]`.
     #[doc(hidden)]
-    fn __register_lifecycle_hooks(mut _type_hooks: &mut TypeHooksT) {}
+    fn __register_meta(_world: impl IntoWorld, _id: EntityT) {}
 }
 
 pub trait ComponentInfo: Sized {
@@ -117,6 +116,9 @@ pub trait ComponentInfo: Sized {
     const NEEDS_DROP: bool = std::mem::needs_drop::<Self>();
     const IMPLS_CLONE: bool;
     const IMPLS_DEFAULT: bool;
+    /// Either [`FlecsIsATag`] or [`FlecsIsNotATag`], used to select the data type of pairs at
+    /// compile time.
+    type TagType;
 }
 
 pub trait CachedEnumData: ComponentType<Enum> + ComponentId {
@@ -131,36 +133,26 @@ pub trait CachedEnumData: ComponentType<Enum> + ComponentId {
 
     fn iter() -> Self::VariantIterator;
 
-    /// # Note
-    /// it only means that the enum is registered with a particular world, not necessarily yours.
-    fn are_fields_registered_as_entities() -> bool {
-        let mut result = true;
-        let ptr = Self::__enum_data_mut();
-        for i in 0..Self::SIZE_ENUM_FIELDS {
-            unsafe {
-                if *ptr.add(i as usize) == 0 {
-                    result = false;
-                    break;
-                }
-            }
-        }
-        result
+    /// checks if all variants of the enum are registered as entities with the world.
+    fn are_fields_registered_as_entities(world: impl IntoWorld) -> bool {
+        (0..Self::SIZE_ENUM_FIELDS as usize)
+            .all(|index| Self::is_index_registered_as_entity(&world, index))
     }
 
-    fn is_field_registered_as_entity(&self) -> bool {
-        let index = self.enum_index();
-        unsafe { *Self::__enum_data_mut().add(index) != 0 }
+    /// checks if the variant is registered as entity with the world.
+    fn is_field_registered_as_entity(&self, world: impl IntoWorld) -> bool {
+        Self::is_index_registered_as_entity(world, self.enum_index())
     }
 
-    fn is_index_registered_as_entity(index: usize) -> bool {
-        unsafe { *Self::__enum_data_mut().add(index) != 0 }
+    /// checks if the variant at `index` is registered as entity with the world.
+    fn is_index_registered_as_entity(world: impl IntoWorld, index: usize) -> bool {
+        Self::get_id_variant_of_index(world, index).is_some()
     }
 
     /// get the entity id of the variant of the enum. This function will register the enum with the world if it's not registered.
     fn get_id_variant(&self, world: impl IntoWorld) -> Entity {
         try_register_component::<Self>(&world);
-        let index = self.enum_index();
-        Entity::new_from_existing_raw(world, unsafe { *Self::__enum_data_mut().add(index) })
+        unsafe { self.get_id_variant_unchecked(world) }
     }
 
     /// # Safety
@@ -168,28 +160,27 @@ pub trait CachedEnumData: ComponentType<Enum> + ComponentId {
     /// This function is unsafe because it assumes the enum has been registered as a component with the world.
     /// if uncertain, use `try_register_component::<T>` to try and register it
     unsafe fn get_id_variant_unchecked(&self, world: impl IntoWorld) -> Entity {
-        let index = self.enum_index();
-        Entity::new_from_existing_raw(world, unsafe { *Self::__enum_data_mut().add(index) })
+        let id = Self::get_id_variant_of_index_unchecked(&world, self.enum_index());
+        Entity::new_from_existing_raw(world, id)
     }
 
-    fn get_id_variant_of_index(index: usize) -> Option<u64> {
-        if index < Self::SIZE_ENUM_FIELDS as usize {
-            Some(unsafe { *Self::__enum_data_mut().add(index) })
-        } else {
-            None
-        }
+    /// get the entity id of the variant at `index` in the world, if the enum is registered with it.
+    fn get_id_variant_of_index(world: impl IntoWorld, index: usize) -> Option<u64> {
+        registered_variant_id::<Self>(world.get_world_raw(), index)
     }
 
     /// ## Safety
-    /// This function is unsafe because it dereferences a raw pointer and you must ensure that the
-    /// index is within the bounds of the number of variants in the enum.
+    /// This function is unsafe because it assumes the enum has been registered as a component with
+    /// the world and that the index is within the bounds of the number of variants in the enum.
     /// if uncertain, use `SIZE_ENUM_FIELDS` to check the number of variants.
-    unsafe fn get_id_variant_of_index_unchecked(index: usize) -> u64 {
-        unsafe { *Self::__enum_data_mut().add(index) }
+    /// # Panics
+    /// panics if the enum is not registered with the world or the index is out of bounds.
+    unsafe fn get_id_variant_of_index_unchecked(world: impl IntoWorld, index: usize) -> u64 {
+        match Self::get_id_variant_of_index(world, index) {
+            Some(id) => id,
+            None => component_not_registered::<Self>(),
+        }
     }
-
-    #[doc(hidden)]
-    fn __enum_data_mut() -> *mut u64;
 }
 
 impl<T: ComponentInfo> ComponentInfo for &T {
@@ -197,6 +188,7 @@ impl<T: ComponentInfo> ComponentInfo for &T {
     const IS_TAG: bool = T::IS_TAG;
     const IMPLS_CLONE: bool = T::IMPLS_CLONE;
     const IMPLS_DEFAULT: bool = T::IMPLS_DEFAULT;
+    type TagType = T::TagType;
 }
 
 impl<T: ComponentInfo> ComponentInfo for &mut T {
@@ -204,6 +196,7 @@ impl<T: ComponentInfo> ComponentInfo for &mut T {
     const IS_TAG: bool = T::IS_TAG;
     const IMPLS_CLONE: bool = T::IMPLS_CLONE;
     const IMPLS_DEFAULT: bool = T::IMPLS_DEFAULT;
+    type TagType = T::TagType;
 }
 
 impl<T: ComponentId> ComponentId for &T {
@@ -234,6 +227,10 @@ pub trait FlecsCloneType {
     type Type: Clone;
 }
 
+pub trait FlecsPairType {
+    type Type: ComponentId;
+}
+
 impl<T> FlecsDefaultType for ConditionalTypeSelector<false, T> {
     type Type = FlecsNoneDefaultDummy;
 }
@@ -255,3 +252,19 @@ where
 {
     type Type = T;
 }
+
+impl<First, Second> FlecsPairType for ConditionalTypePairSelector<FlecsIsATag, First, Second>
+where
+    First: ComponentId,
+    Second: ComponentId,
+{
+    type Type = Second;
+}
+
+impl<First, Second> FlecsPairType for ConditionalTypePairSelector<FlecsIsNotATag, First, Second>
+where
+    First: ComponentId,
+    Second: ComponentId,
+{
+    type Type = First;
+}
@@ -1,10 +1,15 @@
 use flecs_ecs_derive::Component;
 
 /// Component data that is cached by the `ComponentId` trait.
-/// This data is used to register components with the world.
-/// It is also used to ensure that components are registered consistently across different worlds.
+/// It is initialized the first time the component is registered with any world.
+/// The id of the component in other worlds is looked up in their component registry with `index`.
 #[derive(Clone, Debug, Default)]
 pub struct IdComponent {
+    /// index of the component in the component registry of a world.
+    pub index: usize,
+    /// key of the world the component was registered with first.
+    pub world_key: u64,
+    /// id of the component in the world it was registered with first.
     pub id: u64,
 }
 
@@ -25,3 +30,18 @@ pub struct FlecsNoneCloneDummy;
 pub struct ConditionalTypeSelector<const B: bool, T> {
     phantom: std::marker::PhantomData<T>,
 }
+
+/// [`ComponentInfo::TagType`] of components without data.
+///
+/// [`ComponentInfo::TagType`]: super::ComponentInfo::TagType
+pub struct FlecsIsATag;
+
+/// [`ComponentInfo::TagType`] of components with data.
+///
+/// [`ComponentInfo::TagType`]: super::ComponentInfo::TagType
+pub struct FlecsIsNotATag;
+
+/// Selects the type of the data of a pair, based on the `TagType` of the first element.
+pub struct ConditionalTypePairSelector<T, First, Second> {
+    phantom: std::marker::PhantomData<(T, First, Second)>,
+}
@@ -0,0 +1,258 @@
+use std::{
+    any::TypeId,
+    cell::Cell,
+    collections::HashMap,
+    ffi::c_void,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        OnceLock, RwLock,
+    },
+};
+
+use flecs_ecs_sys::{ecs_atfini, ecs_get_world};
+
+use crate::core::{Bundle, BundleInfo, EntityT, IdComponent, WorldT};
+
+use super::{CachedEnumData, ComponentId};
+
+/// source of the slots the component types reserve in the component registries.
+static NEXT_COMPONENT_SLOT: AtomicUsize = AtomicUsize::new(0);
+
+/// source of the keys identifying a world's registry. Keys are never reused, unlike world pointers.
+static NEXT_WORLD_KEY: AtomicU64 = AtomicU64::new(1);
+
+/// the registry of every world, by world pointer.
+static REGISTRIES: RwLock<Option<HashMap<usize, Box<ComponentRegistry>>>> = RwLock::new(None);
+
+/// bumped whenever a registry is removed, which invalidates the registries cached by threads.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// the world (or stage) pointer that was looked up last on this thread, with its registry.
+    static LAST_REGISTRY: Cell<(*const WorldT, u64, *const ComponentRegistry)> =
+        const { Cell::new((std::ptr::null(), 0, std::ptr::null())) };
+}
+
+/// number of slots in the first bucket of a registry, as a power of two.
+const FIRST_BUCKET_BITS: u32 = 5;
+
+/// number of buckets a registry needs to address every slot.
+const BUCKET_COUNT: usize = (usize::BITS - FIRST_BUCKET_BITS) as usize;
+
+/// Maps Rust component types to the ids they were registered with in one world.
+///
+/// Every type reserves a range of slots once per process: one for the component itself, followed
+/// by one for each variant of an enum. Slots are stored in buckets that double in size and never
+/// move once allocated, so ids are read without taking a lock.
+pub(crate) struct ComponentRegistry {
+    key: u64,
+    buckets: [OnceLock<Box<[AtomicU64]>>; BUCKET_COUNT],
+    /// the bundle types used in the world. Entries are never removed, so they don't move.
+    bundles: RwLock<HashMap<TypeId, Box<BundleInfo>>>,
+}
+
+impl ComponentRegistry {
+    fn new() -> Self {
+        Self {
+            key: NEXT_WORLD_KEY.fetch_add(1, Ordering::Relaxed),
+            buckets: std::array::from_fn(|_| OnceLock::new()),
+            bundles: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// returns the bucket and the index in the bucket of a slot.
+    #[inline]
+    fn location(slot: usize) -> (usize, usize) {
+        let slot = slot + (1 << FIRST_BUCKET_BITS);
+        let bits = usize::BITS - 1 - slot.leading_zeros();
+        ((bits - FIRST_BUCKET_BITS) as usize, slot - (1 << bits))
+    }
+
+    #[inline]
+    fn id(&self, slot: usize) -> Option<EntityT> {
+        let (bucket, index) = Self::location(slot);
+        let id = self.buckets[bucket].get()?[index].load(Ordering::Acquire);
+        (id != 0).then_some(id)
+    }
+
+    fn set_id(&self, slot: usize, id: EntityT) {
+        let (bucket, index) = Self::location(slot);
+        let ids = self.buckets[bucket].get_or_init(|| {
+            (0..1usize << (bucket as u32 + FIRST_BUCKET_BITS))
+                .map(|_| AtomicU64::new(0))
+                .collect()
+        });
+        ids[index].store(id, Ordering::Release);
+    }
+}
+
+/// creates the component registry of a world created by [`World::new`](crate::core::World::new).
+///
+/// The registry must be removed with [`remove_component_registry`] once the world is destroyed.
+///
+/// # Returns
+///
+/// The key of the registry.
+pub(crate) fn init_component_registry(world: *mut WorldT) -> u64 {
+    let registry = Box::new(ComponentRegistry::new());
+    let key = registry.key;
+    let mut registries = REGISTRIES.write().unwrap();
+    registries
+        .get_or_insert_with(HashMap::new)
+        .insert(world as usize, registry);
+    key
+}
+
+/// removes the component registry of a destroyed world.
+///
+/// If `key` is set, the registry is only removed if it has that key, as the world pointer may
+/// already be reused by a new world.
+pub(crate) fn remove_component_registry(world: *mut WorldT, key: Option<u64>) {
+    let mut registries = REGISTRIES.write().unwrap();
+    let registry =
+        registries
+            .as_mut()
+            .and_then(|registries| match registries.get(&(world as usize)) {
+                Some(registry) if key.is_none() || key == Some(registry.key) => {
+                    registries.remove(&(world as usize))
+                }
+                _ => None,
+            });
+    GENERATION.fetch_add(1, Ordering::Release);
+    drop(registries);
+    drop(registry);
+}
+
+unsafe extern "C" fn remove_component_registry_action(world: *mut WorldT, _ctx: *mut c_void) {
+    remove_component_registry(world, None);
+}
+
+/// returns the component registry of the world, if it has one.
+#[inline]
+fn component_registry<'a>(world: *const WorldT) -> Option<&'a ComponentRegistry> {
+    if world.is_null() {
+        return None;
+    }
+
+    let generation = GENERATION.load(Ordering::Acquire);
+    let (last_world, last_generation, registry) = LAST_REGISTRY.with(Cell::get);
+    if last_world == world && last_generation == generation {
+        // the registry lives as long as the world it was looked up for.
+        return unsafe { registry.as_ref() };
+    }
+
+    // stages resolve to the registry of their world.
+    let real_world = unsafe { ecs_get_world(world as *const c_void) };
+    let registries = REGISTRIES.read().unwrap();
+    let registry: *const ComponentRegistry = &**registries.as_ref()?.get(&(real_world as usize))?;
+    LAST_REGISTRY.with(|last| last.set((world, generation, registry)));
+    unsafe { registry.as_ref() }
+}
+
+/// returns the component registry of the world, creating it if the world doesn't have one yet.
+///
+/// Only worlds that were not created by [`World::new`](crate::core::World::new) get their registry
+/// here, it is removed when the world is destroyed.
+fn ensure_component_registry<'a>(world: *const WorldT) -> &'a ComponentRegistry {
+    if let Some(registry) = component_registry(world) {
+        return registry;
+    }
+
+    let real_world = unsafe { ecs_get_world(world as *const c_void) as *mut WorldT };
+    let mut registries = REGISTRIES.write().unwrap();
+    let registry: *const ComponentRegistry = &**registries
+        .get_or_insert_with(HashMap::new)
+        .entry(real_world as usize)
+        .or_insert_with(|| {
+            unsafe {
+                ecs_atfini(
+                    real_world,
+                    Some(remove_component_registry_action),
+                    std::ptr::null_mut(),
+                );
+            }
+            Box::new(ComponentRegistry::new())
+        });
+    unsafe { &*registry }
+}
+
+/// returns the data of the component type, reserving its slots in the registries on first use.
+fn component_data<T: ComponentId>(
+    registry: &ComponentRegistry,
+    id: EntityT,
+) -> &'static IdComponent {
+    T::__get_once_lock_data().get_or_init(|| {
+        let slot_count = 1 + T::UnderlyingEnumType::SIZE_ENUM_FIELDS as usize;
+        IdComponent {
+            index: NEXT_COMPONENT_SLOT.fetch_add(slot_count, Ordering::Relaxed),
+            world_key: registry.key,
+            id,
+        }
+    })
+}
+
+/// returns the id the component was registered with in the world.
+///
+/// The world that registered the component first is cached with the type itself, so the common
+/// single world case is answered without looking into the slots of the registry.
+#[inline]
+pub(crate) fn registered_component_id<T: ComponentId>(world: *const WorldT) -> Option<EntityT> {
+    let data = T::__get_once_lock_data().get()?;
+    let registry = component_registry(world)?;
+    if registry.key == data.world_key {
+        Some(data.id)
+    } else {
+        registry.id(data.index)
+    }
+}
+
+/// stores the id the component was registered with in the world.
+pub(crate) fn set_registered_component_id<T: ComponentId>(world: *const WorldT, id: EntityT) {
+    let registry = ensure_component_registry(world);
+    let data = component_data::<T>(registry, id);
+    registry.set_id(data.index, id);
+}
+
+/// returns the id the enum variant at `variant` was registered with in the world.
+pub(crate) fn registered_variant_id<T: ComponentId + CachedEnumData>(
+    world: *const WorldT,
+    variant: usize,
+) -> Option<EntityT> {
+    if variant >= T::SIZE_ENUM_FIELDS as usize {
+        return None;
+    }
+    let data = T::__get_once_lock_data().get()?;
+    component_registry(world)?.id(data.index + 1 + variant)
+}
+
+/// stores the id the enum variant at `variant` was registered with in the world.
+///
+/// The component itself must have been stored with `set_registered_component_id` first.
+pub(crate) fn set_registered_variant_id<T: ComponentId + CachedEnumData>(
+    world: *const WorldT,
+    variant: usize,
+    id: EntityT,
+) {
+    if variant >= T::SIZE_ENUM_FIELDS as usize {
+        return;
+    }
+    let Some(data) = T::__get_once_lock_data().get() else {
+        return;
+    };
+    ensure_component_registry(world).set_id(data.index + 1 + variant, id);
+}
+
+/// returns the components of the bundle type in the world, collecting them on first use.
+pub(crate) fn bundle_info<'a, B: Bundle>(world: *mut WorldT) -> &'a BundleInfo {
+    let registry = ensure_component_registry(world);
+    if let Some(info) = registry.bundles.read().unwrap().get(&TypeId::of::<B>()) {
+        // the info lives as long as the world it was looked up for.
+        return unsafe { &*(&**info as *const BundleInfo) };
+    }
+
+    // the components are registered without holding the lock.
+    let info = Box::new(BundleInfo::new::<B>(world));
+    let mut bundles = registry.bundles.write().unwrap();
+    let info: *const BundleInfo = &**bundles.entry(TypeId::of::<B>()).or_insert(info);
+    unsafe { &*info }
+}
@@ -5,26 +5,26 @@ use std::{
 };
 
 use super::{
+    bundle::{insert_bundle, remove_bundle, Bundle},
     c_types::{IdT, SEPARATOR},
+    component_guard::ComponentGuardMut,
     component_ref::Ref,
     component_registration::{ComponentId, ComponentType, Enum, Struct},
     ecs_pair, ecs_pair_first, ecs_pair_second, set_helper,
     world::World,
-    CachedEnumData, EmptyComponent, EntityView, IntoComponentId, IntoEntityId, IntoEntityIdExt,
-    IntoWorld, NotEmptyComponent, ScopedWorld, ECS_DEPENDS_ON, ECS_EXCLUSIVE, ECS_IS_A,
-    ECS_OVERRIDE, ECS_SLOT_OF, ECS_WILDCARD,
+    CachedEnumData, EmptyComponent, EntityView, IntoCStr, IntoComponentId, IntoEntityId,
+    IntoEntityIdExt, IntoWorld, NotEmptyComponent, ScopedWorld, ECS_DEPENDS_ON, ECS_EXCLUSIVE,
+    ECS_IS_A, ECS_OVERRIDE, ECS_SLOT_OF, ECS_WILDCARD,
 };
-#[cfg(any(debug_assertions, feature = "flecs_force_enable_ecs_asserts"))]
-use crate::core::FlecsErrorCode;
 use crate::{
-    core::ECS_CHILD_OF,
+    core::{FlecsError, FlecsErrorCode, ECS_CHILD_OF},
     ecs_assert,
     sys::{
         ecs_add_id, ecs_clear, ecs_delete, ecs_enable, ecs_enable_id, ecs_entity_desc_t,
         ecs_entity_init, ecs_flatten, ecs_flatten_desc_t, ecs_get_id, ecs_get_mut_id,
-        ecs_get_target, ecs_has_id, ecs_modified_id, ecs_new_id, ecs_remove_id, ecs_set_alias,
-        ecs_set_id, ecs_set_name, ecs_set_scope, ecs_set_with, EcsComponent,
-        FLECS_IDEcsComponentID_,
+        ecs_get_target, ecs_has_id, ecs_is_alive, ecs_is_valid, ecs_lookup_child, ecs_modified_id,
+        ecs_new_id, ecs_remove_id, ecs_set_alias, ecs_set_id, ecs_set_name, ecs_set_scope,
+        ecs_set_with, EcsComponent, FLECS_IDEcsComponentID_,
     },
 };
 
@@ -108,8 +108,8 @@ impl From<IdT> for Entity {
 
 impl std::fmt::Display for Entity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(name) = self.name_optional() {
-            write!(f, "{}", name)
+        if let Some(name) = self.name_cstr_optional() {
+            write!(f, "{}", name.to_string_lossy())
         } else {
             write!(f, "{}", self.raw_id)
         }
@@ -118,7 +118,7 @@ impl std::fmt::Display for Entity {
 
 impl std::fmt::Debug for Entity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let name = self.name();
+        let name = self.name_cstr().to_string_lossy();
         let id = self.raw_id;
         let archetype_str = if let Some(s) = self.archetype().to_string() {
             s
@@ -194,19 +194,21 @@ impl Entity {
     /// * C++ API: `entity::entity`
     #[doc(alias = "entity::entity")]
     #[allow(clippy::not_unsafe_ptr_arg_deref)]
-    pub fn new_named(world: impl IntoWorld, name: &CStr) -> Self {
-        let desc = ecs_entity_desc_t {
-            name: name.as_ptr(),
-            sep: SEPARATOR.as_ptr(),
-            root_sep: SEPARATOR.as_ptr(),
-            _canary: 0,
-            id: 0,
-            symbol: std::ptr::null(),
-            use_low_id: false,
-            add: [0; 32],
-            add_expr: std::ptr::null(),
-        };
-        let id = unsafe { ecs_entity_init(world.world_ptr_mut(), &desc) };
+    pub fn new_named(world: impl IntoWorld, name: impl IntoCStr) -> Self {
+        let id = name.with_cstr(|name| {
+            let desc = ecs_entity_desc_t {
+                name: name.as_ptr(),
+                sep: SEPARATOR.as_ptr(),
+                root_sep: SEPARATOR.as_ptr(),
+                _canary: 0,
+                id: 0,
+                symbol: std::ptr::null(),
+                use_low_id: false,
+                add: [0; 32],
+                add_expr: std::ptr::null(),
+            };
+            unsafe { ecs_entity_init(world.world_ptr_mut(), &desc) }
+        });
         Self {
             entity_view: EntityView::new_from_existing(world, id),
         }
@@ -994,6 +996,32 @@ impl Entity {
         self
     }
 
+    /// Sets the components of a bundle on the entity.
+    ///
+    /// The entity is moved to the table with all components of the bundle at once, instead of
+    /// once for every component.
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle` - The components to set, for example a tuple of components.
+    pub fn insert<B: Bundle>(self, bundle: B) -> Self {
+        insert_bundle(self.world, self.raw_id, bundle);
+        self
+    }
+
+    /// Removes the components of a bundle from the entity.
+    ///
+    /// The entity is moved to the table without the components of the bundle at once, instead of
+    /// once for every component.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `B` - The bundle with the components to remove.
+    pub fn remove_bundle<B: Bundle>(self) -> Self {
+        remove_bundle::<B>(self.world, self.raw_id);
+        self
+    }
+
     /// Set a pair for an entity using the first element type and a second component ID.
     ///
     /// # Type Parameters
@@ -1203,13 +1231,64 @@ impl Entity {
     ///
     /// * C++ API: `entity_builder::set_name`
     #[doc(alias = "entity_builder::set_name")]
-    pub fn set_name(self, name: &CStr) -> Self {
-        unsafe {
+    pub fn set_name(self, name: impl IntoCStr) -> Self {
+        name.with_cstr(|name| unsafe {
             ecs_set_name(self.world, self.raw_id, name.as_ptr());
-        }
+        });
         self
     }
 
+    /// Sets the name of the entity, returning an error instead of aborting if the entity is not
+    /// alive or another entity in the same scope already has the name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A string slice that holds the name to be set.
+    ///
+    /// # Returns
+    ///
+    /// The entity, or an error if the name could not be set.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `entity_builder::set_name`
+    #[doc(alias = "entity_builder::set_name")]
+    pub fn try_set_name(self, name: impl IntoCStr) -> Result<Self, FlecsError> {
+        name.try_with_cstr(|name| self.try_set_name_cstr(name))
+            .unwrap_or_else(|| {
+                Err(FlecsError::new(
+                    FlecsErrorCode::InvalidParameter,
+                    "entity name contains a nul byte",
+                ))
+            })
+    }
+
+    fn try_set_name_cstr(self, name: &CStr) -> Result<Self, FlecsError> {
+        let entity = self.raw_id;
+        if !unsafe { ecs_is_valid(self.world, entity) && ecs_is_alive(self.world, entity) } {
+            return Err(FlecsError::new(
+                FlecsErrorCode::InvalidParameter,
+                format!("cannot name entity {}: it is not alive", entity),
+            ));
+        }
+
+        let existing = unsafe {
+            let parent = ecs_get_target(self.world, entity, ECS_CHILD_OF, 0);
+            ecs_lookup_child(self.world, parent, name.as_ptr())
+        };
+        if existing != 0 && existing != entity {
+            return Err(FlecsError::new(
+                FlecsErrorCode::AlreadyDefined,
+                format!(
+                    "conflicting entity registered with name `{}`",
+                    name.to_string_lossy()
+                ),
+            ));
+        }
+
+        Ok(self.set_name(name))
+    }
+
     /// Sets the alias name of the entity.
     ///
     /// # Arguments
@@ -1220,10 +1299,10 @@ impl Entity {
     ///
     /// * C++ API: `entity_builder::set_alias`
     #[doc(alias = "entity_builder::set_alias")]
-    pub fn set_alias_name(self, name: &CStr) -> Self {
-        unsafe {
+    pub fn set_alias_name(self, name: impl IntoCStr) -> Self {
+        name.with_cstr(|name| unsafe {
             ecs_set_alias(self.world, self.raw_id, name.as_ptr());
-        }
+        });
         self
     }
 
@@ -1506,7 +1585,7 @@ impl Entity {
     /// * C++ API: `entity_builder::get_world`
     #[doc(alias = "entity_builder::get_world")]
     pub fn scope(&self) -> ScopedWorld {
-        ScopedWorld::new(&World::new_wrap_raw_world(self.world), self.raw_id)
+        ScopedWorld::new(&World::new_unowned(self.world), self.raw_id)
     }
 
     /// Gets mut component.
@@ -1516,20 +1595,23 @@ impl Entity {
     /// the component, it will be overridden, and the value of the base component
     /// will be copied to the entity before this function returns.
     ///
+    /// The component is borrowed through a guard. While any guard of the world is alive,
+    /// structural changes (such as adding or removing components) are deferred until the last
+    /// guard is dropped, so the component can't be moved while it is borrowed.
+    ///
     /// # Type Parameters
     ///
     /// * `T`: The component to get.
     ///
     /// # Returns
     ///
-    /// A mutable ref to the component value.
+    /// A mutable guard of the component value.
     ///
     /// # See also
     ///
     /// * C++ API: `entity::get_mut`
     #[doc(alias = "entity::get_mut")]
-    #[allow(clippy::mut_from_ref)]
-    pub fn get_mut<T: ComponentId>(self) -> &'static mut T::UnderlyingType {
+    pub fn get_mut<T: ComponentId>(&mut self) -> ComponentGuardMut<'_, T::UnderlyingType> {
         // This branch will be removed in release mode since this can be determined at compile time.
         if !T::IS_ENUM {
             let component_id = T::get_id(self.world);
@@ -1542,8 +1624,12 @@ impl Entity {
             );
 
             unsafe {
-                &mut *(ecs_get_mut_id(self.world, self.raw_id, component_id)
-                    as *mut T::UnderlyingType)
+                ComponentGuardMut::new(
+                    self.world,
+                    self.raw_id,
+                    component_id,
+                    ecs_get_mut_id(self.world, self.raw_id, component_id) as *mut T::UnderlyingType,
+                )
             }
         } else {
             let component_id: IdT = T::get_id(self.world);
@@ -1552,8 +1638,13 @@ impl Entity {
             if target == 0 {
                 // if there is no matching pair for (r,*), try just r
                 unsafe {
-                    &mut *(ecs_get_mut_id(self.world, self.raw_id, component_id)
-                        as *mut T::UnderlyingType)
+                    ComponentGuardMut::new(
+                        self.world,
+                        self.raw_id,
+                        component_id,
+                        ecs_get_mut_id(self.world, self.raw_id, component_id)
+                            as *mut T::UnderlyingType,
+                    )
                 }
             } else {
                 // get constant value from constant entity
@@ -1568,18 +1659,38 @@ impl Entity {
                     std::any::type_name::<T>()
                 );
 
-                unsafe { &mut *constant_value }
+                unsafe { ComponentGuardMut::new(self.world, target, component_id, constant_value) }
             }
         }
     }
 
-    pub fn get_callback_mut<T: ComponentId + 'static>(
-        self,
-        callback: impl FnOnce(&'static mut T::UnderlyingType),
+    /// Gets mut component and passes it to the callback.
+    ///
+    /// The reference can't escape the callback. Structural changes made while the callback runs
+    /// are deferred until it returns.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: The component to get.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback to invoke with the component
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the entity has the component and the callback was invoked
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `entity::get_mut`
+    #[doc(alias = "entity::get_mut")]
+    pub fn get_callback_mut<T: ComponentId>(
+        mut self,
+        callback: impl FnOnce(&mut T::UnderlyingType),
     ) -> bool {
         if self.has::<T>() {
-            let comp = self.get_mut::<T>();
-            callback(comp);
+            callback(&mut self.get_mut::<T>());
             true
         } else {
             false
@@ -1670,7 +1781,10 @@ impl Entity {
     ///
     /// * C++ API: `entity::get_mut`
     #[doc(alias = "entity::get_mut")]
-    pub fn get_pair_first_id_mut<First>(self, second: impl IntoEntityId) -> &'static mut First
+    pub fn get_pair_first_id_mut<First>(
+        &mut self,
+        second: impl IntoEntityId,
+    ) -> ComponentGuardMut<'_, First>
     where
         First: ComponentId + ComponentType<Struct> + NotEmptyComponent,
     {
@@ -1683,14 +1797,17 @@ impl Entity {
             std::any::type_name::<First>()
         );
 
+        let pair_id = ecs_pair(component_id, second.get_id());
+
         // SAFETY: The pointer is valid because ecs_get_mut_id adds the component if not present, so
         // it is guaranteed to be valid
         unsafe {
-            &mut *(ecs_get_mut_id(
+            ComponentGuardMut::new(
                 self.world,
                 self.raw_id,
-                ecs_pair(component_id, second.get_id()),
-            ) as *mut First)
+                pair_id,
+                ecs_get_mut_id(self.world, self.raw_id, pair_id) as *mut First,
+            )
         }
     }
 
@@ -1706,7 +1823,7 @@ impl Entity {
     ///
     /// * C++ API: `entity::get_mut`
     #[doc(alias = "entity::get_mut")]
-    pub fn get_pair_first_mut<First, Second>(&mut self) -> &'static mut First
+    pub fn get_pair_first_mut<First, Second>(&mut self) -> ComponentGuardMut<'_, First>
     where
         First: ComponentId + ComponentType<Struct> + NotEmptyComponent,
         Second: ComponentId + ComponentType<Struct>,
@@ -1729,7 +1846,10 @@ impl Entity {
     ///
     /// * C++ API: `entity::get_mut`
     #[doc(alias = "entity::get_mut")]
-    pub fn get_pair_second_id_mut<Second>(self, first: impl IntoEntityId) -> &'static mut Second
+    pub fn get_pair_second_id_mut<Second>(
+        &mut self,
+        first: impl IntoEntityId,
+    ) -> ComponentGuardMut<'_, Second>
     where
         Second: ComponentId + ComponentType<Struct> + NotEmptyComponent,
     {
@@ -1742,14 +1862,17 @@ impl Entity {
             std::any::type_name::<Second>()
         );
 
+        let pair_id = ecs_pair(first.get_id(), component_id);
+
         // SAFETY: The pointer is valid because ecs_get_mut_id adds the component if not present, so
         // it is guaranteed to be valid
         unsafe {
-            &mut *(ecs_get_mut_id(
+            ComponentGuardMut::new(
                 self.world,
                 self.raw_id,
-                ecs_pair(first.get_id(), component_id),
-            ) as *mut Second)
+                pair_id,
+                ecs_get_mut_id(self.world, self.raw_id, pair_id) as *mut Second,
+            )
         }
     }
 
@@ -1765,7 +1888,7 @@ impl Entity {
     ///
     /// * C++ API: `entity::get_mut`
     #[doc(alias = "entity::get_mut")]
-    pub fn get_pair_second_mut<First, Second>(&mut self) -> &'static mut Second
+    pub fn get_pair_second_mut<First, Second>(&mut self) -> ComponentGuardMut<'_, Second>
     where
         First: ComponentId + ComponentType<Struct> + EmptyComponent,
         Second: ComponentId + ComponentType<Struct> + NotEmptyComponent,
@@ -1991,3 +2114,28 @@ impl Entity {
         self.entity_view
     }
 }
+
+/// Json mixin implementation
+#[cfg(feature = "flecs_json")]
+impl Entity {
+    /// Deserialize an entity from JSON, as produced by `EntityView::to_json`.
+    ///
+    /// The ids and component values in the JSON are added to the entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON string to deserialize.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `entity::from_json`
+    #[doc(alias = "entity::from_json")]
+    pub fn from_json(self, json: &str) -> Result<Self, crate::addons::json::JsonError> {
+        let json = crate::addons::json::json_to_cstring(json)?;
+        let desc = crate::sys::ecs_from_json_desc_t::default();
+        crate::addons::json::json_parse(|| unsafe {
+            crate::sys::ecs_entity_from_json(self.world, self.raw_id, json.as_ptr(), &desc)
+        })?;
+        Ok(self)
+    }
+}
@@ -4,6 +4,7 @@ use std::{
     mem::MaybeUninit,
     ops::{Deref, DerefMut},
     ptr,
+    str::Utf8Error,
 };
 
 use flecs_ecs_sys::{
@@ -31,13 +32,14 @@ use crate::{
 use super::{
     archetype::Archetype,
     c_types::{EntityT, IdT, TypeT, WorldT, SEPARATOR},
+    component_guard::ComponentGuard,
     component_registration::{ComponentId, ComponentType, Enum, Struct},
     ecs_add_pair, ecs_pair, ecs_pair_first, ecs_pair_second, ecs_record_to_row,
     entity::Entity,
     id::Id,
     table::{Table, TableRange},
     world::World,
-    CachedEnumData, EmptyComponent, EventBuilderImpl, IntoComponentId, IntoEntityId,
+    CachedEnumData, EmptyComponent, EventBuilderImpl, IntoCStr, IntoComponentId, IntoEntityId,
     IntoEntityIdExt, IntoWorld, IterT, NotEmptyComponent, ObserverEntityBindingCtx, ECS_ANY,
     ECS_CHILD_OF, ECS_WILDCARD,
 };
@@ -171,36 +173,35 @@ impl EntityView {
     ///
     /// if the entity has no name, this will return an empty string
     ///
+    /// The name is stored by the world and is freed when the entity is renamed or deleted, so the
+    /// returned string borrows the entity handle. Copy it with `to_owned` to keep it around.
+    ///
+    /// # Returns
+    ///
+    /// The name, or an error if the name is not valid UTF-8.
+    ///
     /// # See also
     ///
     /// * C++ API: `entity_view::name`
     #[doc(alias = "entity_view::name")]
-    pub fn name(self) -> &'static str {
-        let name_ptr = unsafe { ecs_get_name(self.world, self.raw_id) };
-
-        if name_ptr.is_null() {
-            return "";
-        }
-
-        unsafe { CStr::from_ptr(name_ptr).to_str().unwrap_or("") }
+    pub fn name(&self) -> Result<&str, Utf8Error> {
+        self.name_cstr().to_str()
     }
 
     /// Returns the entity name.
     ///
     /// if the entity has no name, this will return none
     ///
+    /// # Returns
+    ///
+    /// The name, or an error if the name is not valid UTF-8.
+    ///
     /// # See also
     ///
     /// * C++ API: `entity_view::name`
     #[doc(alias = "entity_view::name")]
-    pub fn name_optional(self) -> Option<&'static str> {
-        let name_ptr = unsafe { ecs_get_name(self.world, self.raw_id) };
-
-        if name_ptr.is_null() {
-            return None;
-        }
-
-        Some(unsafe { CStr::from_ptr(name_ptr).to_str().unwrap_or("") })
+    pub fn name_optional(&self) -> Option<Result<&str, Utf8Error>> {
+        self.name_cstr_optional().map(CStr::to_str)
     }
 
     /// Returns the entity name as a `CStr`.
@@ -210,15 +211,8 @@ impl EntityView {
     /// # See also
     ///
     /// * C++ API: `entity_view::name`
-    pub fn name_cstr(self) -> &'static CStr {
-        unsafe {
-            let name_ptr = ecs_get_name(self.world, self.raw_id);
-            if name_ptr.is_null() {
-                c""
-            } else {
-                CStr::from_ptr(name_ptr)
-            }
-        }
+    pub fn name_cstr(&self) -> &CStr {
+        self.name_cstr_optional().unwrap_or_default()
     }
 
     /// Returns the entity name as a `CStr`.
@@ -228,7 +222,7 @@ impl EntityView {
     /// # See also
     ///
     /// * C++ API: `entity_view::name`
-    pub fn name_cstr_optional(self) -> Option<&'static CStr> {
+    pub fn name_cstr_optional(&self) -> Option<&CStr> {
         let name_ptr = unsafe { ecs_get_name(self.world, self.raw_id) };
 
         if name_ptr.is_null() {
@@ -244,7 +238,7 @@ impl EntityView {
     ///
     /// * C++ API: `entity_view::symbol`
     #[doc(alias = "entity_view::symbol")]
-    pub fn symbol(self) -> &'static CStr {
+    pub fn symbol(&self) -> &CStr {
         unsafe { CStr::from_ptr(ecs_get_symbol(self.world, self.raw_id)) }
     }
 
@@ -257,7 +251,7 @@ impl EntityView {
     ///
     /// * C++ API: `entity_view::path`
     #[doc(alias = "entity_view::path")]
-    pub fn path_w_sep(self, sep: &CStr, init_sep: &CStr) -> Option<String> {
+    pub fn path_w_sep(self, sep: impl IntoCStr, init_sep: impl IntoCStr) -> Option<String> {
         self.path_from_id_w_sep(0, sep, init_sep)
     }
 
@@ -283,18 +277,20 @@ impl EntityView {
     pub fn path_from_id_w_sep(
         &self,
         parent: impl IntoEntityId,
-        sep: &CStr,
-        init_sep: &CStr,
+        sep: impl IntoCStr,
+        init_sep: impl IntoCStr,
     ) -> Option<String> {
-        let raw_ptr = unsafe {
-            ecs_get_path_w_sep(
-                self.world,
-                parent.get_id(),
-                self.raw_id,
-                sep.as_ptr(),
-                init_sep.as_ptr(),
-            )
-        };
+        let raw_ptr = sep.with_cstr(|sep| {
+            init_sep.with_cstr(|init_sep| unsafe {
+                ecs_get_path_w_sep(
+                    self.world,
+                    parent.get_id(),
+                    self.raw_id,
+                    sep.as_ptr(),
+                    init_sep.as_ptr(),
+                )
+            })
+        });
 
         if raw_ptr.is_null() {
             return None;
@@ -354,7 +350,11 @@ impl EntityView {
         self.path_from_id_w_sep(T::get_id(self.world), SEPARATOR, SEPARATOR)
     }
 
-    pub fn path_from_w_sep<T: ComponentId>(&self, sep: &CStr, init_sep: &CStr) -> Option<String> {
+    pub fn path_from_w_sep<T: ComponentId>(
+        &self,
+        sep: impl IntoCStr,
+        init_sep: impl IntoCStr,
+    ) -> Option<String> {
         self.path_from_id_w_sep(T::get_id(self.world), sep, init_sep)
     }
 
@@ -665,20 +665,24 @@ impl EntityView {
     /// Get (struct) Component from entity
     /// use `.unwrap()` or `.unwrap_unchecked()` or `get_unchecked()` if you're sure the entity has the component
     ///
+    /// The component is borrowed through a guard. While any guard of the world is alive,
+    /// structural changes (such as adding or removing components) are deferred until the last
+    /// guard is dropped, so the component can't be moved while it is borrowed.
+    ///
     /// # Type Parameters
     ///
     /// * `T` - The component type to get
     ///
     /// # Returns
     ///
-    /// * Option<&T> - The component, None if the entity does not have the component
+    /// * `Option<ComponentGuard<T>>` - The component, None if the entity does not have the component
     ///
     /// # See also
     ///
     /// * C++ API: `entity_view::get`
     #[doc(alias = "entity_view::get")]
     #[inline(always)]
-    pub fn get<T: ComponentId>(self) -> Option<&'static T::UnderlyingType> {
+    pub fn get<T: ComponentId>(&self) -> Option<ComponentGuard<'_, T::UnderlyingType>> {
         if !T::IS_ENUM {
             if T::IS_TAG {
                 ecs_assert!(
@@ -692,8 +696,13 @@ impl EntityView {
                 let component_id = T::get_id(self.world);
 
                 unsafe {
-                    (ecs_get_id(self.world, self.raw_id, component_id) as *const T::UnderlyingType)
-                        .as_ref()
+                    ComponentGuard::from_ptr(
+                        self.world,
+                        self.raw_id,
+                        component_id,
+                        ecs_get_id(self.world, self.raw_id, component_id)
+                            as *const T::UnderlyingType,
+                    )
                 }
             }
         } else {
@@ -703,8 +712,13 @@ impl EntityView {
             if target == 0 {
                 // if there is no matching pair for (r,*), try just r
                 unsafe {
-                    (ecs_get_id(self.world, self.raw_id, component_id) as *const T::UnderlyingType)
-                        .as_ref()
+                    ComponentGuard::from_ptr(
+                        self.world,
+                        self.raw_id,
+                        component_id,
+                        ecs_get_id(self.world, self.raw_id, component_id)
+                            as *const T::UnderlyingType,
+                    )
                 }
             } else {
                 // get constant value from constant entity
@@ -719,17 +733,37 @@ impl EntityView {
                     std::any::type_name::<T>()
                 );
 
-                unsafe { constant_value.as_ref() }
+                unsafe {
+                    ComponentGuard::from_ptr(self.world, target, component_id, constant_value)
+                }
             }
         }
     }
 
-    pub fn get_callback<T: ComponentId + 'static>(
-        self,
-        callback: impl FnOnce(&'static T::UnderlyingType),
-    ) -> bool {
+    /// Get (struct) Component from entity and pass it to the callback.
+    ///
+    /// The reference can't escape the callback. Structural changes made while the callback runs
+    /// are deferred until it returns.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The component type to get
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback to invoke with the component
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the entity has the component and the callback was invoked
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `entity_view::get`
+    #[doc(alias = "entity_view::get")]
+    pub fn get_callback<T: ComponentId>(self, callback: impl FnOnce(&T::UnderlyingType)) -> bool {
         if let Some(component) = self.get::<T>() {
-            callback(component);
+            callback(&component);
             return true;
         }
         false
@@ -766,7 +800,7 @@ impl EntityView {
                 );
                 panic!("cannot get a tag component, it has no size");
             } else {
-                let component_id = T::get_id_unchecked();
+                let component_id = T::get_id_unchecked(self.world);
 
                 let ptr =
                     ecs_get_id(self.world, self.raw_id, component_id) as *const T::UnderlyingType;
@@ -873,7 +907,10 @@ impl EntityView {
     ///
     /// * C++ API: `entity_view::get`
     #[doc(alias = "entity_view::get")]
-    pub fn get_pair_first_id<First>(self, second: impl IntoEntityId) -> Option<&'static First>
+    pub fn get_pair_first_id<First>(
+        &self,
+        second: impl IntoEntityId,
+    ) -> Option<ComponentGuard<'_, First>>
     where
         First: ComponentId + ComponentType<Struct> + NotEmptyComponent,
     {
@@ -886,13 +923,15 @@ impl EntityView {
             std::any::type_name::<First>()
         );
 
+        let pair_id = ecs_pair(component_id, second.get_id());
+
         unsafe {
-            (ecs_get_id(
+            ComponentGuard::from_ptr(
                 self.world,
                 self.raw_id,
-                ecs_pair(component_id, second.get_id()),
-            ) as *const First)
-                .as_ref()
+                pair_id,
+                ecs_get_id(self.world, self.raw_id, pair_id) as *const First,
+            )
         }
     }
 
@@ -912,7 +951,7 @@ impl EntityView {
     ///
     /// * C++ API: `entity_view::get`
     #[doc(alias = "entity_view::get")]
-    pub fn get_pair_first<First, Second>(self) -> Option<&'static First>
+    pub fn get_pair_first<First, Second>(&self) -> Option<ComponentGuard<'_, First>>
     where
         First: ComponentId + ComponentType<Struct> + NotEmptyComponent,
         Second: ComponentId,
@@ -939,7 +978,10 @@ impl EntityView {
     ///
     /// * C++ API: `entity_view::get`
     #[doc(alias = "entity_view::get")]
-    pub fn get_pair_second_id<Second>(self, first: impl IntoEntityId) -> Option<&'static Second>
+    pub fn get_pair_second_id<Second>(
+        &self,
+        first: impl IntoEntityId,
+    ) -> Option<ComponentGuard<'_, Second>>
     where
         Second: ComponentId + ComponentType<Struct> + NotEmptyComponent,
     {
@@ -951,13 +993,15 @@ impl EntityView {
             std::any::type_name::<Second>()
         );
 
+        let pair_id = ecs_pair(first.get_id(), component_id);
+
         unsafe {
-            (ecs_get_id(
+            ComponentGuard::from_ptr(
                 self.world,
                 self.raw_id,
-                ecs_pair(first.get_id(), component_id),
-            ) as *const Second)
-                .as_ref()
+                pair_id,
+                ecs_get_id(self.world, self.raw_id, pair_id) as *const Second,
+            )
         }
     }
 
@@ -973,7 +1017,7 @@ impl EntityView {
     ///
     /// * C++ API: `entity_view::get`
     #[doc(alias = "entity_view::get")]
-    pub fn get_pair_second<First, Second>(self) -> Option<&'static Second>
+    pub fn get_pair_second<First, Second>(&self) -> Option<ComponentGuard<'_, Second>>
     where
         First: ComponentId + ComponentType<Struct> + EmptyComponent,
         Second: ComponentId + ComponentType<Struct> + NotEmptyComponent,
@@ -1226,13 +1270,13 @@ impl EntityView {
     /// * C++ API: `entity_view::lookup`
     #[doc(alias = "entity_view::lookup")]
     #[inline(always)]
-    pub fn lookup_name_optional(self, path: &CStr, search_path: bool) -> Option<Entity> {
+    pub fn lookup_name_optional(self, path: impl IntoCStr, search_path: bool) -> Option<Entity> {
         ecs_assert!(
             self.raw_id != 0,
             FlecsErrorCode::InvalidParameter,
             "invalid lookup from null handle"
         );
-        let id = unsafe {
+        let id = path.with_cstr(|path| unsafe {
             ecs_lookup_path_w_sep(
                 self.world,
                 self.raw_id,
@@ -1241,7 +1285,7 @@ impl EntityView {
                 SEPARATOR.as_ptr(),
                 search_path,
             )
-        };
+        });
 
         if id == 0 {
             None
@@ -1662,7 +1706,7 @@ impl EntityView {
     #[doc(alias = "entity_view::mut")]
     pub fn mut_current_stage(self, stage: impl IntoWorld) -> Entity {
         ecs_assert!(
-            !World::new_wrap_raw_world(stage.world_ptr_mut()).is_readonly(),
+            !World::new_unowned(stage.world_ptr_mut()).is_readonly(),
             FlecsErrorCode::InvalidParameter,
             "cannot use readonly world/stage to create mutable handle"
         );
@@ -1691,7 +1735,7 @@ impl EntityView {
         T: IntoEntityId + IntoWorld,
     {
         ecs_assert!(
-            !World::new_wrap_raw_world(entity.world_ptr_mut()).is_readonly(),
+            !World::new_unowned(entity.world_ptr_mut()).is_readonly(),
             FlecsErrorCode::InvalidParameter,
             "cannot use entity created for readonly world/stage to create mutable handle"
         );
@@ -1722,14 +1766,14 @@ impl EntityView {
     ///
     /// # Returns
     ///
-    /// * `Some(&T)` - The enum constant if the entity is a constant.
+    /// * `Some(ComponentGuard<T>)` - The enum constant if the entity is a constant.
     /// * `None` - If the entity is not a constant.
     ///
     /// # See also
     ///
     /// * C++ API: `entity_view::to_constant`
     #[doc(alias = "entity_view::to_constant")]
-    pub fn to_constant<T: ComponentId>(self) -> Option<&'static T::UnderlyingType> {
+    pub fn to_constant<T: ComponentId>(&self) -> Option<ComponentGuard<'_, T::UnderlyingType>> {
         let ptr = self.get::<T>();
         ecs_assert!(
             ptr.is_some(),
@@ -2250,3 +2294,34 @@ impl EntityView {
         }
     }
 }
+
+/// Json mixin implementation
+#[cfg(feature = "flecs_json")]
+impl EntityView {
+    /// Serialize the entity to JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc` - The options that determine what is serialized.
+    ///
+    /// # Returns
+    ///
+    /// The JSON string, or an error if the entity contains components with invalid values.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `entity_view::to_json`
+    #[doc(alias = "entity_view::to_json")]
+    pub fn to_json(
+        &self,
+        desc: &crate::addons::json::EntityToJsonDesc,
+    ) -> Result<String, crate::addons::json::JsonError> {
+        unsafe {
+            crate::addons::json::json_from_raw(crate::sys::ecs_entity_to_json(
+                self.world,
+                self.raw_id,
+                &desc.desc,
+            ))
+        }
+    }
+}
@@ -10,12 +10,13 @@ use super::{
     component_registration::ComponentId,
     event::EventBuilderImpl,
     world::World,
+    world_ref::WorldRef,
     IntoEntityId,
 };
 
 pub struct EventBuilder {
     /// non-owning world reference
-    pub world: World,
+    pub world: WorldRef,
     pub(crate) desc: ecs_event_desc_t,
     pub(crate) ids: TypeT,
     pub(crate) ids_array: [IdT; FLECS_EVENT_DESC_MAX as usize],
@@ -35,7 +36,7 @@ impl EventBuilder {
     #[doc(alias = "event_builder_base::event_builder_base")]
     pub fn new(world: &World, event: impl IntoEntityId) -> Self {
         let mut obj = Self {
-            world: world.clone(),
+            world: world.handle(),
             desc: Default::default(),
             ids: Default::default(),
             ids_array: Default::default(),
@@ -1,20 +1,21 @@
 //! Filters are cheaper to create, but slower to iterate than queries.
 
 use crate::sys::{
-    ecs_abort_, ecs_filter_copy, ecs_filter_desc_t, ecs_filter_fini, ecs_filter_init,
-    ecs_filter_iter, ecs_filter_move, ecs_filter_next, ecs_get_entity, ecs_os_api,
+    ecs_filter_copy, ecs_filter_desc_t, ecs_filter_fini, ecs_filter_init, ecs_filter_iter,
+    ecs_filter_move, ecs_filter_next, ecs_get_entity, ecs_os_api,
 };
 
 use super::{
-    c_types::FilterT, entity::Entity, iterable::Iterable, world::World, FlecsErrorCode, IntoWorld,
-    IterAPI, IterOperations,
+    c_types::FilterT, capture_log, entity::Entity, iterable::Iterable, world::World,
+    world_ref::WorldRef, FlecsError, FlecsErrorCode, IntoWorld, IterAPI, IterOperations,
 };
+use crate::ecs_abort;
 
 pub struct FilterView<'a, T>
 where
     T: Iterable<'a>,
 {
-    world: World,
+    world: WorldRef,
     filter_ptr: *const FilterT,
     _phantom: std::marker::PhantomData<&'a T>,
 }
@@ -49,7 +50,7 @@ where
     #[doc(alias = "filter_view::filter_view")]
     pub fn new(world: &World, filter: *const FilterT) -> Self {
         Self {
-            world: world.clone(),
+            world: world.handle(),
             _phantom: std::marker::PhantomData,
             filter_ptr: filter as *const FilterT,
         }
@@ -61,7 +62,7 @@ pub struct Filter<'a, T>
 where
     T: Iterable<'a>,
 {
-    world: World,
+    world: WorldRef,
     _phantom: std::marker::PhantomData<&'a T>,
     filter: FilterT,
 }
@@ -87,7 +88,7 @@ where
         desc.storage = &mut filter;
         unsafe { ecs_filter_init(world.raw_world, &desc) };
         Filter {
-            world: world.clone(),
+            world: world.handle(),
             _phantom: std::marker::PhantomData,
             filter,
         }
@@ -107,7 +108,7 @@ where
     #[allow(clippy::not_unsafe_ptr_arg_deref)]
     pub fn new_ownership(world: &World, filter: *mut FilterT) -> Self {
         let mut filter_obj = Filter {
-            world: world.clone(),
+            world: world.handle(),
             _phantom: std::marker::PhantomData,
             filter: Default::default(),
         };
@@ -132,8 +133,37 @@ where
     #[doc(alias = "filter::filter")]
     #[allow(clippy::not_unsafe_ptr_arg_deref)]
     pub fn new_from_desc(world: &World, desc: *mut ecs_filter_desc_t) -> Self {
+        match Self::try_new_from_desc(world, desc) {
+            Ok(filter) => filter,
+            Err(err) => {
+                ecs_abort!(err);
+            }
+        }
+    }
+
+    /// Create a new filter from a filter descriptor, returning an error instead of aborting
+    /// when the descriptor is invalid, for example when its expression fails to parse.
+    ///
+    /// # Arguments
+    ///
+    /// * `world`: the world to create the filter from
+    /// * `desc`: the filter descriptor to create the filter from
+    ///
+    /// # Returns
+    ///
+    /// The filter, or an error carrying the messages flecs logged while creating it.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `filter::filter`
+    #[doc(alias = "filter::filter")]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    pub fn try_new_from_desc(
+        world: &World,
+        desc: *mut ecs_filter_desc_t,
+    ) -> Result<Self, FlecsError> {
         let mut filter_obj = Filter {
-            world: world.clone(),
+            world: world.handle(),
             _phantom: std::marker::PhantomData,
             filter: Default::default(),
         };
@@ -142,20 +172,10 @@ where
             (*desc).storage = &mut filter_obj.filter;
         }
 
-        unsafe {
-            if ecs_filter_init(filter_obj.world.raw_world, desc).is_null() {
-                ecs_abort_(
-                    FlecsErrorCode::InvalidParameter.to_int(),
-                    file!().as_ptr() as *const i8,
-                    line!() as i32,
-                    std::ptr::null(),
-                );
-
-                if let Some(abort_func) = ecs_os_api.abort_ {
-                    abort_func();
-                }
-            }
+        let (filter, log) =
+            capture_log(|| unsafe { ecs_filter_init(filter_obj.world.raw_world, desc) });
 
+        unsafe {
             if !(*desc).terms_buffer.is_null() {
                 if let Some(free_func) = ecs_os_api.free_ {
                     free_func((*desc).terms_buffer as *mut _);
@@ -163,7 +183,15 @@ where
             }
         }
 
-        filter_obj
+        if filter.is_null() {
+            return Err(FlecsError::new(
+                FlecsErrorCode::InvalidParameter,
+                "failed to create filter",
+            )
+            .with_log(log));
+        }
+
+        Ok(filter_obj)
     }
 }
 
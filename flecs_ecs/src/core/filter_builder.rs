@@ -10,8 +10,9 @@ use super::{
     term::{Term, TermBuilder},
     type_to_inout,
     world::World,
-    CachedEnumData, IdT, InOutType, IntoComponentId, IntoEntityId, IntoEntityIdExt, WorldT,
-    ECS_WILDCARD,
+    world_ref::WorldRef,
+    CachedEnumData, FlecsError, IdT, InOutType, IntoCStr, IntoComponentId, IntoEntityId,
+    IntoEntityIdExt, IntoStaticCStr, WorldT, ECS_WILDCARD,
 };
 #[cfg(any(debug_assertions, feature = "flecs_force_enable_ecs_asserts"))]
 use crate::{core::FlecsErrorCode, sys::ecs_term_is_initialized};
@@ -32,7 +33,7 @@ where
     pub desc: ecs_filter_desc_t,
     expr_count: i32,
     pub(crate) term: Term,
-    pub world: World,
+    pub world: WorldRef,
     pub next_term_index: i32,
     _phantom: std::marker::PhantomData<&'a T>,
 }
@@ -56,7 +57,7 @@ where
             desc: Default::default(),
             expr_count: 0,
             term: Term::new_world_only(world),
-            world: world.clone(),
+            world: world.handle(),
             next_term_index: 0,
             _phantom: std::marker::PhantomData,
         };
@@ -84,24 +85,25 @@ where
     ///
     /// * C++ API: `filter_builder::filter_builder`
     #[doc(alias = "filter_builder::filter_builder")]
-    pub fn new_named(world: &World, name: &CStr) -> Self {
+    pub fn new_named(world: &World, name: impl IntoCStr) -> Self {
         let mut obj = Self {
             desc: Default::default(),
             expr_count: 0,
             term: Term::default(),
-            world: world.clone(),
+            world: world.handle(),
             next_term_index: 0,
             _phantom: std::marker::PhantomData,
         };
 
-        let entity_desc = ecs_entity_desc_t {
-            name: name.as_ptr(),
-            sep: SEPARATOR.as_ptr(),
-            root_sep: SEPARATOR.as_ptr(),
-            ..Default::default()
-        };
-
-        obj.desc.entity = unsafe { ecs_entity_init(world.raw_world, &entity_desc) };
+        obj.desc.entity = name.with_cstr(|name| {
+            let entity_desc = ecs_entity_desc_t {
+                name: name.as_ptr(),
+                sep: SEPARATOR.as_ptr(),
+                root_sep: SEPARATOR.as_ptr(),
+                ..Default::default()
+            };
+            unsafe { ecs_entity_init(world.raw_world, &entity_desc) }
+        });
         T::populate(&mut obj);
         obj
     }
@@ -123,7 +125,7 @@ where
             desc: *desc,
             expr_count: 0,
             term: Term::default(),
-            world: world.clone(),
+            world: world.handle(),
             next_term_index: term_index,
             _phantom: std::marker::PhantomData,
         }
@@ -133,6 +135,16 @@ where
     pub fn next_term(&mut self) {
         self.next_term_index += 1;
     }
+
+    /// Build the filter, returning an error instead of aborting when the builder describes an
+    /// invalid filter, for example an expression that fails to parse.
+    ///
+    /// # Returns
+    ///
+    /// The filter, or an error carrying the messages flecs logged while creating it.
+    pub fn try_build(&mut self) -> Result<Filter<'a, T>, FlecsError> {
+        Filter::<'a, T>::try_new_from_desc(&self.world, &mut self.desc as *mut _)
+    }
 }
 
 impl<'a, T> Filterable for FilterBuilder<'a, T>
@@ -248,14 +260,14 @@ pub trait FilterBuilderImpl: TermBuilder {
     ///
     /// * C++ API: `filter_builder_i::expr`
     #[doc(alias = "filter_builder_i::expr")]
-    fn expr(&mut self, expr: &CStr) -> &mut Self {
+    fn expr(&mut self, expr: impl IntoStaticCStr) -> &mut Self {
         ecs_assert!(
             *self.expr_count_mut() == 0,
             FlecsErrorCode::InvalidOperation,
             "filter_builder::expr() called more than once"
         );
 
-        self.desc_filter_mut().expr = expr.as_ptr();
+        self.desc_filter_mut().expr = self.term_mut().store_name(expr);
         *self.expr_count_mut() += 1;
         self
     }
@@ -297,7 +309,7 @@ pub trait FilterBuilderImpl: TermBuilder {
     ///
     /// * C++ API: `filter_builder_i::with`
     #[doc(alias = "filter_builder_i::with")]
-    fn with_pair_name<First: ComponentId>(&mut self, second: &'static CStr) -> &mut Self {
+    fn with_pair_name<First: ComponentId>(&mut self, second: impl IntoStaticCStr) -> &mut Self {
         self.term_with_pair_name::<First>(second)
     }
 
@@ -371,7 +383,7 @@ pub trait FilterBuilderImpl: TermBuilder {
     ///
     /// * C++ API: `filter_builder_i::without`
     #[doc(alias = "filter_builder_i::without")]
-    fn without_pair_name<First: ComponentId>(&mut self, second: &'static CStr) -> &mut Self {
+    fn without_pair_name<First: ComponentId>(&mut self, second: impl IntoStaticCStr) -> &mut Self {
         self.term_with_pair_name::<First>(second).not()
     }
 
@@ -568,12 +580,9 @@ pub trait FilterBuilderImpl: TermBuilder {
     ///
     /// * C++ API: `filter_builder_i::term`
     #[doc(alias = "filter_builder_i::term")]
-    fn term_with_name(&mut self, name: &'static CStr) -> &mut Self {
+    fn term_with_name(&mut self, name: impl IntoStaticCStr) -> &mut Self {
         self.term();
-        unsafe {
-            *self.term_ptr_mut() = Term::default().select_first_name(name).move_raw_term();
-        }
-        self
+        self.select_first_name(name)
     }
 
     /// set term with Pair Names
@@ -582,15 +591,13 @@ pub trait FilterBuilderImpl: TermBuilder {
     ///
     /// * C++ API: `filter_builder_i::term`
     #[doc(alias = "filter_builder_i::term")]
-    fn term_with_pair_names(&mut self, first: &'static CStr, second: &'static CStr) -> &mut Self {
+    fn term_with_pair_names(
+        &mut self,
+        first: impl IntoStaticCStr,
+        second: impl IntoStaticCStr,
+    ) -> &mut Self {
         self.term();
-        unsafe {
-            *self.term_ptr_mut() = Term::default()
-                .select_first_name(first)
-                .select_second_name(second)
-                .move_raw_term();
-        }
-        self
+        self.select_first_name(first).select_second_name(second)
     }
 
     /// set term with Pair Id Name
@@ -602,15 +609,9 @@ pub trait FilterBuilderImpl: TermBuilder {
     fn term_with_pair_id_name(
         &mut self,
         first: impl IntoEntityId,
-        second: &'static CStr,
+        second: impl IntoStaticCStr,
     ) -> &mut Self {
-        self.term();
-        unsafe {
-            *self.term_ptr_mut() = Term::new_id(None, first.get_id())
-                .select_second_name(second)
-                .move_raw_term();
-        }
-        self
+        self.term_with_id(first.get_id()).select_second_name(second)
     }
 
     /// set term with Pair
@@ -630,7 +631,10 @@ pub trait FilterBuilderImpl: TermBuilder {
     ///
     /// * C++ API: `filter_builder_i::term`
     #[doc(alias = "filter_builder_i::term")]
-    fn term_with_pair_name<First: ComponentId>(&mut self, second: &'static CStr) -> &mut Self {
+    fn term_with_pair_name<First: ComponentId>(
+        &mut self,
+        second: impl IntoStaticCStr,
+    ) -> &mut Self {
         let world = self.world_ptr_mut();
         self.term_with_id(First::get_id(world))
             .select_second_name(second)
@@ -3,8 +3,8 @@
 use std::{ffi::CStr, sync::OnceLock};
 
 use super::{
-    ComponentId, ComponentInfo, ComponentType, EmptyComponent, FlecsConstantId, IdComponent,
-    IntoWorld, NoneEnum, Struct,
+    ComponentId, ComponentInfo, ComponentType, EmptyComponent, FlecsConstantId, FlecsIsATag,
+    IdComponent, IntoWorld, NoneEnum, Struct,
 };
 
 use super::c_types::*;
@@ -23,6 +23,7 @@ macro_rules! create_pre_registered_component {
             const IS_TAG: bool = true;
             const IMPLS_CLONE: bool = false;
             const IMPLS_DEFAULT: bool = false;
+            type TagType = FlecsIsATag;
         }
 
         impl EmptyComponent for $struct_name {}
@@ -51,7 +52,7 @@ macro_rules! create_pre_registered_component {
                 $const_name
             }
 
-            unsafe fn get_id_unchecked() -> IdT {
+            unsafe fn get_id_unchecked(_world: impl IntoWorld) -> IdT {
                 $const_name
             }
 
@@ -0,0 +1,133 @@
+//! Dispatch of Rust closures used to group the tables of queries.
+//!
+//! flecs passes the `group_by_ctx` of a query to the `group_by`, `on_group_create` and
+//! `on_group_delete` actions, so the closures are stored there and called from `extern "C"`
+//! trampolines. Group contexts created by a closure are boxed, and dropped by the
+//! `on_group_delete` trampoline.
+
+use std::{os::raw::c_void, ptr};
+
+use crate::{ecs_abort, sys::ecs_query_desc_t};
+
+use super::{c_types::TableT, FlecsErrorCode, IdT, WorldT};
+
+/// Computes the group id of a table.
+pub(crate) type GroupByClosure = dyn Fn(*mut WorldT, *mut TableT, IdT) -> u64 + Send + Sync;
+
+/// Creates the context of a group, returned as a pointer to a boxed value.
+pub(crate) type GroupCreateClosure = dyn Fn(*mut WorldT, u64) -> *mut c_void + Send + Sync;
+
+/// Frees the context of a group, passed as a pointer to a boxed value.
+pub(crate) type GroupDeleteClosure = dyn Fn(*mut WorldT, u64, *mut c_void) + Send + Sync;
+
+/// The closures of a query that groups its tables, stored in its `group_by_ctx`.
+#[derive(Default)]
+pub(crate) struct GroupByCtx {
+    pub(crate) group_by: Option<Box<GroupByClosure>>,
+    pub(crate) on_create: Option<Box<GroupCreateClosure>>,
+    pub(crate) on_delete: Option<Box<GroupDeleteClosure>>,
+}
+
+/// Get the closures of the query described by `desc`, which are created on first use.
+///
+/// # Panics
+///
+/// If the `group_by_ctx` of the query was set to a context not owned by the closures.
+pub(crate) fn group_by_ctx_mut(desc: &mut ecs_query_desc_t) -> &mut GroupByCtx {
+    if desc.group_by_ctx.is_null() {
+        desc.group_by_ctx = Box::into_raw(Box::<GroupByCtx>::default()) as *mut c_void;
+        desc.group_by_ctx_free = Some(group_by_ctx_free);
+    }
+
+    if !owns_group_by_ctx(desc) {
+        ecs_abort!(
+            FlecsErrorCode::InvalidOperation,
+            "group_by closures can't be combined with a group_by_ctx"
+        );
+    }
+
+    unsafe { &mut *(desc.group_by_ctx as *mut GroupByCtx) }
+}
+
+/// Test whether the `group_by_ctx` of the query described by `desc` stores the closures.
+pub(crate) fn owns_group_by_ctx(desc: &ecs_query_desc_t) -> bool {
+    desc.group_by_ctx_free.is_some_and(|ctx_free| {
+        ctx_free as usize == group_by_ctx_free as unsafe extern "C" fn(*mut c_void) as usize
+    })
+}
+
+unsafe extern "C" fn group_by_ctx_free(ctx: *mut c_void) {
+    drop(Box::from_raw(ctx as *mut GroupByCtx));
+}
+
+/// Hand the closures of the query described by `desc` over to the query that was built from it.
+///
+/// The trampolines are removed from `desc`, so a query built from it again doesn't call the
+/// closures of the first query.
+pub(crate) fn release_group_by_ctx(desc: &mut ecs_query_desc_t) {
+    if !owns_group_by_ctx(desc) {
+        return;
+    }
+
+    if desc
+        .group_by
+        .is_some_and(|action| action as usize == group_by_trampoline as GroupByAction as usize)
+    {
+        desc.group_by = None;
+    }
+    if desc.on_group_create.is_some_and(|action| {
+        action as usize == group_create_trampoline as GroupCreateAction as usize
+    }) {
+        desc.on_group_create = None;
+    }
+    if desc.on_group_delete.is_some_and(|action| {
+        action as usize == group_delete_trampoline as GroupDeleteAction as usize
+    }) {
+        desc.on_group_delete = None;
+    }
+    desc.group_by_ctx = ptr::null_mut();
+    desc.group_by_ctx_free = None;
+}
+
+/// Free the closures of the query described by `desc` when no query was built from it.
+pub(crate) fn free_group_by_ctx(desc: &mut ecs_query_desc_t) {
+    if owns_group_by_ctx(desc) {
+        unsafe { group_by_ctx_free(desc.group_by_ctx) };
+        desc.group_by_ctx = ptr::null_mut();
+        desc.group_by_ctx_free = None;
+    }
+}
+
+type GroupByAction = unsafe extern "C" fn(*mut WorldT, *mut TableT, IdT, *mut c_void) -> u64;
+type GroupCreateAction = unsafe extern "C" fn(*mut WorldT, u64, *mut c_void) -> *mut c_void;
+type GroupDeleteAction = unsafe extern "C" fn(*mut WorldT, u64, *mut c_void, *mut c_void);
+
+pub(crate) unsafe extern "C" fn group_by_trampoline(
+    world: *mut WorldT,
+    table: *mut TableT,
+    group_id: IdT,
+    ctx: *mut c_void,
+) -> u64 {
+    let ctx = &*(ctx as *const GroupByCtx);
+    // the trampoline is only installed together with the closure
+    (ctx.group_by.as_ref().unwrap())(world, table, group_id)
+}
+
+pub(crate) unsafe extern "C" fn group_create_trampoline(
+    world: *mut WorldT,
+    group_id: u64,
+    ctx: *mut c_void,
+) -> *mut c_void {
+    let ctx = &*(ctx as *const GroupByCtx);
+    (ctx.on_create.as_ref().unwrap())(world, group_id)
+}
+
+pub(crate) unsafe extern "C" fn group_delete_trampoline(
+    world: *mut WorldT,
+    group_id: u64,
+    group_ctx: *mut c_void,
+    ctx: *mut c_void,
+) {
+    let ctx = &*(ctx as *const GroupByCtx);
+    (ctx.on_delete.as_ref().unwrap())(world, group_id, group_ctx);
+}
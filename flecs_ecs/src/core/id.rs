@@ -5,6 +5,7 @@ use super::{
     ecs_pair_first,
     entity::Entity,
     world::World,
+    world_ref::WorldRef,
     IntoEntityId, IntoEntityIdExt, IntoWorld,
 };
 #[cfg(any(debug_assertions, feature = "flecs_force_enable_ecs_asserts"))]
@@ -468,11 +469,11 @@ impl Id {
         unsafe { std::str::from_utf8_unchecked(std::ffi::CStr::from_ptr(c_str_ptr).to_bytes()) }
     }
 
-    pub fn world(self) -> World {
-        World {
-            raw_world: self.world,
-            is_owned: false,
-        }
+    /// Get a handle to the world of the id.
+    ///
+    /// The handle keeps the world alive, see [`World::new_wrap_raw_world`].
+    pub fn world(self) -> WorldRef {
+        World::new_wrap_raw_world(self.world)
     }
 
     pub(crate) fn world_ptr(self) -> *mut WorldT {
@@ -12,6 +12,8 @@ use crate::{
 };
 use flecs_ecs_sys::{ecs_field_id, ecs_iter_get_var};
 
+#[cfg(feature = "flecs_rules")]
+use super::IntoCStr;
 use super::{
     c_types::{IdT, IterT},
     column::{Column, UntypedColumn},
@@ -20,6 +22,7 @@ use super::{
     id::Id,
     table::{Table, TableRange},
     world::World,
+    world_ref::WorldRef,
     Archetype, FTime,
 };
 
@@ -84,13 +87,15 @@ impl<'a> Iter<'a> {
         Id::new_from_existing(self.iter.world, self.iter.event_id)
     }
 
-    /// wrap the world in the iterator in a `World` object
+    /// Get a handle to the world (or stage) of the iterator.
+    ///
+    /// The handle keeps the world alive, see [`World::new_wrap_raw_world`].
     ///
     /// # See also
     ///
     /// * C++ API: `iter::world`
     #[doc(alias = "iter::world")]
-    pub fn world(&self) -> World {
+    pub fn world(&self) -> WorldRef {
         World::new_wrap_raw_world(self.iter.world)
     }
 
@@ -163,10 +168,7 @@ impl<'a> Iter<'a> {
     /// * C++ API: `iter::table`
     #[doc(alias = "iter::table")]
     pub fn table(&self) -> Table {
-        Table::new(
-            &World::new_wrap_raw_world(self.iter.real_world),
-            self.iter.table,
-        )
+        Table::new(World::new_unowned(self.iter.real_world), self.iter.table)
     }
 
     /// # See also
@@ -209,20 +211,22 @@ impl<'a> Iter<'a> {
     /// * C++ API: `iter::get_var`
     #[doc(alias = "iter::get_var")]
     #[cfg(feature = "flecs_rules")]
-    pub fn get_var_by_name(&mut self, name: &CStr) -> Entity {
+    pub fn get_var_by_name(&mut self, name: impl IntoCStr) -> Entity {
         use flecs_ecs_sys::ecs_rule_find_var;
 
         let world = self.iter.world;
         let iter: &mut IterT = self.iter;
         let rit = unsafe { &mut iter.priv_.iter.rule };
         let rule = rit.rule;
-        let var_id = unsafe { ecs_rule_find_var(rule, name.as_ptr()) };
-        ecs_assert!(
-            var_id != -1,
-            FlecsErrorCode::InvalidParameter,
-            name.to_str().unwrap()
-        );
-        Entity::new_from_existing_raw(world, unsafe { ecs_iter_get_var(iter, var_id) })
+        name.with_cstr(|name| {
+            let var_id = unsafe { ecs_rule_find_var(rule, name.as_ptr()) };
+            ecs_assert!(
+                var_id != -1,
+                FlecsErrorCode::InvalidParameter,
+                name.to_str().unwrap()
+            );
+            Entity::new_from_existing_raw(world, unsafe { ecs_iter_get_var(iter, var_id) })
+        })
     }
 
     /// Access ctx.
@@ -628,6 +632,63 @@ impl<'a> Iter<'a> {
     }
 }
 
+/// Json mixin implementation
+#[cfg(feature = "flecs_json")]
+impl<'a> Iter<'a> {
+    /// Serialize the current result of the iterator, a single table, to JSON.
+    ///
+    /// The live iterator is passed to the serializer, which is limited to the current result so the
+    /// iterator is not advanced. With `dont_serialize_results` set, the list of results is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc` - The options that determine what is serialized.
+    ///
+    /// # Returns
+    ///
+    /// The JSON string.
+    ///
+    /// # Panics
+    ///
+    /// If a value can't be serialized, as flecs finishes the iterator on failure.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `iter::to_json`
+    #[doc(alias = "iter::to_json")]
+    pub fn to_json(&mut self, desc: &crate::addons::json::IterToJsonDesc) -> String {
+        use std::cell::Cell;
+
+        thread_local! {
+            static RESULT_YIELDED: Cell<bool> = const { Cell::new(false) };
+        }
+
+        unsafe extern "C" fn yield_current_result(_iter: *mut IterT) -> bool {
+            !RESULT_YIELDED.with(|yielded| yielded.replace(true))
+        }
+
+        // the serializer iterates the results itself, and finishes the iterator when asked not to
+        // serialize them
+        let mut desc = desc.desc;
+        RESULT_YIELDED.with(|yielded| yielded.set(desc.dont_serialize_results));
+        desc.dont_serialize_results = false;
+
+        // the serializer sets these flags, which would change how the iterator is advanced
+        let serializer_flags = crate::sys::EcsIterIsInstanced | crate::sys::EcsIterNoData;
+        let flags = self.iter.flags & serializer_flags;
+        let next = self.iter.next.replace(yield_current_result);
+        let json =
+            unsafe { crate::sys::ecs_iter_to_json(self.iter.real_world, &mut *self.iter, &desc) };
+        self.iter.next = next;
+        self.iter.flags = (self.iter.flags & !serializer_flags) | flags;
+
+        match unsafe { crate::addons::json::json_from_raw(json) } {
+            Ok(json) => json,
+            Err(err) => panic!("{}, the iterator was finished", err),
+        }
+    }
+}
+
 pub struct IterIterator<'a> {
     iter: &'a Iter<'a>,
     index: usize,
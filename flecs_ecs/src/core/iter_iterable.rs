@@ -1,4 +1,4 @@
-use std::ffi::{c_void, CStr};
+use std::ffi::c_void;
 
 use flecs_ecs_sys::{
     ecs_get_entity, ecs_iter_set_var, ecs_iter_set_var_as_range, ecs_query_set_group,
@@ -6,8 +6,8 @@ use flecs_ecs_sys::{
 };
 
 use super::{
-    ComponentId, Entity, FilterT, IntoEntityId, IntoTableRange, IntoWorld, IterAPI, IterOperations,
-    IterT, Iterable, WorldT,
+    ComponentId, Entity, FilterT, IntoCStr, IntoEntityId, IntoTableRange, IntoWorld, IterAPI,
+    IterOperations, IterT, Iterable, WorldT,
 };
 #[cfg(any(debug_assertions, feature = "flecs_force_enable_ecs_asserts"))]
 use crate::core::FlecsErrorCode;
@@ -111,14 +111,17 @@ where
     /// * C++ API: `iter_iterable::set_var`
     #[doc(alias = "iter_iterable::set_var_rule")]
     #[cfg(feature = "flecs_rules")]
-    pub fn set_var_rule(&mut self, name: &CStr, value: impl IntoEntityId) -> &mut Self {
+    pub fn set_var_rule(&mut self, name: impl IntoCStr, value: impl IntoEntityId) -> &mut Self {
         let rit: *mut ecs_rule_iter_t = unsafe { &mut self.iter.priv_.iter.rule };
-        let var_id = unsafe { ecs_rule_find_var((*rit).rule, name.as_ptr()) };
-        ecs_assert!(
-            var_id != -1,
-            FlecsErrorCode::InvalidParameter,
-            name.to_str().unwrap()
-        );
+        let var_id = name.with_cstr(|name| {
+            let var_id = unsafe { ecs_rule_find_var((*rit).rule, name.as_ptr()) };
+            ecs_assert!(
+                var_id != -1,
+                FlecsErrorCode::InvalidParameter,
+                name.to_str().unwrap()
+            );
+            var_id
+        });
         unsafe { ecs_iter_set_var(&mut self.iter, var_id, value.get_id()) };
         self
     }
@@ -3,7 +3,7 @@ use crate::sys::{self, ecs_filter_desc_t, ecs_inout_kind_t, ecs_oper_kind_t};
 use super::{
     c_types::{IterT, OperKind, TermT},
     component_registration::ComponentId,
-    ecs_field, FilterBuilderImpl, InOutKind, WorldT,
+    ecs_field, ComponentPair, FilterBuilderImpl, IdT, InOutKind, WorldT,
 };
 
 pub trait Filterable: Sized + FilterBuilderImpl {
@@ -29,6 +29,17 @@ pub trait IterableTypeOperation {
     type ActualType;
     type SliceType;
     type OnlyType: ComponentId;
+    /// `ActualType` with its reference bound to the lifetime `'w`.
+    type ActualTypeIn<'w>
+    where
+        Self: 'w;
+    /// `SliceType` with its reference bound to the lifetime `'w`.
+    type SliceTypeIn<'w>
+    where
+        Self: 'w;
+
+    /// Get the id of the term, the component or pair matched by the type.
+    fn get_id(world: *mut WorldT) -> IdT;
 
     fn populate_term(term: &mut sys::ecs_term_t);
     fn create_tuple_data(array_components_data: *mut u8, index: usize) -> Self::ActualType;
@@ -43,6 +54,24 @@ pub trait IterableTypeOperation {
         is_ref_array_components: bool,
         count: usize,
     ) -> Self::SliceType;
+
+    /// Bind the reference of the component data to the lifetime `'w`.
+    ///
+    /// # Safety
+    ///
+    /// The component data must stay valid, and must not be borrowed elsewhere, for `'w`.
+    unsafe fn rebind<'w>(value: Self::ActualType) -> Self::ActualTypeIn<'w>
+    where
+        Self: 'w;
+
+    /// Bind the reference of the component slice to the lifetime `'w`.
+    ///
+    /// # Safety
+    ///
+    /// The component data must stay valid, and must not be borrowed elsewhere, for `'w`.
+    unsafe fn rebind_slice<'w>(slice: Self::SliceType) -> Self::SliceTypeIn<'w>
+    where
+        Self: 'w;
 }
 
 impl<'a, T> IterableTypeOperation for &'a T
@@ -53,6 +82,18 @@ where
     type ActualType = &'a T;
     type SliceType = &'a [T];
     type OnlyType = T;
+    type ActualTypeIn<'w>
+        = &'w T
+    where
+        Self: 'w;
+    type SliceTypeIn<'w>
+        = &'w [T]
+    where
+        Self: 'w;
+
+    fn get_id(world: *mut WorldT) -> IdT {
+        T::get_id(world)
+    }
 
     fn populate_term(term: &mut sys::ecs_term_t) {
         term.inout = InOutKind::In as ecs_inout_kind_t;
@@ -97,6 +138,20 @@ where
             }
         }
     }
+
+    unsafe fn rebind<'w>(value: Self::ActualType) -> Self::ActualTypeIn<'w>
+    where
+        Self: 'w,
+    {
+        &*(value as *const T)
+    }
+
+    unsafe fn rebind_slice<'w>(slice: Self::SliceType) -> Self::SliceTypeIn<'w>
+    where
+        Self: 'w,
+    {
+        &*(slice as *const [T])
+    }
 }
 
 impl<'a, T> IterableTypeOperation for &'a mut T
@@ -107,6 +162,18 @@ where
     type ActualType = &'a mut T;
     type SliceType = &'a mut [T];
     type OnlyType = T;
+    type ActualTypeIn<'w>
+        = &'w mut T
+    where
+        Self: 'w;
+    type SliceTypeIn<'w>
+        = &'w mut [T]
+    where
+        Self: 'w;
+
+    fn get_id(world: *mut WorldT) -> IdT {
+        T::get_id(world)
+    }
 
     fn populate_term(term: &mut sys::ecs_term_t) {
         term.inout = InOutKind::InOut as ecs_inout_kind_t;
@@ -151,6 +218,20 @@ where
             }
         }
     }
+
+    unsafe fn rebind<'w>(value: Self::ActualType) -> Self::ActualTypeIn<'w>
+    where
+        Self: 'w,
+    {
+        &mut *(value as *mut T)
+    }
+
+    unsafe fn rebind_slice<'w>(slice: Self::SliceType) -> Self::SliceTypeIn<'w>
+    where
+        Self: 'w,
+    {
+        &mut *(slice as *mut [T])
+    }
 }
 
 impl<'a, T> IterableTypeOperation for Option<&'a T>
@@ -161,6 +242,18 @@ where
     type ActualType = Option<&'a T>;
     type SliceType = Option<&'a [T]>;
     type OnlyType = T;
+    type ActualTypeIn<'w>
+        = Option<&'w T>
+    where
+        Self: 'w;
+    type SliceTypeIn<'w>
+        = Option<&'w [T]>
+    where
+        Self: 'w;
+
+    fn get_id(world: *mut WorldT) -> IdT {
+        T::get_id(world)
+    }
 
     fn populate_term(term: &mut sys::ecs_term_t) {
         term.inout = InOutKind::In as ecs_inout_kind_t;
@@ -214,6 +307,20 @@ where
             Some(unsafe { std::slice::from_raw_parts(data_ptr, count) })
         }
     }
+
+    unsafe fn rebind<'w>(value: Self::ActualType) -> Self::ActualTypeIn<'w>
+    where
+        Self: 'w,
+    {
+        value.map(|value| &*(value as *const T))
+    }
+
+    unsafe fn rebind_slice<'w>(slice: Self::SliceType) -> Self::SliceTypeIn<'w>
+    where
+        Self: 'w,
+    {
+        slice.map(|slice| &*(slice as *const [T]))
+    }
 }
 
 impl<'a, T> IterableTypeOperation for Option<&'a mut T>
@@ -224,6 +331,18 @@ where
     type ActualType = Option<&'a mut T>;
     type SliceType = Option<&'a mut [T]>;
     type OnlyType = T;
+    type ActualTypeIn<'w>
+        = Option<&'w mut T>
+    where
+        Self: 'w;
+    type SliceTypeIn<'w>
+        = Option<&'w mut [T]>
+    where
+        Self: 'w;
+
+    fn get_id(world: *mut WorldT) -> IdT {
+        T::get_id(world)
+    }
 
     fn populate_term(term: &mut sys::ecs_term_t) {
         term.inout = InOutKind::InOut as ecs_inout_kind_t;
@@ -277,13 +396,135 @@ where
             Some(unsafe { std::slice::from_raw_parts_mut(data_ptr, count) })
         }
     }
+
+    unsafe fn rebind<'w>(value: Self::ActualType) -> Self::ActualTypeIn<'w>
+    where
+        Self: 'w,
+    {
+        value.map(|value| &mut *(value as *mut T))
+    }
+
+    unsafe fn rebind_slice<'w>(slice: Self::SliceType) -> Self::SliceTypeIn<'w>
+    where
+        Self: 'w,
+    {
+        slice.map(|slice| &mut *(slice as *mut [T]))
+    }
+}
+
+/// Implements `IterableTypeOperation` for a pair, by delegating to the implementation for the data
+/// type of the pair.
+macro_rules! impl_iterable_type_operation_pair {
+    ($a:lifetime, $pair:ty => $data:ty) => {
+        impl<$a, T, U> IterableTypeOperation for $pair
+        where
+            (T, U): ComponentPair,
+            <(T, U) as ComponentPair>::CastType: $a,
+        {
+            type CastType = <$data as IterableTypeOperation>::CastType;
+            type ActualType = <$data as IterableTypeOperation>::ActualType;
+            type SliceType = <$data as IterableTypeOperation>::SliceType;
+            type OnlyType = <(T, U) as ComponentPair>::CastType;
+            type ActualTypeIn<'w>
+                = <$data as IterableTypeOperation>::ActualTypeIn<'w>
+            where
+                Self: 'w;
+            type SliceTypeIn<'w>
+                = <$data as IterableTypeOperation>::SliceTypeIn<'w>
+            where
+                Self: 'w;
+
+            fn get_id(world: *mut WorldT) -> IdT {
+                <(T, U) as ComponentPair>::get_id(world)
+            }
+
+            fn populate_term(term: &mut sys::ecs_term_t) {
+                <$data>::populate_term(term);
+            }
+
+            fn create_tuple_data(array_components_data: *mut u8, index: usize) -> Self::ActualType {
+                <$data>::create_tuple_data(array_components_data, index)
+            }
+
+            fn create_tuple_with_ref_data(
+                array_components_data: *mut u8,
+                is_ref: bool,
+                index: usize,
+            ) -> Self::ActualType {
+                <$data>::create_tuple_with_ref_data(array_components_data, is_ref, index)
+            }
+
+            fn create_tuple_slice_data(
+                array_components_data: *mut u8,
+                count: usize,
+            ) -> Self::SliceType {
+                <$data>::create_tuple_slice_data(array_components_data, count)
+            }
+
+            fn create_tuple_slices_with_ref_data(
+                array_components_data: *mut u8,
+                is_ref_array_components: bool,
+                count: usize,
+            ) -> Self::SliceType {
+                <$data>::create_tuple_slices_with_ref_data(
+                    array_components_data,
+                    is_ref_array_components,
+                    count,
+                )
+            }
+
+            unsafe fn rebind<'w>(value: Self::ActualType) -> Self::ActualTypeIn<'w>
+            where
+                Self: 'w,
+            {
+                <$data>::rebind(value)
+            }
+
+            unsafe fn rebind_slice<'w>(slice: Self::SliceType) -> Self::SliceTypeIn<'w>
+            where
+                Self: 'w,
+            {
+                <$data>::rebind_slice(slice)
+            }
+        }
+    };
+}
+
+impl_iterable_type_operation_pair!('a, &'a (T, U) => &'a <(T, U) as ComponentPair>::CastType);
+impl_iterable_type_operation_pair!('a, &'a mut (T, U) => &'a mut <(T, U) as ComponentPair>::CastType);
+impl_iterable_type_operation_pair!('a, Option<&'a (T, U)> => Option<&'a <(T, U) as ComponentPair>::CastType>);
+impl_iterable_type_operation_pair!('a, Option<&'a mut (T, U)> => Option<&'a mut <(T, U) as ComponentPair>::CastType>);
+
+/// Get the pointer to the data of a field.
+///
+/// Tags have no data, so flecs returns a null pointer for them. A dangling pointer is returned
+/// instead when the field is set, so references to zero sized types can be created from it.
+unsafe fn field_ptr<T: ComponentId>(it: &IterT, index: i32) -> *mut u8 {
+    let ptr = ecs_field::<T>(it, index) as *mut u8;
+    if ptr.is_null() && std::mem::size_of::<T>() == 0 && sys::ecs_field_is_set(it, index) {
+        std::ptr::NonNull::<T>::dangling().as_ptr() as *mut u8
+    } else {
+        ptr
+    }
 }
 
 pub trait Iterable<'a>: Sized {
     type TupleType: 'a;
-    type ComponentsArray: 'a + std::ops::Index<usize, Output = *mut u8> + std::ops::IndexMut<usize>;
-    type BoolArray: 'a + std::ops::Index<usize, Output = bool> + std::ops::IndexMut<usize>;
+    type ComponentsArray: 'a
+        + std::ops::Index<usize, Output = *mut u8>
+        + std::ops::IndexMut<usize>
+        + AsMut<[*mut u8]>
+        + Clone;
+    type BoolArray: 'a + std::ops::Index<usize, Output = bool> + std::ops::IndexMut<usize> + Clone;
     type TupleSliceType: 'a;
+    /// `TupleType` with its references bound to the lifetime `'w`.
+    type TupleTypeIn<'w>
+    where
+        Self: 'w;
+    /// `TupleSliceType` with its references bound to the lifetime `'w`.
+    type TupleSliceTypeIn<'w>
+    where
+        Self: 'w;
 
     fn populate(filter: &mut impl Filterable);
     fn register_ids_descriptor(world: *mut WorldT, desc: &mut ecs_filter_desc_t);
@@ -307,6 +548,24 @@ pub trait Iterable<'a>: Sized {
         is_ref_array_components: &Self::BoolArray,
         count: usize,
     ) -> Self::TupleSliceType;
+
+    /// Bind the references of the components to the lifetime `'w`.
+    ///
+    /// # Safety
+    ///
+    /// The component data must stay valid, and must not be borrowed elsewhere, for `'w`.
+    unsafe fn rebind_tuple<'w>(tuple: Self::TupleType) -> Self::TupleTypeIn<'w>
+    where
+        Self: 'w;
+
+    /// Bind the references of the component slices to the lifetime `'w`.
+    ///
+    /// # Safety
+    ///
+    /// The component data must stay valid, and must not be borrowed elsewhere, for `'w`.
+    unsafe fn rebind_tuple_slices<'w>(slices: Self::TupleSliceType) -> Self::TupleSliceTypeIn<'w>
+    where
+        Self: 'w;
 }
 
 /////////////////////
@@ -321,6 +580,8 @@ impl<'a> Iterable<'a> for ()
     type ComponentsArray = [*mut u8; 0];
     type BoolArray = [bool; 0];
     type TupleSliceType = ();
+    type TupleTypeIn<'w> = () where Self: 'w;
+    type TupleSliceTypeIn<'w> = () where Self: 'w;
 
     fn populate(_filter : &mut impl Filterable){}
 
@@ -353,6 +614,9 @@ impl<'a> Iterable<'a> for ()
         _count: usize,
     ) -> Self::TupleSliceType {}
 
+    unsafe fn rebind_tuple<'w>(_tuple: Self::TupleType) -> Self::TupleTypeIn<'w> where Self: 'w {}
+
+    unsafe fn rebind_tuple_slices<'w>(_slices: Self::TupleSliceType) -> Self::TupleSliceTypeIn<'w> where Self: 'w {}
 }
 
 #[rustfmt::skip]
@@ -364,11 +628,13 @@ where
     type ComponentsArray = [*mut u8; 1];
     type BoolArray = [bool; 1];
     type TupleSliceType = (A::SliceType,);
+    type TupleTypeIn<'w> = (A::ActualTypeIn<'w>,) where Self: 'w;
+    type TupleSliceTypeIn<'w> = (A::SliceTypeIn<'w>,) where Self: 'w;
 
     fn populate(filter: &mut impl Filterable) {
 
         let world = filter.world_ptr_mut();
-        filter.term_with_id(A::OnlyType::get_id(world));
+        filter.term_with_id(A::get_id(world));
         let term = filter.current_term();
         A::populate_term(term);
 
@@ -376,13 +642,13 @@ where
 
     fn register_ids_descriptor(world: *mut WorldT, desc: &mut ecs_filter_desc_t) {
         let term = &mut desc.terms[0];
-        term.id = A::OnlyType::get_id(world);
+        term.id = A::get_id(world);
         A::populate_term(term);
     }
 
     fn create_array_ptrs_of_components(it: &IterT) -> ComponentsData<'a, Self> {
         let array_components = unsafe {
-            [ecs_field::<A::OnlyType>(it, 1) as *mut u8]
+            [field_ptr::<A::OnlyType>(it, 1)]
         };
         let is_ref_array_components = if !it.sources.is_null() { unsafe {
             [*it.sources.add(0) != 0]
@@ -426,6 +692,16 @@ where
         (A::create_tuple_slices_with_ref_data(array_components[0], is_ref_array_components[0], count),)
 
     }
+
+    unsafe fn rebind_tuple<'w>(tuple: Self::TupleType) -> Self::TupleTypeIn<'w> where Self: 'w {
+        let (a,) = tuple;
+        (A::rebind(a),)
+    }
+
+    unsafe fn rebind_tuple_slices<'w>(slices: Self::TupleSliceType) -> Self::TupleSliceTypeIn<'w> where Self: 'w {
+        let (a,) = slices;
+        (A::rebind_slice(a),)
+    }
 }
 
 #[rustfmt::skip]
@@ -438,15 +714,17 @@ where
     type ComponentsArray = [*mut u8; 2];
     type BoolArray = [bool; 2];
     type TupleSliceType = (A::SliceType, B::SliceType);
+    type TupleTypeIn<'w> = (A::ActualTypeIn<'w>, B::ActualTypeIn<'w>) where Self: 'w;
+    type TupleSliceTypeIn<'w> = (A::SliceTypeIn<'w>, B::SliceTypeIn<'w>) where Self: 'w;
 
     fn populate(filter : &mut impl Filterable)
     {
         let world = filter.world_ptr_mut();
-         filter.term_with_id(A::OnlyType::get_id(world));
+         filter.term_with_id(A::get_id(world));
         let term = filter.current_term();
         A::populate_term(term);
 
-        filter.term_with_id(B::OnlyType::get_id(world));
+        filter.term_with_id(B::get_id(world));
         let term = filter.current_term();
         B::populate_term(term);
 
@@ -455,17 +733,17 @@ where
     fn register_ids_descriptor(world: *mut WorldT,desc: &mut ecs_filter_desc_t)
     {
         let term = &mut desc.terms[0];
-        term.id = A::OnlyType::get_id(world);
+        term.id = A::get_id(world);
         A::populate_term(term);
         let term = &mut desc.terms[1];
-        term.id = B::OnlyType::get_id(world);
+        term.id = B::get_id(world);
         B::populate_term(term);
     }
 
     fn create_array_ptrs_of_components(it: &IterT) -> ComponentsData<'a, Self> {
         let array_components = unsafe {
-            [ecs_field::<A::OnlyType>(it, 1) as *mut u8,
-            ecs_field::<B::OnlyType>(it, 2) as *mut u8]
+            [field_ptr::<A::OnlyType>(it, 1),
+            field_ptr::<B::OnlyType>(it, 2)]
         };
 
         let is_ref_array_components = if !it.sources.is_null() { unsafe {
@@ -509,6 +787,16 @@ where
     ) -> Self::TupleSliceType {
         (A::create_tuple_slices_with_ref_data(array_components[0], is_ref_array_components[0], count),B::create_tuple_slices_with_ref_data(array_components[1], is_ref_array_components[1], count),)
     }
+
+    unsafe fn rebind_tuple<'w>(tuple: Self::TupleType) -> Self::TupleTypeIn<'w> where Self: 'w {
+        let (a, b) = tuple;
+        (A::rebind(a), B::rebind(b))
+    }
+
+    unsafe fn rebind_tuple_slices<'w>(slices: Self::TupleSliceType) -> Self::TupleSliceTypeIn<'w> where Self: 'w {
+        let (a, b) = slices;
+        (A::rebind_slice(a), B::rebind_slice(b))
+    }
 }
 
 #[rustfmt::skip]
@@ -522,19 +810,21 @@ where
     type ComponentsArray = [*mut u8; 3];
     type BoolArray = [bool; 3];
     type TupleSliceType = (A::SliceType, B::SliceType, C::SliceType);
+    type TupleTypeIn<'w> = (A::ActualTypeIn<'w>, B::ActualTypeIn<'w>, C::ActualTypeIn<'w>) where Self: 'w;
+    type TupleSliceTypeIn<'w> = (A::SliceTypeIn<'w>, B::SliceTypeIn<'w>, C::SliceTypeIn<'w>) where Self: 'w;
 
     fn populate(filter : &mut impl Filterable)
     {
         let world = filter.world_ptr_mut();
-        filter.term_with_id(A::OnlyType::get_id(world));
+        filter.term_with_id(A::get_id(world));
         let term = filter.current_term();
         A::populate_term(term);
 
-        unsafe { filter.term_with_id(B::OnlyType::get_id_unchecked()) } ;
+        filter.term_with_id(B::get_id(world));
         let term = filter.current_term();
         B::populate_term(term);
 
-        unsafe { filter.term_with_id(C::OnlyType::get_id_unchecked()) } ;
+        filter.term_with_id(C::get_id(world));
         let term = filter.current_term();
         C::populate_term(term);
 
@@ -543,21 +833,21 @@ where
     fn register_ids_descriptor(world: *mut WorldT,desc: &mut ecs_filter_desc_t)
     {
         let term = &mut desc.terms[0];
-        term.id = A::OnlyType::get_id(world);
+        term.id = A::get_id(world);
         A::populate_term(term);
         let term = &mut desc.terms[1];
-        term.id = B::OnlyType::get_id(world);
+        term.id = B::get_id(world);
         B::populate_term(term);
         let term = &mut desc.terms[2];
-        term.id = C::OnlyType::get_id(world);
+        term.id = C::get_id(world);
         C::populate_term(term);
     }
 
     fn create_array_ptrs_of_components(it: &IterT) -> ComponentsData<'a, Self>{
        let array_components = unsafe {
-            [ecs_field::<A::OnlyType>(it, 1) as *mut u8,
-            ecs_field::<B::OnlyType>(it, 2) as *mut u8,
-            ecs_field::<C::OnlyType>(it, 3) as *mut u8]
+            [field_ptr::<A::OnlyType>(it, 1),
+            field_ptr::<B::OnlyType>(it, 2),
+            field_ptr::<C::OnlyType>(it, 3)]
         };
 
         let is_ref_array_components = if !it.sources.is_null() { unsafe {
@@ -602,6 +892,16 @@ where
     ) -> Self::TupleSliceType {
         (A::create_tuple_slices_with_ref_data(array_components[0], is_ref_array_components[0], count),B::create_tuple_slices_with_ref_data(array_components[1], is_ref_array_components[1], count),C::create_tuple_slices_with_ref_data(array_components[2], is_ref_array_components[2], count),)
     }
+
+    unsafe fn rebind_tuple<'w>(tuple: Self::TupleType) -> Self::TupleTypeIn<'w> where Self: 'w {
+        let (a, b, c) = tuple;
+        (A::rebind(a), B::rebind(b), C::rebind(c))
+    }
+
+    unsafe fn rebind_tuple_slices<'w>(slices: Self::TupleSliceType) -> Self::TupleSliceTypeIn<'w> where Self: 'w {
+        let (a, b, c) = slices;
+        (A::rebind_slice(a), B::rebind_slice(b), C::rebind_slice(c))
+    }
 }
 
 pub struct Wrapper<T>(T);
@@ -752,6 +1052,13 @@ macro_rules! impl_iterable {
             type TupleSliceType = ($(
                 $t::SliceType
             ),*);
+            type TupleTypeIn<'w> = ($(
+                $t::ActualTypeIn<'w>
+            ),*) where Self: 'w;
+
+            type TupleSliceTypeIn<'w> = ($(
+                $t::SliceTypeIn<'w>
+            ),*) where Self: 'w;
             type ComponentsArray = [*mut u8; tuple_count!($($t),*)];
             type BoolArray = [bool; tuple_count!($($t),*)];
 
@@ -759,7 +1066,7 @@ macro_rules! impl_iterable {
             fn populate(filter: &mut impl Filterable) {
                 let world = filter.world_ptr_mut();
                 $(
-                    filter.term_with_id($t::OnlyType::get_id(world));
+                    filter.term_with_id($t::get_id(world));
                     let term = filter.current_term();
                     $t::populate_term(term);
 
@@ -771,7 +1078,7 @@ macro_rules! impl_iterable {
                 let mut term_index = 0;
                 $(
                     let term = &mut desc.terms[term_index];
-                    term.id = $t::OnlyType::get_id(world);
+                    term.id = $t::get_id(world);
                     $t::populate_term(term);
                     term_index += 1;
                 )*
@@ -786,7 +1093,7 @@ macro_rules! impl_iterable {
                 unsafe {
                     let array_components = [ $(
                         {
-                            let ptr = ecs_field::<$t::OnlyType>(it, index) as *mut u8;
+                            let ptr = field_ptr::<$t::OnlyType>(it, index);
                             index += 1;
                             ptr
                         },
@@ -882,6 +1189,18 @@ macro_rules! impl_iterable {
                         )*
                     )
             }
+
+            #[allow(non_snake_case)]
+            unsafe fn rebind_tuple<'w>(tuple: Self::TupleType) -> Self::TupleTypeIn<'w> where Self: 'w {
+                let ($($t,)*) = tuple;
+                ($($t::rebind($t),)*)
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn rebind_tuple_slices<'w>(slices: Self::TupleSliceType) -> Self::TupleSliceTypeIn<'w> where Self: 'w {
+                let ($($t,)*) = slices;
+                ($($t::rebind_slice($t),)*)
+            }
         }
     }
 }
@@ -54,9 +54,9 @@ pub fn register_lifecycle_actions<T: Default>(type_hooks: &mut TypeHooksT) {
     type_hooks.ctor = Some(generic_ctor::<T>);
     type_hooks.dtor = Some(generic_dtor::<T>);
     type_hooks.move_ = Some(generic_move::<T>);
-    type_hooks.move_ctor = Some(generic_move::<T>); //same implementation as move
+    type_hooks.move_ctor = Some(generic_move_ctor::<T>);
     type_hooks.ctor_move_dtor = Some(generic_ctor_move_dtor::<T>);
-    type_hooks.move_dtor = Some(generic_ctor_move_dtor::<T>); //same implementation as ctor_move_dtor
+    type_hooks.move_dtor = Some(generic_move_dtor::<T>);
 }
 
 pub fn register_copy_lifecycle_action<T: Clone>(type_hooks: &mut TypeHooksT) {
@@ -202,14 +202,14 @@ extern "C" fn generic_move<T: Default>(
     }
 }
 
-// TODO: improve this so we can avoid the heap allocation
-/// when the struct is non trivial, this will move the value and replace it with a default (heap allocation) and then drop it (deallocating the heap allocation)
+/// This is the generic move constructor for non-trivial types
+/// It will move the memory into uninitialized memory, leaving the source in a default state
 ///
 /// # See also
 ///
 /// * C++ API: `move_ctor_impl`
 #[doc(alias = "move_ctor_impl")]
-extern "C" fn generic_ctor_move_dtor<T: Default>(
+extern "C" fn generic_move_ctor<T: Default>(
     dst_ptr: *mut c_void,
     src_ptr: *mut c_void,
     count: i32,
@@ -222,24 +222,65 @@ extern "C" fn generic_ctor_move_dtor<T: Default>(
     let dst_arr = dst_ptr as *mut T;
     let src_arr = src_ptr as *mut T;
     for i in 0..count as isize {
-        //this is safe because C manages the memory and we are just moving the internal data around
+        // the destination is not constructed yet, so it must be written without dropping it.
+        // The source is destructed by flecs later, so it is left in a default state.
         unsafe {
             let moved_value = std::ptr::replace(src_arr.offset(i), T::default());
-            let dst_value = &mut *dst_arr.offset(i); // Obtain a mutable reference to the destination.
-                                                     // Write moved src to dst without dropping src since src is being moved to dst
-            *dst_value = moved_value; // Assign the moved value, which automatically drops the previous value.
+            std::ptr::write(dst_arr.offset(i), moved_value);
+        }
+    }
+}
 
-            ptr::drop_in_place(src_arr.offset(i));
-
-            //TODO evaluate if this could under here could potentially improve performance
-            //my suspicion is that it's dangerous to do this because it could lead to double free / premature free
-            {
-                //// Read out the source value, effectively moving it.
-                //let moved_value = std::ptr::read(src_arr.offset(i));
-                //
-                //// Write the moved value to the destination.
-                //std::ptr::write(dst_arr.offset(i), moved_value);
-            }
+/// This is the generic move for non-trivial types into uninitialized memory, destructing the source
+///
+/// # See also
+///
+/// * C++ API: `ctor_move_dtor_impl`
+#[doc(alias = "ctor_move_dtor_impl")]
+extern "C" fn generic_ctor_move_dtor<T>(
+    dst_ptr: *mut c_void,
+    src_ptr: *mut c_void,
+    count: i32,
+    _type_info: *const ecs_type_info_t,
+) {
+    ecs_assert!(
+        check_type_info::<T>(_type_info),
+        FlecsErrorCode::InternalError
+    );
+    let dst_arr = dst_ptr as *mut T;
+    let src_arr = src_ptr as *mut T;
+    for i in 0..count as isize {
+        // the destination is not constructed yet and the source is considered destructed after
+        // the move, so the value is moved bitwise without dropping either.
+        unsafe {
+            std::ptr::write(dst_arr.offset(i), std::ptr::read(src_arr.offset(i)));
+        }
+    }
+}
+
+/// This is the generic move for non-trivial types into constructed memory, destructing the source
+///
+/// # See also
+///
+/// * C++ API: `move_dtor_impl`
+#[doc(alias = "move_dtor_impl")]
+extern "C" fn generic_move_dtor<T>(
+    dst_ptr: *mut c_void,
+    src_ptr: *mut c_void,
+    count: i32,
+    _type_info: *const ecs_type_info_t,
+) {
+    ecs_assert!(
+        check_type_info::<T>(_type_info),
+        FlecsErrorCode::InternalError
+    );
+    let dst_arr = dst_ptr as *mut T;
+    let src_arr = src_ptr as *mut T;
+    for i in 0..count as isize {
+        // the previous destination value is dropped by the assignment, the source is considered
+        // destructed after the move.
+        unsafe {
+            *dst_arr.offset(i) = std::ptr::read(src_arr.offset(i));
         }
     }
 }
@@ -1,8 +1,11 @@
 pub mod archetype;
 pub mod builder;
+pub mod bundle;
 pub mod c_types;
 pub mod column;
+pub mod commands;
 pub mod component;
+pub mod component_guard;
 pub mod component_ref;
 pub mod component_registration;
 pub mod entity;
@@ -12,6 +15,7 @@ pub mod event_builder;
 pub mod filter;
 pub mod filter_builder;
 pub mod flecs;
+mod group_by;
 pub mod id;
 pub mod iter;
 pub mod iter_iterable;
@@ -19,19 +23,28 @@ pub mod iterable;
 pub mod lifecycle_traits;
 pub mod observer;
 pub mod observer_builder;
+mod order_by;
+#[cfg(feature = "flecs_rayon")]
+mod par_iter;
 pub mod query;
 pub mod query_builder;
+pub mod query_iter;
 pub mod scoped_world;
+pub mod stage;
 pub mod table;
 pub mod term;
 pub mod utility;
 pub mod world;
+pub mod world_ref;
 
 pub use archetype::*;
 pub use builder::*;
+pub use bundle::*;
 pub use c_types::*;
 pub use column::*;
+pub use commands::*;
 pub use component::*;
+pub use component_guard::*;
 pub use component_registration::*;
 pub use entity::*;
 pub use entity_view::*;
@@ -39,7 +52,7 @@ pub use event::*;
 pub use event_builder::*;
 pub use filter::*;
 pub use filter_builder::*;
-
+pub(crate) use group_by::*;
 pub use id::*;
 pub use iter::*;
 pub use iter_iterable::*;
@@ -47,10 +60,16 @@ pub use iterable::*;
 pub use lifecycle_traits::*;
 pub use observer::*;
 pub use observer_builder::*;
+pub(crate) use order_by::*;
+#[cfg(feature = "flecs_rayon")]
+pub use par_iter::PAR_CHUNK_SIZE;
 pub use query::*;
 pub use query_builder::*;
+pub use query_iter::*;
 pub use scoped_world::*;
+pub use stage::*;
 pub use table::*;
 pub use term::*;
 pub use utility::*;
 pub use world::*;
+pub use world_ref::*;
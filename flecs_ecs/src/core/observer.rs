@@ -9,12 +9,13 @@ use super::{
     entity::Entity,
     filter::Filter,
     world::World,
+    world_ref::WorldRef,
 };
 
 #[derive(Clone)]
 pub struct Observer {
     pub entity: Entity,
-    world: World,
+    world: WorldRef,
 }
 
 impl Deref for Observer {
@@ -52,14 +53,14 @@ impl Observer {
 
         Self {
             entity,
-            world: world.clone(),
+            world: world.handle(),
         }
     }
 
     /// Wrap an existing observer entity in an observer object
     pub fn new_from_existing(world: &World, observer_entity: Entity) -> Self {
         Self {
-            world: world.clone(),
+            world: world.handle(),
             entity: observer_entity,
         }
     }
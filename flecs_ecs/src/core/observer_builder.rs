@@ -1,8 +1,4 @@
-use std::{
-    default,
-    ffi::{c_void, CStr},
-    ops::Deref,
-};
+use std::{default, ffi::c_void, ops::Deref};
 
 use crate::sys::{
     ecs_entity_desc_t, ecs_entity_init, ecs_filter_desc_t, ecs_iter_action_t, ecs_observer_desc_t,
@@ -18,7 +14,7 @@ use super::{
     private::internal_ReactorAPI,
     term::TermBuilder,
     world::World,
-    Builder, IntoEntityId, ReactorAPI, Term, WorldT,
+    Builder, IntoCStr, IntoEntityId, ReactorAPI, Term, WorldT,
 };
 
 pub struct ObserverBuilder<'a, T>
@@ -90,7 +86,7 @@ where
     ///
     /// * C++ API: `node_builder::node_builder`
     #[doc(alias = "node_builder::node_builder")]
-    pub fn new_named(world: &World, name: &CStr) -> Self {
+    pub fn new_named(world: &World, name: impl IntoCStr) -> Self {
         let mut desc = Default::default();
         let mut obj = Self {
             desc,
@@ -98,14 +94,15 @@ where
             event_count: 0,
             is_instanced: false,
         };
-        let entity_desc: ecs_entity_desc_t = ecs_entity_desc_t {
-            name: name.as_ptr(),
-            sep: SEPARATOR.as_ptr(),
-            root_sep: SEPARATOR.as_ptr(),
-            ..default::Default::default()
-        };
-
-        obj.desc.entity = unsafe { ecs_entity_init(obj.world.raw_world, &entity_desc) };
+        obj.desc.entity = name.with_cstr(|name| {
+            let entity_desc = ecs_entity_desc_t {
+                name: name.as_ptr(),
+                sep: SEPARATOR.as_ptr(),
+                root_sep: SEPARATOR.as_ptr(),
+                ..default::Default::default()
+            };
+            unsafe { ecs_entity_init(obj.world.raw_world, &entity_desc) }
+        });
         T::populate(&mut obj);
         obj
     }
@@ -0,0 +1,77 @@
+//! Dispatch of Rust closures used to sort the results of queries.
+//!
+//! flecs calls the `order_by` comparator of a query without a context, so a closure can't be passed
+//! to it directly. Instead the closure is stored in the binding context of the query, and every
+//! query sorted by a closure uses the same comparator. flecs only sorts a query while the query is
+//! created or while an iterator is obtained for it, so the closure of the query is made available
+//! to the comparator in a thread local for the duration of those calls.
+
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    os::raw::{c_int, c_void},
+    ptr::NonNull,
+};
+
+use crate::{ecs_abort, sys::ecs_query_get_binding_ctx};
+
+use super::{c_types::EntityT, FlecsErrorCode, QueryBindingCtx, QueryT};
+
+/// Compares the values of two entities, passed as pointers to the sorted component.
+pub(crate) type OrderByClosure =
+    dyn Fn(EntityT, *const c_void, EntityT, *const c_void) -> Ordering + Send + Sync;
+
+thread_local! {
+    /// The closure of the query that is sorted on this thread.
+    static SORTED_BY: Cell<Option<NonNull<OrderByClosure>>> = const { Cell::new(None) };
+}
+
+/// The comparator of every query sorted by a closure.
+pub(crate) unsafe extern "C" fn order_by_trampoline(
+    e1: EntityT,
+    ptr1: *const c_void,
+    e2: EntityT,
+    ptr2: *const c_void,
+) -> c_int {
+    let Some(compare) = SORTED_BY.with(Cell::get) else {
+        ecs_abort!(
+            FlecsErrorCode::Unsupported,
+            "a query sorted by a closure can only be iterated through the Rust API"
+        );
+    };
+    // the closure is owned by the query that is being sorted
+    (compare.as_ref())(e1, ptr1, e2, ptr2) as c_int
+}
+
+/// Makes the closure of a query available to [`order_by_trampoline`] while it is alive.
+pub(crate) struct OrderByScope {
+    previous: Option<NonNull<OrderByClosure>>,
+}
+
+impl OrderByScope {
+    /// Sort with `compare` until the scope is dropped. Does nothing if `compare` is `None`.
+    pub(crate) fn new(compare: Option<&OrderByClosure>) -> Self {
+        let previous = SORTED_BY.with(Cell::get);
+        if let Some(compare) = compare {
+            SORTED_BY.with(|sorted_by| sorted_by.set(Some(NonNull::from(compare))));
+        }
+        Self { previous }
+    }
+
+    /// Sort with the closure of `query`, if it is sorted by a closure.
+    ///
+    /// # Safety
+    ///
+    /// The binding context of `query` must be null or a `QueryBindingCtx`, and the query must
+    /// outlive the scope.
+    pub(crate) unsafe fn for_query(query: *mut QueryT) -> Self {
+        let ctx = ecs_query_get_binding_ctx(query) as *const QueryBindingCtx;
+        Self::new(ctx.as_ref().and_then(|ctx| ctx.order_by.as_deref()))
+    }
+}
+
+impl Drop for OrderByScope {
+    fn drop(&mut self) {
+        SORTED_BY.with(|sorted_by| sorted_by.set(self.previous));
+    }
+}
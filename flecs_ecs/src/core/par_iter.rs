@@ -0,0 +1,212 @@
+//! Parallel iteration of query results on a rayon thread pool.
+//!
+//! The results of the flecs iterator are collected on the calling thread, which locks their tables
+//! and borrows their components. Tables are split into ranges of at most [`PAR_CHUNK_SIZE`]
+//! entities, which are then processed by the threads of a rayon thread pool.
+
+use std::collections::HashSet;
+
+use rayon::{prelude::*, ThreadPool};
+
+use crate::sys::{ecs_field_is_self, ecs_field_is_set, ecs_field_size};
+
+use super::{
+    c_types::{IterT, TableT},
+    iterable::Iterable,
+    Id, IdT, InOutKind, IterAPI, IterBorrows, IterGuard, TableLock,
+};
+
+/// The maximum number of entities of a table processed by a single task.
+pub const PAR_CHUNK_SIZE: usize = 1024;
+
+/// A range of the entities of a result, with pointers to its first entity.
+struct Chunk<'a, T>
+where
+    T: Iterable<'a>,
+{
+    array_components: T::ComponentsArray,
+    is_ref_array_components: T::BoolArray,
+    is_any_array_a_ref: bool,
+    count: usize,
+}
+
+// SAFETY: chunks only point to the component data of disjoint entities, the component types are
+// required to be `Send` or `Sync` through the tuple types created from them.
+unsafe impl<'a, T> Send for Chunk<'a, T> where T: Iterable<'a> {}
+unsafe impl<'a, T> Sync for Chunk<'a, T> where T: Iterable<'a> {}
+
+impl<'a, T> Chunk<'a, T>
+where
+    T: Iterable<'a>,
+{
+    fn tuple(&self, index: usize) -> T::TupleType {
+        if self.is_any_array_a_ref {
+            T::create_tuple_with_ref(&self.array_components, &self.is_ref_array_components, index)
+        } else {
+            T::create_tuple(&self.array_components, index)
+        }
+    }
+
+    fn tuple_slices(&self) -> T::TupleSliceType {
+        if self.is_any_array_a_ref {
+            T::create_tuple_slices_with_ref(
+                &self.array_components,
+                &self.is_ref_array_components,
+                self.count,
+            )
+        } else {
+            T::create_tuple_slices(&self.array_components, self.count)
+        }
+    }
+}
+
+/// The chunks of all results of an iterator, which keeps their tables locked and their components
+/// borrowed until dropped.
+struct Chunks<'a, T>
+where
+    T: Iterable<'a>,
+{
+    chunks: Vec<Chunk<'a, T>>,
+    _locks: Vec<TableLock>,
+    _borrows: Option<IterBorrows>,
+}
+
+impl<'a, T> Chunks<'a, T>
+where
+    T: Iterable<'a>,
+{
+    fn collect<I>(source: &I) -> Self
+    where
+        I: IterAPI<'a, T> + ?Sized,
+    {
+        let world = source.world_ptr_mut();
+        let mut chunks = Vec::new();
+        let mut locks = Vec::new();
+        let mut borrows: Option<IterBorrows> = None;
+        let mut written = HashSet::new();
+
+        let mut iter = IterGuard(source.retrieve_iter());
+        while source.iter_next(&mut iter) {
+            let count = iter.count as usize;
+            if count == 0 {
+                continue;
+            }
+
+            assert_disjoint_writes(&iter, &mut written);
+            match &mut borrows {
+                Some(borrows) => borrows.extend(&iter),
+                None => borrows = Some(IterBorrows::new(&iter)),
+            }
+            locks.push(TableLock::new(world, iter.table));
+
+            let components_data = T::create_array_ptrs_of_components(&iter);
+            for offset in (0..count).step_by(PAR_CHUNK_SIZE) {
+                let mut array_components = components_data.array_components.clone();
+                for (index, ptr) in array_components.as_mut().iter_mut().enumerate() {
+                    if !ptr.is_null() && !components_data.is_ref_array_components[index] {
+                        let size = unsafe { ecs_field_size(&*iter, index as i32 + 1) };
+                        *ptr = unsafe { ptr.add(offset * size) };
+                    }
+                }
+
+                chunks.push(Chunk {
+                    array_components,
+                    is_ref_array_components: components_data.is_ref_array_components.clone(),
+                    is_any_array_a_ref: components_data.is_any_array_a_ref,
+                    count: PAR_CHUNK_SIZE.min(count - offset),
+                });
+            }
+        }
+
+        Self {
+            chunks,
+            _locks: locks,
+            _borrows: borrows,
+        }
+    }
+}
+
+/// Panic if a result writes to a component that is shared between its entities, like a singleton
+/// or a component of a parent, or to a component of a table that a previous result already writes
+/// to, like queries with wildcard terms do. The component would be written from multiple threads.
+///
+/// `written` holds the tables, offsets and ids of the components written by the previous results.
+fn assert_disjoint_writes(iter: &IterT, written: &mut HashSet<(*mut TableT, i32, IdT)>) {
+    let filter = iter.query;
+    if filter.is_null() {
+        return;
+    }
+
+    let (terms, term_count) = unsafe { ((*filter).terms, (*filter).term_count) };
+    for index in 0..term_count as usize {
+        let term = unsafe { &*terms.add(index) };
+        let mutable = term.inout == InOutKind::InOut as u32 || term.inout == InOutKind::Out as u32;
+        if !mutable || term.field_index < 0 || term.field_index >= iter.field_count {
+            continue;
+        }
+
+        let field = term.field_index + 1;
+        if unsafe { !ecs_field_is_set(iter, field) } {
+            continue;
+        }
+
+        let id = unsafe { *iter.ids.add(term.field_index as usize) };
+        if unsafe { !ecs_field_is_self(iter, field) } {
+            panic!(
+                "cannot write to component `{}` in parallel: it is shared between entities",
+                Id::new_from_existing(iter.real_world, id).to_str()
+            );
+        }
+        if !written.insert((iter.table, iter.offset, id)) {
+            panic!(
+                "cannot write to component `{}` in parallel: the query matches its entities more than once",
+                Id::new_from_existing(iter.real_world, id).to_str()
+            );
+        }
+    }
+}
+
+/// Run `func` for every chunk, on `pool` or on the current thread pool.
+fn for_each_chunk<'a, T>(
+    chunks: &[Chunk<'a, T>],
+    pool: Option<&ThreadPool>,
+    func: impl Fn(&Chunk<'a, T>) + Sync,
+) where
+    T: Iterable<'a>,
+{
+    let process = || chunks.par_iter().for_each(&func);
+    match pool {
+        Some(pool) => pool.install(process),
+        None => process(),
+    }
+}
+
+pub(crate) fn par_each<'a, T, I>(
+    source: &I,
+    pool: Option<&ThreadPool>,
+    func: impl Fn(T::TupleType) + Sync,
+) where
+    T: Iterable<'a>,
+    T::TupleType: Send,
+    I: IterAPI<'a, T> + ?Sized,
+{
+    let chunks = Chunks::collect(source);
+    for_each_chunk(&chunks.chunks, pool, |chunk| {
+        for index in 0..chunk.count {
+            func(chunk.tuple(index));
+        }
+    });
+}
+
+pub(crate) fn par_iter<'a, T, I>(
+    source: &I,
+    pool: Option<&ThreadPool>,
+    func: impl Fn(T::TupleSliceType) + Sync,
+) where
+    T: Iterable<'a>,
+    T::TupleSliceType: Send,
+    I: IterAPI<'a, T> + ?Sized,
+{
+    let chunks = Chunks::collect(source);
+    for_each_chunk(&chunks.chunks, pool, |chunk| func(chunk.tuple_slices()));
+}
@@ -1,22 +1,27 @@
 //! Query API. Queries are used to iterate over entities that match a filter.
 //! Queries are better for persistence than filters, but are slower to create.
 
-use std::os::raw::c_void;
+use std::{any::TypeId, os::raw::c_void};
 
 use crate::sys::{
-    ecs_abort_, ecs_get_entity, ecs_os_api, ecs_query_changed, ecs_query_desc_t, ecs_query_fini,
-    ecs_query_get_filter, ecs_query_get_group_info, ecs_query_init, ecs_query_iter, ecs_query_next,
-    ecs_query_orphaned,
+    ecs_get_entity, ecs_os_api, ecs_query_changed, ecs_query_desc_t, ecs_query_fini,
+    ecs_query_get_binding_ctx, ecs_query_get_filter, ecs_query_get_group_info, ecs_query_init,
+    ecs_query_iter, ecs_query_next, ecs_query_orphaned,
 };
 
 use super::{
     c_types::{FilterT, IterT, QueryGroupInfoT, QueryT},
+    capture_log,
     entity::Entity,
     filter::FilterView,
     iterable::Iterable,
+    query_ctx_order_by,
     world::World,
-    FlecsErrorCode, IntoEntityId, IterAPI, IterOperations,
+    world_ref::WorldRef,
+    FlecsError, FlecsErrorCode, IntoEntityId, IterAPI, IterOperations, OrderByScope,
+    QueryBindingCtx,
 };
+use crate::ecs_abort;
 
 /// Cached query implementation. Fast to iterate, but slower to create than `Filters`
 #[derive(Clone)]
@@ -24,7 +29,7 @@ pub struct Query<'a, T>
 where
     T: Iterable<'a>,
 {
-    pub world: World,
+    pub world: WorldRef,
     pub query: *mut QueryT,
     _phantom: std::marker::PhantomData<&'a T>,
 }
@@ -35,7 +40,10 @@ where
 {
     #[inline(always)]
     fn retrieve_iter(&self) -> IterT {
-        unsafe { ecs_query_iter(self.world.raw_world, self.query) }
+        unsafe {
+            let _scope = OrderByScope::for_query(self.query);
+            ecs_query_iter(self.world.raw_world, self.query)
+        }
     }
 
     #[inline(always)]
@@ -84,7 +92,7 @@ where
         desc.filter.storage = &mut filter;
         let query = unsafe { ecs_query_init(world.raw_world, &desc) };
         Self {
-            world: world.clone(),
+            world: world.handle(),
             query,
             _phantom: std::marker::PhantomData,
         }
@@ -103,7 +111,7 @@ where
     #[doc(alias = "query::query")]
     pub fn new_ownership(world: &World, query: *mut QueryT) -> Self {
         Self {
-            world: world.clone(),
+            world: world.handle(),
             query,
             _phantom: std::marker::PhantomData,
         }
@@ -121,32 +129,61 @@ where
     /// * C++ API: `query::query`
     #[doc(alias = "query::query")]
     pub fn new_from_desc(world: &World, desc: &mut ecs_query_desc_t) -> Self {
-        let obj = Self {
-            world: world.clone(),
-            query: unsafe { ecs_query_init(world.raw_world, desc) },
-            _phantom: std::marker::PhantomData,
-        };
-        unsafe {
-            if obj.query.is_null() {
-                ecs_abort_(
-                    FlecsErrorCode::InvalidParameter.to_int(),
-                    file!().as_ptr() as *const i8,
-                    line!() as i32,
-                    std::ptr::null(),
-                );
-
-                if let Some(abort_func) = ecs_os_api.abort_ {
-                    abort_func();
-                };
+        match Self::try_new_from_desc(world, desc) {
+            Ok(query) => query,
+            Err(err) => {
+                ecs_abort!(err);
             }
+        }
+    }
+
+    /// Create a new query from a query descriptor, returning an error instead of aborting
+    /// when the descriptor is invalid, for example when its expression fails to parse.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to create the query in
+    /// * `desc` - The query descriptor to create the query from
+    ///
+    /// # Returns
+    ///
+    /// The query, or an error carrying the messages flecs logged while creating it.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `query::query`
+    #[doc(alias = "query::query")]
+    pub fn try_new_from_desc(
+        world: &World,
+        desc: &mut ecs_query_desc_t,
+    ) -> Result<Self, FlecsError> {
+        let (query, log) = capture_log(|| {
+            // flecs sorts the query when it is created
+            let _scope = OrderByScope::new(query_ctx_order_by(desc));
+            unsafe { ecs_query_init(world.raw_world, desc) }
+        });
 
+        unsafe {
             if !desc.filter.terms_buffer.is_null() {
                 if let Some(free_func) = ecs_os_api.free_ {
                     free_func(desc.filter.terms_buffer as *mut _);
                 }
             }
-        };
-        obj
+        }
+
+        if query.is_null() {
+            return Err(FlecsError::new(
+                FlecsErrorCode::InvalidParameter,
+                "failed to create query",
+            )
+            .with_log(log));
+        }
+
+        Ok(Self {
+            world: world.handle(),
+            query,
+            _phantom: std::marker::PhantomData,
+        })
     }
 
     /// Free the query
@@ -174,9 +211,12 @@ where
     #[doc(alias = "query::get_iter")]
     fn get_iter_raw(&mut self, world: &World) -> IterT {
         if !world.is_null() {
-            self.world = world.clone();
+            self.world = world.handle();
+        }
+        unsafe {
+            let _scope = OrderByScope::for_query(self.query);
+            ecs_query_iter(self.world.raw_world, self.query)
         }
-        unsafe { ecs_query_iter(self.world.raw_world, self.query) }
     }
 
     ///  Returns whether the query data changed since the last iteration.
@@ -235,6 +275,43 @@ where
 
     /// Get context for group
     ///
+    /// # Type Parameters
+    ///
+    /// * `G` - The type of the group context, created by the `on_group_create` closure
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The group id to get context for
+    ///
+    /// # Returns
+    ///
+    /// Returns the group context, or `None` if the group doesn't exist or its context isn't a `G`
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `query_base::group_ctx`
+    #[doc(alias = "query_base::group_ctx")]
+    pub fn group_context<G>(&self, group_id: impl IntoEntityId) -> Option<&G>
+    where
+        G: 'static,
+    {
+        let binding_ctx =
+            unsafe { ecs_query_get_binding_ctx(self.query) } as *const QueryBindingCtx;
+        if binding_ctx.is_null()
+            || unsafe { (*binding_ctx).group_context } != Some(TypeId::of::<G>())
+        {
+            return None;
+        }
+
+        let group_ctx = self.group_context_ptr(group_id) as *const G;
+        unsafe { group_ctx.as_ref() }
+    }
+
+    /// Get context for group
+    ///
+    /// This is similar to `group_context<G>`, but returns the context created by a C compatible
+    /// `on_group_create` action.
+    ///
     /// # Arguments
     ///
     /// * `group_id` - The group id to get context for
@@ -247,7 +324,7 @@ where
     ///
     /// * C++ API: `query_base::group_ctx`
     #[doc(alias = "query_base::group_ctx")]
-    pub fn group_context(&self, group_id: impl IntoEntityId) -> *mut c_void {
+    pub fn group_context_ptr(&self, group_id: impl IntoEntityId) -> *mut c_void {
         let group_info = self.group_info(group_id);
 
         if !group_info.is_null() {
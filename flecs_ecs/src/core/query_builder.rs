@@ -1,14 +1,19 @@
 //! Cached query implementation. Fast to iterate, but slower to create than Filter
 
 use std::{
-    ffi::CStr,
+    any::{type_name, TypeId},
+    cmp::Ordering,
     ops::Deref,
     os::raw::{c_int, c_void},
 };
 
-use crate::sys::{
-    ecs_ctx_free_t, ecs_entity_desc_t, ecs_entity_init, ecs_filter_desc_t, ecs_group_by_action_t,
-    ecs_group_create_action_t, ecs_group_delete_action_t, ecs_order_by_action_t, ecs_query_desc_t,
+use crate::{
+    ecs_abort,
+    sys::{
+        ecs_ctx_free_t, ecs_entity_desc_t, ecs_entity_init, ecs_filter_desc_t,
+        ecs_group_by_action_t, ecs_group_create_action_t, ecs_group_delete_action_t,
+        ecs_order_by_action_t, ecs_query_desc_t,
+    },
 };
 
 use super::{
@@ -16,11 +21,16 @@ use super::{
     c_types::{TermT, SEPARATOR},
     component_registration::ComponentId,
     filter_builder::{FilterBuilder, FilterBuilderImpl},
+    free_group_by_ctx, group_by_ctx_mut, group_by_trampoline, group_create_trampoline,
+    group_delete_trampoline,
     iterable::{Filterable, Iterable},
+    order_by_trampoline, owns_group_by_ctx,
     query::Query,
+    release_group_by_ctx,
     term::TermBuilder,
     world::World,
-    EntityT, IdT, IntoEntityId, TableT, Term, WorldT,
+    Entity, EntityT, FlecsError, FlecsErrorCode, IdT, IntoCStr, IntoEntityId, OrderByClosure,
+    QueryBindingCtx, Table, TableT, Term, WorldT,
 };
 
 /// Fast to iterate, but slower to create than Filter
@@ -89,7 +99,7 @@ where
     ///
     /// * C++ API: `query_builder::query_builder`
     #[doc(alias = "query_builder::query_builder")]
-    pub fn new_named(world: &World, name: &CStr) -> Self {
+    pub fn new_named(world: &World, name: impl IntoCStr) -> Self {
         let mut desc = Default::default();
 
         let mut obj = Self {
@@ -97,14 +107,15 @@ where
             filter_builder: FilterBuilder::new_from_desc(world, &mut desc.filter, 0),
         };
 
-        let entity_desc = ecs_entity_desc_t {
-            name: name.as_ptr(),
-            sep: SEPARATOR.as_ptr(),
-            root_sep: SEPARATOR.as_ptr(),
-            ..Default::default()
-        };
-
-        obj.desc.filter.entity = unsafe { ecs_entity_init(world.raw_world, &entity_desc) };
+        obj.desc.filter.entity = name.with_cstr(|name| {
+            let entity_desc = ecs_entity_desc_t {
+                name: name.as_ptr(),
+                sep: SEPARATOR.as_ptr(),
+                root_sep: SEPARATOR.as_ptr(),
+                ..Default::default()
+            };
+            unsafe { ecs_entity_init(world.raw_world, &entity_desc) }
+        });
         T::populate(&mut obj);
         obj
     }
@@ -161,6 +172,27 @@ where
         T::populate(&mut obj);
         obj
     }
+
+    /// Build the query, returning an error instead of aborting when the builder describes an
+    /// invalid query, for example an expression that fails to parse.
+    ///
+    /// # Returns
+    ///
+    /// The query, or an error carrying the messages flecs logged while creating it.
+    pub fn try_build(&mut self) -> Result<Query<'a, T>, FlecsError> {
+        let query = Query::<'a, T>::try_new_from_desc(&self.filter_builder.world, &mut self.desc)?;
+        release_query_ctx(&mut self.desc);
+        Ok(query)
+    }
+}
+
+impl<'a, T> Drop for QueryBuilder<'a, T>
+where
+    T: Iterable<'a>,
+{
+    fn drop(&mut self) {
+        free_query_ctx(&mut self.desc);
+    }
 }
 
 impl<'a, T> Filterable for QueryBuilder<'a, T>
@@ -234,13 +266,17 @@ where
     /// * C++ API: `node_builder::build`
     #[doc(alias = "node_builder::build")]
     fn build(&mut self) -> Self::BuiltType {
-        let world = &self.filter_builder.world;
-        Query::<'a, T>::new_from_desc(world, &mut self.desc)
+        match self.try_build() {
+            Ok(query) => query,
+            Err(err) => {
+                ecs_abort!(err);
+            }
+        }
     }
 }
 
 // This is a raw function pointer type, compatible with C to pass to the desc.
-type OrderByFn<T> = extern "C" fn(EntityT, *const T, EntityT, *const T) -> c_int;
+pub type OrderByFn<T> = extern "C" fn(EntityT, *const T, EntityT, *const T) -> c_int;
 // Assuming some imports and definitions from your previous example, and adding the required ones for this example.
 type GroupByFn = extern "C" fn(*mut WorldT, *mut TableT, IdT, *mut c_void) -> u64;
 
@@ -263,6 +299,54 @@ pub trait QueryBuilderImpl: FilterBuilderImpl {
     /// table changes. If no component is provided, resorting only occurs when
     /// the entity order changes.
     ///
+    /// The closure can capture state, which is dropped together with the query, or with the
+    /// builder if no query is built from it. The closure can be called from any thread that
+    /// iterates the query. As flecs passes no context to the comparator, the closure is only
+    /// available while the query is built or iterated through the Rust API. Systems and pipelines
+    /// are iterated by flecs, building them with a closure aborts, use `order_by_fn` instead.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: The component used to sort.
+    ///
+    /// # Arguments
+    ///
+    /// * `compare`: The closure used to compare the components of two entities.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `query_builder_i::order_by`
+    #[doc(alias = "query_builder_i::order_by")]
+    fn order_by<T>(
+        &mut self,
+        compare: impl Fn(Entity, &T, Entity, &T) -> Ordering + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        T: ComponentId,
+    {
+        let world = self.world_ptr_mut();
+        let component = T::get_id(world);
+        // the world pointer is captured as an address, so the closure can be sent to other threads
+        let world_addr = world as usize;
+        self.order_by_id(component, Some(order_by_trampoline));
+        // the closure of a previous call is dropped
+        binding_ctx_mut(self.desc_query_mut()).order_by =
+            Some(Box::new(move |e1, ptr1, e2, ptr2| unsafe {
+                let world = world_addr as *mut WorldT;
+                compare(
+                    Entity::new_from_existing_raw(world, e1),
+                    &*(ptr1 as *const T),
+                    Entity::new_from_existing_raw(world, e2),
+                    &*(ptr2 as *const T),
+                )
+            }));
+        self
+    }
+
+    /// Sorts the output of a query.
+    ///
+    /// This is similar to `order_by<T>`, but uses a C compatible function instead of a closure.
+    ///
     /// # Type Parameters
     ///
     /// * `T`: The component used to sort.
@@ -275,7 +359,7 @@ pub trait QueryBuilderImpl: FilterBuilderImpl {
     ///
     /// * C++ API: `query_builder_i::order_by`
     #[doc(alias = "query_builder_i::order_by")]
-    fn order_by<T>(&mut self, compare: OrderByFn<T>) -> &mut Self
+    fn order_by_fn<T>(&mut self, compare: OrderByFn<T>) -> &mut Self
     where
         T: ComponentId,
     {
@@ -304,6 +388,9 @@ pub trait QueryBuilderImpl: FilterBuilderImpl {
         let desc = self.desc_query_mut();
         desc.order_by = compare;
         desc.order_by_component = component.get_id();
+        if owns_binding_ctx(desc) {
+            binding_ctx_mut(desc).order_by = None;
+        }
         self
     }
 
@@ -326,6 +413,63 @@ pub trait QueryBuilderImpl: FilterBuilderImpl {
         self.group_by_id_fn(T::get_id(self.world_ptr_mut()), None)
     }
 
+    /// Group and sort matched tables.
+    ///
+    /// This is similar to `group_by_fn<T>`, but uses a closure instead of a C compatible
+    /// function. The closure can capture state, which is dropped together with the query, or with
+    /// the builder if no query is built from it. The closure can be called from any thread that
+    /// matches tables to the query.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: The component used to determine the group rank.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_by`: The closure that determines the group id of a table. It is passed the world,
+    ///   the table and the id of `T`.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `query_builder_i::group_by`
+    #[doc(alias = "query_builder_i::group_by")]
+    fn group_by_with<T>(
+        &mut self,
+        group_by: impl Fn(&World, &Table, IdT) -> u64 + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        T: ComponentId,
+    {
+        self.group_by_id_with(T::get_id(self.world_ptr_mut()), group_by)
+    }
+
+    /// Group and sort matched tables.
+    ///
+    /// This is similar to `group_by_with<T>`, but uses a component identifier instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `component`: The component used to determine the group rank.
+    /// * `group_by`: The closure that determines the group id of a table. It is passed the world,
+    ///   the table and `component`.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `query_builder_i::group_by`
+    #[doc(alias = "query_builder_i::group_by")]
+    fn group_by_id_with(
+        &mut self,
+        component: impl IntoEntityId,
+        group_by: impl Fn(&World, &Table, IdT) -> u64 + Send + Sync + 'static,
+    ) -> &mut Self {
+        let desc = self.desc_query_mut();
+        group_by_ctx_mut(desc).group_by = Some(Box::new(move |world, table, id| {
+            let world = World::new_unowned(world);
+            group_by(&world, &Table::new(&world, table), id)
+        }));
+        self.group_by_id_fn(component, Some(group_by_trampoline))
+    }
+
     /// Group and sort matched tables.
     ///
     /// This function is similar to `order_by`, but instead of sorting individual entities,
@@ -403,6 +547,9 @@ pub trait QueryBuilderImpl: FilterBuilderImpl {
 
     /// Specify context to be passed to the `group_by` function.
     ///
+    /// The closures passed to `group_by_with`, `on_group_create` and `on_group_delete` are stored
+    /// in this context, so it can't be combined with them.
+    ///
     /// # Arguments
     ///
     /// * `ctx`: Context to pass to the `group_by` function.
@@ -414,13 +561,106 @@ pub trait QueryBuilderImpl: FilterBuilderImpl {
     #[doc(alias = "query_builder_i::group_by_ctx")]
     fn group_by_ctx(&mut self, ctx: *mut c_void, ctx_free: ecs_ctx_free_t) -> &mut Self {
         let desc = self.desc_query_mut();
+        if owns_group_by_ctx(desc) {
+            ecs_abort!(
+                FlecsErrorCode::InvalidOperation,
+                "group_by_ctx can't be combined with group_by closures"
+            );
+        }
         desc.group_by_ctx = ctx;
         desc.group_by_ctx_free = ctx_free;
         self
     }
 
+    /// Specify the closure that creates the context of a group.
+    ///
+    /// The context is created when a group is created, and is dropped when the group is deleted,
+    /// or when the query is freed, possibly on another thread. It can be retrieved with
+    /// `Query::group_context`.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `G`: The type of the group context.
+    ///
+    /// # Arguments
+    ///
+    /// * `create`: The closure that creates the context of a group, passed the world and the group
+    ///   id.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `query_builder_i::on_group_create`
+    #[doc(alias = "query_builder_i::on_group_create")]
+    fn on_group_create<G>(
+        &mut self,
+        create: impl Fn(&World, u64) -> G + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        G: Send + 'static,
+    {
+        let desc = self.desc_query_mut();
+        let ctx = group_by_ctx_mut(desc);
+        ctx.on_create = Some(Box::new(move |world, group_id| {
+            let world = World::new_unowned(world);
+            Box::into_raw(Box::new(create(&world, group_id))) as *mut c_void
+        }));
+        ctx.on_delete = Some(Box::new(|_, _, group_ctx| unsafe {
+            drop(Box::from_raw(group_ctx as *mut G));
+        }));
+        desc.on_group_create = Some(group_create_trampoline);
+        desc.on_group_delete = Some(group_delete_trampoline);
+        binding_ctx_mut(desc).group_context = Some(TypeId::of::<G>());
+        self
+    }
+
+    /// Specify the closure that is called when a group is deleted.
+    ///
+    /// The closure takes ownership of the context created by `on_group_create`, which must be
+    /// specified first, with the same context type.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `G`: The type of the group context.
+    ///
+    /// # Arguments
+    ///
+    /// * `delete`: The closure that is passed the world, the group id and the context of a deleted
+    ///   group.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `query_builder_i::on_group_delete`
+    #[doc(alias = "query_builder_i::on_group_delete")]
+    fn on_group_delete<G>(
+        &mut self,
+        delete: impl Fn(&World, u64, G) + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        G: Send + 'static,
+    {
+        let desc = self.desc_query_mut();
+        if binding_ctx_mut(desc).group_context != Some(TypeId::of::<G>()) {
+            ecs_abort!(
+                FlecsErrorCode::InvalidOperation,
+                "on_group_delete expects the context type of on_group_create, got {}",
+                type_name::<G>()
+            );
+        }
+
+        group_by_ctx_mut(desc).on_delete = Some(Box::new(move |world, group_id, group_ctx| {
+            let world = World::new_unowned(world);
+            delete(&world, group_id, *unsafe {
+                Box::from_raw(group_ctx as *mut G)
+            });
+        }));
+        self
+    }
+
     /// Specify the `on_group_create` action.
     ///
+    /// This is similar to `on_group_create<G>`, but uses a C compatible function instead of a
+    /// closure. Its context can be retrieved with `Query::group_context_ptr`.
+    ///
     /// # Arguments
     ///
     /// * `action`: The action to execute when a group is created.
@@ -429,14 +669,20 @@ pub trait QueryBuilderImpl: FilterBuilderImpl {
     ///
     /// * C++ API: `query_builder_i::on_group_create`
     #[doc(alias = "query_builder_i::on_group_create")]
-    fn on_group_create(&mut self, action: ecs_group_create_action_t) -> &mut Self {
+    fn on_group_create_fn(&mut self, action: ecs_group_create_action_t) -> &mut Self {
         let desc = self.desc_query_mut();
         desc.on_group_create = action;
+        if owns_binding_ctx(desc) {
+            binding_ctx_mut(desc).group_context = None;
+        }
         self
     }
 
     /// Specify the `on_group_delete` action.
     ///
+    /// This is similar to `on_group_delete<G>`, but uses a C compatible function instead of a
+    /// closure.
+    ///
     /// # Arguments
     ///
     /// * `action`: The action to execute when a group is deleted.
@@ -445,7 +691,7 @@ pub trait QueryBuilderImpl: FilterBuilderImpl {
     ///
     /// * C++ API: `query_builder_i::on_group_delete`
     #[doc(alias = "query_builder_i::on_group_delete")]
-    fn on_group_delete(&mut self, action: ecs_group_delete_action_t) -> &mut Self {
+    fn on_group_delete_fn(&mut self, action: ecs_group_delete_action_t) -> &mut Self {
         let desc = self.desc_query_mut();
         desc.on_group_delete = action;
         self
@@ -473,3 +719,84 @@ where
         &mut self.desc
     }
 }
+
+/// Get the binding context of the query described by `desc`, which is created on first use.
+///
+/// # Panics
+///
+/// If the `binding_ctx` of the query was set to a context not created by the builder.
+fn binding_ctx_mut(desc: &mut ecs_query_desc_t) -> &mut QueryBindingCtx {
+    if desc.binding_ctx.is_null() {
+        desc.binding_ctx = Box::into_raw(Box::<QueryBindingCtx>::default()) as *mut c_void;
+        desc.binding_ctx_free = Some(binding_ctx_drop);
+    }
+
+    if !owns_binding_ctx(desc) {
+        ecs_abort!(
+            FlecsErrorCode::InvalidOperation,
+            "order_by and group closures can't be combined with a binding_ctx"
+        );
+    }
+
+    unsafe { &mut *(desc.binding_ctx as *mut QueryBindingCtx) }
+}
+
+/// Test whether the binding context of the query described by `desc` was created by the builder.
+fn owns_binding_ctx(desc: &ecs_query_desc_t) -> bool {
+    desc.binding_ctx_free.is_some_and(|ctx_free| {
+        ctx_free as usize == binding_ctx_drop as unsafe extern "C" fn(*mut c_void) as usize
+    })
+}
+
+unsafe extern "C" fn binding_ctx_drop(ctx: *mut c_void) {
+    drop(Box::from_raw(ctx as *mut QueryBindingCtx));
+}
+
+/// Get the closure that sorts the query described by `desc`, if any.
+pub(crate) fn query_ctx_order_by(desc: &ecs_query_desc_t) -> Option<&OrderByClosure> {
+    if !owns_binding_ctx(desc) {
+        return None;
+    }
+    unsafe { &*(desc.binding_ctx as *const QueryBindingCtx) }
+        .order_by
+        .as_deref()
+}
+
+/// Abort if the query of a system or pipeline described by `desc` is sorted by a closure, as
+/// flecs iterates these queries without the closure.
+pub(crate) fn check_flecs_iterated_query(desc: &ecs_query_desc_t) {
+    if query_ctx_order_by(desc).is_some() {
+        ecs_abort!(FlecsError::new(
+            FlecsErrorCode::Unsupported,
+            "systems and pipelines can't be sorted by closures, use `order_by_fn` instead",
+        ));
+    }
+}
+
+/// Hand the contexts created by the builder over to the query that was built from `desc`.
+///
+/// The closures stored in the contexts are removed from `desc`, so a query built from it again
+/// doesn't share them with the first query.
+pub(crate) fn release_query_ctx(desc: &mut ecs_query_desc_t) {
+    if owns_binding_ctx(desc) {
+        if unsafe { &*(desc.binding_ctx as *const QueryBindingCtx) }
+            .order_by
+            .is_some()
+        {
+            desc.order_by = None;
+        }
+        desc.binding_ctx = std::ptr::null_mut();
+        desc.binding_ctx_free = None;
+    }
+    release_group_by_ctx(desc);
+}
+
+/// Free the contexts created by the builder when no query was built from `desc`.
+pub(crate) fn free_query_ctx(desc: &mut ecs_query_desc_t) {
+    if owns_binding_ctx(desc) {
+        unsafe { binding_ctx_drop(desc.binding_ctx) };
+        desc.binding_ctx = std::ptr::null_mut();
+        desc.binding_ctx_free = None;
+    }
+    free_group_by_ctx(desc);
+}
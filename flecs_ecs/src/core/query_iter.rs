@@ -0,0 +1,235 @@
+//! Rust iterators over the results of queries, filters and rules.
+
+use std::{iter::FusedIterator, marker::PhantomData};
+
+use super::{
+    c_types::{EntityT, IterT, WorldT},
+    iterable::{ComponentsData, Iterable},
+    world::World,
+    Entity, IterBorrows, IterGuard, Table, TableLock,
+};
+
+/// The C iterator of a Rust iterator, which locks the tables of the results it returned.
+///
+/// The cursor outlives the Rust iterator borrowing it, so the tables stay locked, and the
+/// components borrowed, until all results returned by the iterator are dropped.
+pub(crate) struct IterCursor {
+    // dropped first, so the tables are unlocked before the iterator is finished
+    borrows: Option<IterBorrows>,
+    locks: Vec<TableLock>,
+    iter: IterGuard,
+    iter_next: unsafe extern "C" fn(*mut IterT) -> bool,
+    world: *mut WorldT,
+    done: bool,
+}
+
+impl IterCursor {
+    pub(crate) fn new(
+        world: *mut WorldT,
+        iter: IterT,
+        iter_next: unsafe extern "C" fn(*mut IterT) -> bool,
+    ) -> Self {
+        Self {
+            borrows: None,
+            locks: Vec::new(),
+            iter: IterGuard(iter),
+            iter_next,
+            world,
+            done: false,
+        }
+    }
+
+    /// Advance to the next result, locking its table.
+    fn next_result(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+
+        // an exhausted iterator is finished by flecs
+        if !unsafe { (self.iter_next)(&mut *self.iter) } {
+            self.done = true;
+            return false;
+        }
+
+        match &mut self.borrows {
+            Some(borrows) => borrows.extend(&self.iter),
+            None => self.borrows = Some(IterBorrows::new(&self.iter)),
+        }
+        self.locks.push(TableLock::new(self.world, self.iter.table));
+        true
+    }
+}
+
+/// Iterator over the entities matched by a query, passed to the closure of
+/// [`IterAPI::iter_entities`].
+///
+/// The components returned by the iterator can't outlive the closure. The tables of the returned
+/// entities stay locked until the closure returns, which also finishes the underlying flecs
+/// iterator if it was not exhausted.
+///
+/// [`IterAPI::iter_entities`]: super::IterAPI::iter_entities
+pub struct EntityIter<'i, 'a, T>
+where
+    T: Iterable<'a>,
+{
+    cursor: &'i mut IterCursor,
+    components: Option<ComponentsData<'a, T>>,
+    row: usize,
+    _components: PhantomData<&'i T>,
+}
+
+impl<'i, 'a, T> EntityIter<'i, 'a, T>
+where
+    T: Iterable<'a>,
+{
+    pub(crate) fn new(cursor: &'i mut IterCursor) -> Self {
+        Self {
+            cursor,
+            components: None,
+            row: 0,
+            _components: PhantomData,
+        }
+    }
+}
+
+impl<'i, 'a, T> Iterator for EntityIter<'i, 'a, T>
+where
+    T: Iterable<'a> + 'i,
+{
+    type Item = (Entity, T::TupleTypeIn<'i>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(components_data) = &self.components {
+                let iter = &*self.cursor.iter;
+                let row = self.row;
+                if row < iter.count as usize {
+                    self.row += 1;
+
+                    let entity = Entity::new_from_existing_raw(self.cursor.world, unsafe {
+                        *iter.entities.add(row)
+                    });
+                    let array_components = &components_data.array_components;
+                    let tuple = if components_data.is_any_array_a_ref {
+                        let is_ref_array_components = &components_data.is_ref_array_components;
+                        T::create_tuple_with_ref(array_components, is_ref_array_components, row)
+                    } else {
+                        T::create_tuple(array_components, row)
+                    };
+                    // the table stays locked until the closure of `iter_entities` returns
+                    return Some((entity, unsafe { T::rebind_tuple(tuple) }));
+                }
+            }
+
+            if !self.cursor.next_result() {
+                self.components = None;
+                return None;
+            }
+            self.components = Some(T::create_array_ptrs_of_components(&self.cursor.iter));
+            self.row = 0;
+        }
+    }
+}
+
+impl<'i, 'a, T> FusedIterator for EntityIter<'i, 'a, T> where T: Iterable<'a> + 'i {}
+
+/// Iterator over the tables matched by a query, passed to the closure of
+/// [`IterAPI::iter_tables`].
+///
+/// The views returned by the iterator can't outlive the closure. The tables of the returned views
+/// stay locked until the closure returns, which also finishes the underlying flecs iterator if it
+/// was not exhausted.
+///
+/// [`IterAPI::iter_tables`]: super::IterAPI::iter_tables
+pub struct TableIter<'i, 'a, T>
+where
+    T: Iterable<'a>,
+{
+    cursor: &'i mut IterCursor,
+    _phantom: PhantomData<&'i T::TupleSliceType>,
+}
+
+impl<'i, 'a, T> TableIter<'i, 'a, T>
+where
+    T: Iterable<'a>,
+{
+    pub(crate) fn new(cursor: &'i mut IterCursor) -> Self {
+        Self {
+            cursor,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'i, 'a, T> Iterator for TableIter<'i, 'a, T>
+where
+    T: Iterable<'a> + 'i,
+{
+    type Item = TableView<'i, 'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.cursor.next_result() {
+            return None;
+        }
+
+        let iter = &*self.cursor.iter;
+        let count = iter.count as usize;
+        let components_data = T::create_array_ptrs_of_components(iter);
+        let array_components = &components_data.array_components;
+        let components = if components_data.is_any_array_a_ref {
+            let is_ref_array_components = &components_data.is_ref_array_components;
+            T::create_tuple_slices_with_ref(array_components, is_ref_array_components, count)
+        } else {
+            T::create_tuple_slices(array_components, count)
+        };
+
+        let entities = if iter.entities.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(iter.entities, count) }
+        };
+
+        Some(TableView {
+            world: self.cursor.world,
+            table: Table::new(World::new_unowned(iter.real_world), iter.table),
+            entities,
+            // the table stays locked until the closure of `iter_tables` returns
+            components: unsafe { T::rebind_tuple_slices(components) },
+        })
+    }
+}
+
+impl<'i, 'a, T> FusedIterator for TableIter<'i, 'a, T> where T: Iterable<'a> + 'i {}
+
+/// The entities of a table matched by a query, and slices of their components.
+pub struct TableView<'i, 'a, T>
+where
+    T: Iterable<'a> + 'i,
+{
+    world: *mut WorldT,
+    /// The matched table.
+    pub table: Table,
+    /// The ids of the entities, in the same order as the components.
+    pub entities: &'i [EntityT],
+    /// The slices of the components, in the order of the query terms.
+    pub components: T::TupleSliceTypeIn<'i>,
+}
+
+impl<'i, 'a, T> TableView<'i, 'a, T>
+where
+    T: Iterable<'a> + 'i,
+{
+    /// Return the number of entities in the view
+    pub fn count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Get the entity at the given index
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the entity in the view
+    pub fn entity(&self, index: usize) -> Entity {
+        Entity::new_from_existing_raw(self.world, self.entities[index])
+    }
+}
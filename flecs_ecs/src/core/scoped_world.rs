@@ -1,11 +1,11 @@
 use std::ops::Deref;
 
-use super::{c_types::EntityT, world::World, IntoEntityId};
+use super::{c_types::EntityT, world::World, world_ref::WorldRef, IntoEntityId};
 use crate::sys::ecs_set_scope;
 
 /// Utility class used by the `world::scope` method to create entities in a scope
 pub struct ScopedWorld {
-    pub world: World,
+    pub world: WorldRef,
     pub prev_scope: EntityT,
 }
 
@@ -33,7 +33,7 @@ impl ScopedWorld {
     pub fn new(world: &World, scope: impl IntoEntityId) -> Self {
         let prev_scope = unsafe { ecs_set_scope(world.raw_world, scope.get_id()) };
         Self {
-            world: world.clone(),
+            world: world.handle(),
             prev_scope,
         }
     }
@@ -0,0 +1,194 @@
+//! Stages record operations on a world from other threads, to be merged with the world later.
+
+use std::{marker::PhantomData, ops::Deref};
+
+use crate::sys::{
+    ecs_async_stage_free, ecs_get_stage_id, ecs_is_deferred, ecs_merge, ecs_stage_is_async,
+};
+
+use super::{Entity, IntoEntityId, IntoWorld, World, WorldT};
+
+/// Handle to a stage of a world.
+///
+/// A stage is a private command queue of a world. Entities obtained through a stage record
+/// their structural changes in the queue of the stage instead of applying them to the world,
+/// which is what allows multiple threads to work on the same world: every thread gets its own
+/// stage, and the queues are merged once the threads are done.
+///
+/// Stages are returned by [`World::stage`], [`World::stages`] and [`World::create_async_stage`].
+/// A stage can be sent to another thread. It borrows the world mutably, so it can't outlive it
+/// and the world can't be used from other threads while the stage is alive. Because stages may only be used for deferred operations, entity handles are only handed out
+/// while the stage is deferred, which is the case for all stages while the world is in readonly
+/// mode (see [`World::readonly_begin`]) and always for asynchronous stages. Components have to be
+/// registered with the world before they are used through a stage.
+///
+/// # See also
+///
+/// * C++ API: `world::get_stage`
+#[doc(alias = "world::get_stage")]
+pub struct Stage<'w> {
+    raw_stage: *mut WorldT,
+    _world: PhantomData<&'w mut World>,
+}
+
+// SAFETY: flecs allows a stage to be used from any thread, as long as a single thread uses it at a
+// time. The handle is not `Clone`, it borrows the world mutably and all operations require
+// deferred mode, so the world itself is never mutated through the stage.
+unsafe impl Send for Stage<'_> {}
+
+impl<'w> Stage<'w> {
+    /// Wrap a stage of a world.
+    ///
+    /// # Safety
+    ///
+    /// `raw_stage` must be a stage of a world that outlives `'w` and that is not used through any
+    /// other handle during `'w`.
+    pub(crate) unsafe fn new(raw_stage: *mut WorldT) -> Self {
+        Self {
+            raw_stage,
+            _world: PhantomData,
+        }
+    }
+
+    /// Get the id of the stage.
+    ///
+    /// # Returns
+    ///
+    /// The stage id, which typically corresponds with a worker thread id, or -1 for asynchronous
+    /// stages.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::get_stage_id`
+    #[doc(alias = "world::get_stage_id")]
+    pub fn id(&self) -> i32 {
+        unsafe { ecs_get_stage_id(self.raw_stage) }
+    }
+
+    /// Test if the stage is asynchronous.
+    ///
+    /// Asynchronous stages are created with [`World::create_async_stage`], they are always
+    /// deferred and never merged automatically.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::is_async`
+    #[doc(alias = "world::is_async")]
+    pub fn is_async(&self) -> bool {
+        unsafe { ecs_stage_is_async(self.raw_stage) }
+    }
+
+    /// Test if operations through the stage are currently deferred.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::is_deferred`
+    #[doc(alias = "world::is_deferred")]
+    pub fn is_deferred(&self) -> bool {
+        unsafe { ecs_is_deferred(self.raw_stage) }
+    }
+
+    /// Create a new entity whose operations are recorded in the stage.
+    ///
+    /// The returned handle borrows the stage, so it can't be used after the stage is dropped.
+    ///
+    /// Entity ids are only allocated atomically for asynchronous stages and while the world runs
+    /// systems on multiple threads. When stages are handed out after [`World::readonly_begin`],
+    /// create entities up front or from a single stage, and only modify them from the others.
+    ///
+    /// # Panics
+    ///
+    /// If the stage is not deferred.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::entity`
+    #[doc(alias = "world::entity")]
+    pub fn new_entity(&self) -> StageEntity<'_> {
+        self.assert_deferred();
+        StageEntity::new(Entity::new(self.raw_stage))
+    }
+
+    /// Get a handle to an existing entity whose operations are recorded in the stage.
+    ///
+    /// The returned handle borrows the stage, so it can't be used after the stage is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the entity.
+    ///
+    /// # Panics
+    ///
+    /// If the stage is not deferred.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::entity`
+    #[doc(alias = "world::entity")]
+    pub fn entity_from_id(&self, id: impl IntoEntityId) -> StageEntity<'_> {
+        self.assert_deferred();
+        StageEntity::new(Entity::new_from_existing_raw(self.raw_stage, id.get_id()))
+    }
+
+    /// Merge the operations recorded in the stage with the world.
+    ///
+    /// This is only needed for asynchronous stages or when automatic merging is disabled, and
+    /// must not be called while the world is in readonly mode.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::merge`
+    #[doc(alias = "world::merge")]
+    pub fn merge(&mut self) {
+        unsafe { ecs_merge(self.raw_stage) };
+    }
+
+    fn assert_deferred(&self) {
+        if !self.is_deferred() {
+            panic!("stage operations must be deferred, call `readonly_begin` on the world first");
+        }
+    }
+}
+
+impl IntoWorld for Stage<'_> {
+    #[inline]
+    #[doc(hidden)]
+    fn world_ptr_mut(&self) -> *mut WorldT {
+        self.raw_stage
+    }
+}
+
+impl Drop for Stage<'_> {
+    fn drop(&mut self) {
+        if self.is_async() {
+            unsafe { ecs_async_stage_free(self.raw_stage) };
+        }
+    }
+}
+
+/// Handle to an entity whose operations are recorded in a [`Stage`].
+///
+/// Returned by [`Stage::new_entity`] and [`Stage::entity_from_id`]. The handle borrows the stage
+/// it was obtained from, because the entity refers to the stage rather than the world.
+pub struct StageEntity<'s> {
+    entity: Entity,
+    _stage: PhantomData<&'s Stage<'s>>,
+}
+
+impl StageEntity<'_> {
+    fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            _stage: PhantomData,
+        }
+    }
+}
+
+impl Deref for StageEntity<'_> {
+    type Target = Entity;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.entity
+    }
+}
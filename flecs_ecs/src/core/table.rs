@@ -4,7 +4,8 @@ use std::{ffi::CStr, os::raw::c_void};
 
 use crate::sys::{
     ecs_table_count, ecs_table_get_column, ecs_table_get_column_index, ecs_table_get_column_size,
-    ecs_table_get_depth, ecs_table_get_type, ecs_table_get_type_index, ecs_table_str,
+    ecs_table_get_depth, ecs_table_get_type, ecs_table_get_type_index, ecs_table_lock,
+    ecs_table_str, ecs_table_unlock,
 };
 
 use super::{
@@ -583,6 +584,27 @@ impl Table {
     }
 }
 
+/// Locks a table for as long as it is alive, also when unwinding from a panic.
+///
+/// While locked, operations that would move entities out of the table assert.
+pub(crate) struct TableLock {
+    world: *mut WorldT,
+    table: *mut TableT,
+}
+
+impl TableLock {
+    pub(crate) fn new(world: *mut WorldT, table: *mut TableT) -> Self {
+        unsafe { ecs_table_lock(world, table) };
+        Self { world, table }
+    }
+}
+
+impl Drop for TableLock {
+    fn drop(&mut self) {
+        unsafe { ecs_table_unlock(self.world, self.table) };
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct TableRange {
     pub table: Table,
@@ -642,7 +664,7 @@ impl TableRange {
     /// The world and table pointers must be valid
     pub(crate) fn new_raw(world: *mut WorldT, table: *mut TableT, offset: i32, count: i32) -> Self {
         Self {
-            table: Table::new(&World::new_wrap_raw_world(world), table),
+            table: Table::new(World::new_unowned(world), table),
             offset,
             count,
         }
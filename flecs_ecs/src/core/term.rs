@@ -8,7 +8,10 @@ use crate::{
         ecs_term_fini, ecs_term_is_initialized, ecs_term_move,
     },
 };
-use std::ffi::CStr;
+use std::{
+    borrow::Cow,
+    ffi::{c_char, CString},
+};
 
 use super::{
     c_types::{
@@ -19,7 +22,8 @@ use super::{
     entity::Entity,
     id::Id,
     world::World,
-    IntoComponentId, IntoEntityId, IntoEntityIdExt, RUST_ecs_id_FLAGS_MASK, ECS_DESC,
+    IntoComponentId, IntoEntityId, IntoEntityIdExt, IntoStaticCStr, RUST_ecs_id_FLAGS_MASK,
+    ECS_DESC,
 };
 
 /// Struct that describes a term identifier.
@@ -34,6 +38,10 @@ pub struct Term {
     pub term_ptr: *mut TermT,
     pub term: TermT,
     world: *mut WorldT,
+    /// names set on the term, kept alive until the term is dropped or the filter is created.
+    strings: Vec<CString>,
+    /// identifiers of `term` whose name was set by the builder, one bit each for first, src and second.
+    borrowed_names: u8,
 }
 
 impl Default for Term {
@@ -41,6 +49,8 @@ impl Default for Term {
         Self {
             term_id_ptr: std::ptr::null_mut(),
             term_ptr: std::ptr::null_mut(),
+            strings: Vec::new(),
+            borrowed_names: 0,
             term: Default::default(),
             world: std::ptr::null_mut(),
         }
@@ -53,6 +63,8 @@ impl Clone for Term {
         let mut obj = Self {
             term_id_ptr: std::ptr::null_mut(),
             term_ptr: std::ptr::null_mut(),
+            strings: Vec::new(),
+            borrowed_names: 0,
             term: Default::default(),
             world: self.world,
         };
@@ -92,6 +104,8 @@ impl Term {
             term_id_ptr: std::ptr::null_mut(),
             term,
             term_ptr: std::ptr::null_mut(),
+            strings: Vec::new(),
+            borrowed_names: 0,
         };
         obj.term.move_ = false;
         let obj_term = &mut obj.term as *mut TermT;
@@ -115,6 +129,8 @@ impl Term {
             term_id_ptr: std::ptr::null_mut(),
             term: Default::default(),
             term_ptr: std::ptr::null_mut(),
+            strings: Vec::new(),
+            borrowed_names: 0,
         };
         obj.term.move_ = true;
         obj
@@ -122,6 +138,8 @@ impl Term {
 
     /// Create a new term from a component or pair
     ///
+    /// The component types are registered with the world if they aren't yet.
+    ///
     /// # Arguments
     ///
     /// * `world` - The world to use.
@@ -134,43 +152,13 @@ impl Term {
     ///
     /// * C++ API: `term::term`
     #[doc(alias = "term::term")]
-    pub fn new_type<T: IntoComponentId>(world: Option<&World>) -> Self {
+    pub fn new_type<T: IntoComponentId>(world: &World) -> Self {
         if !T::IS_PAIR {
-            let id: IdT = if T::First::is_registered() {
-                unsafe { T::First::get_id_unchecked() }
-            } else {
-                ecs_assert!(
-                    false,
-                    FlecsErrorCode::InvalidOperation,
-                    "component not registered"
-                );
-                0
-            };
-            Self::new_id(world, id)
+            Self::new_id(Some(world), <T::First as ComponentId>::get_id(world))
         } else {
-            let id_rel = if T::First::is_registered() {
-                unsafe { T::First::get_id_unchecked() }
-            } else {
-                ecs_assert!(
-                    false,
-                    FlecsErrorCode::InvalidOperation,
-                    "component not registered"
-                );
-                0
-            };
-
-            let id_target = if T::Second::is_registered() {
-                unsafe { T::Second::get_id_unchecked() }
-            } else {
-                ecs_assert!(
-                    false,
-                    FlecsErrorCode::InvalidOperation,
-                    "component not registered"
-                );
-                0
-            };
-
-            Self::new_id(world, (id_rel, id_target))
+            let id_rel = <T::First as ComponentId>::get_id(world);
+            let id_target = <T::Second as ComponentId>::get_id(world);
+            Self::new_id(Some(world), (id_rel, id_target))
         }
     }
 
@@ -196,6 +184,8 @@ impl Term {
             world,
             term_id_ptr: std::ptr::null_mut(),
             term_ptr: std::ptr::null_mut(),
+            strings: Vec::new(),
+            borrowed_names: 0,
             term: Default::default(),
         };
 
@@ -233,6 +223,8 @@ impl Term {
             world: self.world,
             term_id_ptr: std::ptr::null_mut(),
             term_ptr: std::ptr::null_mut(),
+            strings: Vec::new(),
+            borrowed_names: 0,
             term: Default::default(),
         };
         obj.term = unsafe { ecs_term_move(&mut self.term) };
@@ -361,8 +353,39 @@ impl Term {
     /// * C++ API: `term::move`
     #[doc(alias = "term::move")]
     pub fn move_raw_term(&mut self) -> TermT {
+        if self.borrowed_names != 0 {
+            // names set by the builder aren't owned by flecs, let flecs copy them instead
+            self.term.move_ = false;
+        }
         unsafe { ecs_term_move(&mut self.term) }
     }
+
+    /// Keep a name alive for as long as the term, and return a pointer to it.
+    pub(crate) fn store_name(&mut self, name: impl IntoStaticCStr) -> *const c_char {
+        match name.into_static_cstr() {
+            Cow::Borrowed(name) => name.as_ptr(),
+            Cow::Owned(name) => {
+                let ptr = name.as_ptr();
+                self.strings.push(name);
+                ptr
+            }
+        }
+    }
+
+    /// Set the name of a term identifier, keeping it alive for as long as the term.
+    pub(crate) fn set_id_name(&mut self, id: *mut TermIdT, name: impl IntoStaticCStr) {
+        let name = self.store_name(name);
+        let term = &mut self.term;
+        for (bit, term_id) in [&mut term.first, &mut term.src, &mut term.second]
+            .into_iter()
+            .enumerate()
+        {
+            if std::ptr::eq(term_id, id) {
+                self.borrowed_names |= 1 << bit;
+            }
+        }
+        unsafe { (*id).name = name };
+    }
 }
 
 /// Builder pattern functions
@@ -370,6 +393,16 @@ impl Term {}
 
 impl Drop for Term {
     fn drop(&mut self) {
+        // names owned by the term are freed by flecs, except for the ones set by the builder
+        let term = &mut self.term;
+        for (bit, term_id) in [&mut term.first, &mut term.src, &mut term.second]
+            .into_iter()
+            .enumerate()
+        {
+            if self.borrowed_names & (1 << bit) != 0 {
+                term_id.name = std::ptr::null();
+            }
+        }
         unsafe { ecs_term_fini(&mut self.term) };
     }
 }
@@ -635,12 +668,11 @@ pub trait TermBuilder: Sized {
     ///
     /// * C++ API: `term_builder_i::name`
     #[doc(alias = "term_builder_i::name")]
-    fn name(&mut self, name: &CStr) -> &mut Self {
+    fn name(&mut self, name: impl IntoStaticCStr) -> &mut Self {
         self.assert_term_id_ptr_mut();
-        unsafe {
-            (*self.term_id_ptr_mut()).name = name.as_ptr() as *mut i8;
-            (*self.term_id_ptr_mut()).flags |= ECS_IS_NAME;
-        };
+        let term_id = self.term_id_ptr_mut();
+        self.term_mut().set_id_name(term_id, name);
+        unsafe { (*term_id).flags |= ECS_IS_NAME };
         self
     }
 
@@ -654,12 +686,11 @@ pub trait TermBuilder: Sized {
     ///
     /// * C++ API: `term_builder_i::var`
     #[doc(alias = "term_builder_i::var")]
-    fn var(&mut self, var_name: &CStr) -> &mut Self {
+    fn var(&mut self, var_name: impl IntoStaticCStr) -> &mut Self {
         self.assert_term_id_ptr_mut();
-        unsafe {
-            (*self.term_id_ptr_mut()).flags |= ECS_IS_VARIABLE;
-            (*self.term_id_ptr_mut()).name = var_name.as_ptr() as *mut i8;
-        };
+        let term_id = self.term_id_ptr_mut();
+        self.term_mut().set_id_name(term_id, var_name);
+        unsafe { (*term_id).flags |= ECS_IS_VARIABLE };
         self
     }
 
@@ -764,21 +795,9 @@ pub trait TermBuilder: Sized {
     ///
     /// * C++ API: `term_builder_i::src`
     #[doc(alias = "term_builder_i::src")]
-    fn select_src_name(&mut self, name: &'static CStr) -> &mut Self {
-        ecs_assert!(
-            !name.is_empty(),
-            FlecsErrorCode::InvalidParameter,
-            "name is empty"
-        );
-
+    fn select_src_name(&mut self, name: impl IntoStaticCStr) -> &mut Self {
         self.setup_src();
-        if let Some(stripped_name) =
-            strip_prefix_cstr_raw(name, CStr::from_bytes_with_nul(b"$\0").unwrap())
-        {
-            self.var(stripped_name)
-        } else {
-            self.name(name)
-        }
+        name_or_var(self, name)
     }
 
     /// Select first identifier, initialize it with entity id
@@ -821,21 +840,9 @@ pub trait TermBuilder: Sized {
     ///
     /// * C++ API: `term_builder_i::first`
     #[doc(alias = "term_builder_i::first")]
-    fn select_first_name(&mut self, name: &'static CStr) -> &mut Self {
-        ecs_assert!(
-            !name.is_empty(),
-            FlecsErrorCode::InvalidParameter,
-            "name is empty"
-        );
-
+    fn select_first_name(&mut self, name: impl IntoStaticCStr) -> &mut Self {
         self.setup_first();
-        if let Some(stripped_name) =
-            strip_prefix_cstr_raw(name, CStr::from_bytes_with_nul(b"$\0").unwrap())
-        {
-            self.var(stripped_name)
-        } else {
-            self.name(name)
-        }
+        name_or_var(self, name)
     }
 
     /// Select second identifier, initialize it with entity id
@@ -878,21 +885,9 @@ pub trait TermBuilder: Sized {
     ///
     /// * C++ API: `term_builder_i::second`
     #[doc(alias = "term_builder_i::second")]
-    fn select_second_name(&mut self, name: &'static CStr) -> &mut Self {
-        ecs_assert!(
-            !name.is_empty(),
-            FlecsErrorCode::InvalidParameter,
-            "name is empty"
-        );
-
+    fn select_second_name(&mut self, name: impl IntoStaticCStr) -> &mut Self {
         self.setup_second();
-        if let Some(stripped_name) =
-            strip_prefix_cstr_raw(name, CStr::from_bytes_with_nul(b"$\0").unwrap())
-        {
-            self.var(stripped_name)
-        } else {
-            self.name(name)
-        }
+        name_or_var(self, name)
     }
 
     /// Set role of term
@@ -1160,6 +1155,27 @@ pub trait TermBuilder: Sized {
     }
 }
 
+/// Set the name of the current term identifier, or the variable name if `name` starts with a $.
+fn name_or_var<T: TermBuilder>(builder: &mut T, name: impl IntoStaticCStr) -> &mut T {
+    let name = name.into_static_cstr();
+    ecs_assert!(
+        !name.is_empty(),
+        FlecsErrorCode::InvalidParameter,
+        "name is empty"
+    );
+
+    match name {
+        Cow::Borrowed(name) => match strip_prefix_cstr_raw(name, c"$") {
+            Some(var_name) => builder.var(var_name),
+            None => builder.name(name),
+        },
+        Cow::Owned(name) => match strip_prefix_cstr_raw(&name, c"$") {
+            Some(var_name) => builder.var(var_name.to_owned()),
+            None => builder.name(name),
+        },
+    }
+}
+
 impl TermBuilder for Term {
     fn world_ptr_mut(&self) -> *mut WorldT {
         self.world
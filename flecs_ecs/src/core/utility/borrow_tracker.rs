@@ -0,0 +1,252 @@
+//! Runtime tracking of component borrows.
+//!
+//! Borrowed components are registered with a thread local tracker that follows `RefCell`
+//! semantics: a component can be borrowed by any number of readers or by a single writer.
+//! Conflicting borrows panic with the name of the component.
+//!
+//! With the `flecs_borrow_tracking` feature enabled (and ecs asserts enabled), the components
+//! borrowed by iterators and component guards are registered. Without the feature these borrows
+//! compile to nothing.
+
+#[cfg(all(
+    feature = "flecs_borrow_tracking",
+    any(debug_assertions, feature = "flecs_force_enable_ecs_asserts")
+))]
+mod tracking {
+    use std::{cell::RefCell, collections::HashMap, ffi::CStr, os::raw::c_void};
+
+    use crate::core::{EntityT, IdT, InOutKind, IterT, WorldT};
+    use crate::sys::{ecs_get_world, ecs_id_str, ecs_os_api};
+
+    #[derive(Default)]
+    struct ComponentBorrows {
+        /// borrow of the component for all entities, positive for readers, -1 for a writer.
+        all: isize,
+        /// borrows of the component of single entities, using the same encoding.
+        entities: HashMap<EntityT, isize>,
+    }
+
+    thread_local! {
+        static BORROWS: RefCell<HashMap<(usize, IdT), ComponentBorrows>> =
+            RefCell::new(HashMap::new());
+    }
+
+    fn id_str(world: *const WorldT, id: IdT) -> String {
+        unsafe {
+            let str = ecs_id_str(world, id);
+            if str.is_null() {
+                return id.to_string();
+            }
+            let result = CStr::from_ptr(str).to_string_lossy().into_owned();
+            if let Some(free) = ecs_os_api.free_ {
+                free(str as *mut c_void);
+            }
+            result
+        }
+    }
+
+    /// Borrow of a component, released when dropped.
+    ///
+    /// `entity` is 0 when the component is borrowed for all entities, like iterators do.
+    struct ComponentBorrow {
+        world: usize,
+        id: IdT,
+        entity: EntityT,
+        mutable: bool,
+    }
+
+    impl ComponentBorrow {
+        fn new(world: *const WorldT, id: IdT, entity: EntityT, mutable: bool) -> Self {
+            let world = unsafe { ecs_get_world(world as *const c_void) };
+            let key = (world as usize, id);
+
+            let acquired = BORROWS.with(|borrows| {
+                let mut borrows = borrows.borrow_mut();
+                let borrows = borrows.entry(key).or_default();
+                let state = if entity == 0 {
+                    borrows.all
+                } else {
+                    borrows.entities.get(&entity).copied().unwrap_or(0)
+                };
+
+                let conflict = if mutable {
+                    borrows.all != 0 || state != 0 || (entity == 0 && !borrows.entities.is_empty())
+                } else {
+                    borrows.all < 0
+                        || state < 0
+                        || (entity == 0 && borrows.entities.values().any(|state| *state < 0))
+                };
+                if conflict {
+                    return false;
+                }
+
+                let new_state = if mutable { -1 } else { state + 1 };
+                if entity == 0 {
+                    borrows.all = new_state;
+                } else {
+                    borrows.entities.insert(entity, new_state);
+                }
+                true
+            });
+
+            if !acquired {
+                let target = if entity == 0 {
+                    String::new()
+                } else {
+                    format!(" of entity `{}`", id_str(world, entity))
+                };
+                if mutable {
+                    panic!(
+                        "cannot borrow component `{}`{} mutably: it is already borrowed",
+                        id_str(world, id),
+                        target
+                    );
+                } else {
+                    panic!(
+                        "cannot borrow component `{}`{}: it is already borrowed mutably",
+                        id_str(world, id),
+                        target
+                    );
+                }
+            }
+
+            Self {
+                world: world as usize,
+                id,
+                entity,
+                mutable,
+            }
+        }
+    }
+
+    impl Drop for ComponentBorrow {
+        fn drop(&mut self) {
+            // the tracker may already be destroyed when the thread exits
+            let _ = BORROWS.try_with(|borrows| {
+                let mut borrows = borrows.borrow_mut();
+                let key = (self.world, self.id);
+                let Some(component) = borrows.get_mut(&key) else {
+                    return;
+                };
+
+                if self.entity == 0 {
+                    component.all = if self.mutable { 0 } else { component.all - 1 };
+                } else if let Some(state) = component.entities.get_mut(&self.entity) {
+                    *state = if self.mutable { 0 } else { *state - 1 };
+                    if *state == 0 {
+                        component.entities.remove(&self.entity);
+                    }
+                }
+
+                if component.all == 0 && component.entities.is_empty() {
+                    borrows.remove(&key);
+                }
+            });
+        }
+    }
+
+    /// Borrow of the component of a single entity, held by component guards.
+    pub(crate) struct GuardBorrow {
+        _borrow: ComponentBorrow,
+    }
+
+    impl GuardBorrow {
+        #[inline(always)]
+        pub(crate) fn new(world: *const WorldT, id: IdT, entity: EntityT, mutable: bool) -> Self {
+            Self {
+                _borrow: ComponentBorrow::new(world, id, entity, mutable),
+            }
+        }
+    }
+
+    /// Borrows of the fields of the current result of an iterator.
+    pub(crate) struct IterBorrows {
+        borrows: Vec<ComponentBorrow>,
+    }
+
+    impl IterBorrows {
+        pub(crate) fn new(iter: &IterT) -> Self {
+            let mut borrows = Self {
+                borrows: Vec::new(),
+            };
+            borrows.extend(iter);
+            borrows
+        }
+
+        /// Borrow the fields of another result of the iterator, skipping components that are
+        /// already borrowed.
+        pub(crate) fn extend(&mut self, iter: &IterT) {
+            let filter = iter.query;
+            if filter.is_null() || iter.ids.is_null() {
+                return;
+            }
+
+            // only borrows of previous results are skipped, the fields of a single result can't
+            // alias each other
+            let previous = self.borrows.len();
+            let (terms, term_count) = unsafe { ((*filter).terms, (*filter).term_count) };
+            for index in 0..term_count as usize {
+                let term = unsafe { &*terms.add(index) };
+                let mutable = if term.inout == InOutKind::In as u32 {
+                    false
+                } else if term.inout == InOutKind::InOut as u32
+                    || term.inout == InOutKind::Out as u32
+                {
+                    true
+                } else {
+                    continue;
+                };
+
+                if term.field_index < 0 || term.field_index >= iter.field_count {
+                    continue;
+                }
+
+                let id = unsafe { *iter.ids.add(term.field_index as usize) };
+                if id != 0
+                    && !self.borrows[..previous]
+                        .iter()
+                        .any(|borrow| borrow.id == id)
+                {
+                    self.borrows
+                        .push(ComponentBorrow::new(iter.real_world, id, 0, mutable));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(all(
+    feature = "flecs_borrow_tracking",
+    any(debug_assertions, feature = "flecs_force_enable_ecs_asserts")
+)))]
+mod tracking {
+    use crate::core::{EntityT, IdT, IterT, WorldT};
+
+    pub(crate) struct GuardBorrow;
+
+    impl GuardBorrow {
+        #[inline(always)]
+        pub(crate) fn new(
+            _world: *const WorldT,
+            _id: IdT,
+            _entity: EntityT,
+            _mutable: bool,
+        ) -> Self {
+            Self
+        }
+    }
+
+    pub(crate) struct IterBorrows;
+
+    impl IterBorrows {
+        #[inline(always)]
+        pub(crate) fn new(_iter: &IterT) -> Self {
+            Self
+        }
+
+        #[inline(always)]
+        pub(crate) fn extend(&mut self, _iter: &IterT) {}
+    }
+}
+
+pub(crate) use tracking::*;
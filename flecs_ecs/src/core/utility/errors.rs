@@ -11,6 +11,7 @@ impl std::fmt::Display for InvalidStrFromId {
 }
 
 /// Enum representing the error codes that can be used by `ecs_asserts` and `ecs_abort`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FlecsErrorCode {
     InvalidOperation,
     InvalidParameter,
@@ -134,6 +135,66 @@ impl FlecsErrorCode {
     }
 }
 
+/// Error returned by operations that can fail on user input, like parsing a query expression.
+///
+/// Besides the error code and a message describing the failed operation, the error carries the
+/// text flecs logged while the operation ran, which usually contains the actual reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlecsError {
+    code: FlecsErrorCode,
+    message: String,
+    log: String,
+}
+
+impl FlecsError {
+    /// Create a new error.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The error code.
+    /// * `message` - A description of the failed operation.
+    pub fn new(code: FlecsErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            log: String::new(),
+        }
+    }
+
+    /// Attach the log text flecs emitted while the failed operation ran.
+    pub(crate) fn with_log(mut self, log: String) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Returns the error code.
+    pub fn code(&self) -> FlecsErrorCode {
+        self.code
+    }
+
+    /// Returns the description of the failed operation.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the text flecs logged while the operation ran, empty if nothing was logged.
+    pub fn log(&self) -> &str {
+        &self.log
+    }
+}
+
+impl Display for FlecsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)?;
+        if !self.log.is_empty() {
+            write!(f, " ({})", self.log.lines().collect::<Vec<_>>().join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FlecsError {}
+
 /// Macro to assert a condition.
 /// In release mode, the condition is not checked.
 /// Can be turned off by disabling the `flecs_ecs_asserts` feature
@@ -397,15 +397,15 @@ pub(crate) unsafe fn print_c_string(c_string: *const c_char) {
 
 /// Strips the given prefix from the given C string, returning a new C string with the prefix removed.
 /// If the given C string does not start with the given prefix, returns None.
-pub(crate) fn strip_prefix_cstr_raw(cstr: &'static CStr, prefix: &CStr) -> Option<&'static CStr> {
-    let cstr_bytes = cstr.to_bytes();
+pub(crate) fn strip_prefix_cstr_raw<'a>(cstr: &'a CStr, prefix: &CStr) -> Option<&'a CStr> {
+    let cstr_bytes = cstr.to_bytes_with_nul();
     let prefix_bytes = prefix.to_bytes();
 
     if cstr_bytes.starts_with(prefix_bytes) {
         // SAFETY: We are slicing `cstr_bytes` which is guaranteed to be a valid
-        // C string since it comes from a `&CStr`. We also check that it starts
-        // with `prefix_bytes`, and we only slice off `prefix_bytes`, so the rest
-        // remains a valid C string.
+        // C string including its nul terminator since it comes from a `&CStr`. We
+        // also check that it starts with `prefix_bytes`, and we only slice off
+        // `prefix_bytes`, so the rest remains a valid C string.
         unsafe {
             Some(CStr::from_bytes_with_nul_unchecked(
                 &cstr_bytes[prefix_bytes.len()..],
@@ -1,6 +1,12 @@
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr},
+    sync::OnceLock,
+};
+
 use crate::sys::{
     ecs_log_enable_colors, ecs_log_enable_timedelta, ecs_log_enable_timestamp, ecs_log_get_level,
-    ecs_log_set_level,
+    ecs_log_set_level, ecs_os_api, ecs_os_api_log_t,
 };
 
 /// Sets the logging level to the specified value.
@@ -55,3 +61,67 @@ pub fn enable_timedelta_logging(enabled: bool) {
         ecs_log_enable_timedelta(enabled);
     }
 }
+
+thread_local! {
+    /// messages logged by flecs while `capture_log` runs on this thread.
+    static CAPTURED_LOG: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// log function that was installed before the capture hook, messages that aren't captured go there.
+static FORWARD_LOG: OnceLock<ecs_os_api_log_t> = OnceLock::new();
+
+unsafe extern "C" fn capture_log_hook(
+    level: i32,
+    file: *const c_char,
+    line: i32,
+    msg: *const c_char,
+) {
+    // warnings and errors have a negative level
+    let captured = level < 0
+        && !msg.is_null()
+        && CAPTURED_LOG.with(|captured| {
+            let mut captured = captured.borrow_mut();
+            let Some(messages) = captured.as_mut() else {
+                return false;
+            };
+            messages.push(CStr::from_ptr(msg).to_string_lossy().into_owned());
+            true
+        });
+
+    if !captured {
+        if let Some(Some(forward)) = FORWARD_LOG.get() {
+            forward(level, file, line, msg);
+        }
+    }
+}
+
+/// Installs the hook that captures the log of `capture_log`, once per process.
+///
+/// The hook is put in front of the log function of the OS API, which is initialized by the
+/// first world, so an OS API set with `ecs_os_set_api` before that is kept. Call this before the
+/// world starts any threads, as flecs reads the log function without synchronization.
+pub(crate) fn init_log_capture() {
+    FORWARD_LOG.get_or_init(|| unsafe {
+        let forward = ecs_os_api.log_;
+        ecs_os_api.log_ = Some(capture_log_hook);
+        forward
+    });
+}
+
+/// Runs `func` and collects the warnings and errors flecs logs meanwhile on this thread, instead
+/// of printing them.
+///
+/// # Returns
+///
+/// The result of `func` and the captured messages, one per line.
+pub(crate) fn capture_log<R>(func: impl FnOnce() -> R) -> (R, String) {
+    init_log_capture();
+
+    let outer = CAPTURED_LOG.with(|captured| captured.replace(Some(Vec::new())));
+    let result = func();
+    let messages = CAPTURED_LOG
+        .with(|captured| captured.replace(outer))
+        .unwrap_or_default();
+
+    (result, messages.join("\n"))
+}
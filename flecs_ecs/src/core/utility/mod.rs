@@ -1,9 +1,11 @@
+mod borrow_tracker;
 mod errors;
 mod functions;
 mod log;
 pub mod traits;
 pub mod types;
 
+pub(crate) use borrow_tracker::*;
 pub use errors::*;
 pub use functions::*;
 pub use log::*;
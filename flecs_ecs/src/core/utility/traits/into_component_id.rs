@@ -1,4 +1,6 @@
-use crate::core::{ecs_pair, ComponentId, ComponentType, IdT, Struct};
+use crate::core::{
+    ecs_pair, ComponentId, ComponentType, ConditionalTypePairSelector, FlecsPairType, IdT, Struct,
+};
 
 use super::IntoWorld;
 
@@ -64,3 +66,38 @@ where
         std::any::type_name::<(T, U)>()
     }
 }
+
+/// A relationship pair `(First, Second)` that can be used as a typed term of a query.
+///
+/// The data of a pair is stored as the first element, unless the first element is a tag, in which
+/// case the data is stored as the second element. `Wildcard` and `Any` can be used as the second
+/// element to match any target, which is then available through [`Iter::pair`].
+///
+/// [`Iter::pair`]: crate::core::Iter::pair
+pub trait ComponentPair {
+    type First: ComponentId;
+    type Second: ComponentId;
+    /// The type of the data of the pair.
+    type CastType: ComponentId;
+
+    fn get_id(world: impl IntoWorld) -> IdT;
+}
+
+impl<T, U> ComponentPair for (T, U)
+where
+    T: ComponentId,
+    U: ComponentId,
+    ConditionalTypePairSelector<T::TagType, T, U>: FlecsPairType,
+{
+    type First = T;
+    type Second = U;
+    type CastType = <ConditionalTypePairSelector<T::TagType, T, U> as FlecsPairType>::Type;
+
+    #[inline]
+    fn get_id(world: impl IntoWorld) -> IdT {
+        ecs_pair(
+            T::get_id(world.world_ptr_mut()),
+            U::get_id(world.world_ptr_mut()),
+        )
+    }
+}
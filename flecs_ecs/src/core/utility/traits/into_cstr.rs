@@ -0,0 +1,253 @@
+use std::{
+    borrow::Cow,
+    ffi::{CStr, CString},
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Strings up to this length are made nul-terminated on the stack instead of the heap.
+const STACK_BUFFER_LEN: usize = 128;
+
+/// A string that can be passed to flecs as a name, path or expression.
+///
+/// Implemented for the standard string types like `&str`, `String` and `Cow<str>`, which are
+/// copied into a nul-terminated buffer on the stack when they are short enough, and for `&CStr`
+/// and `CString`, which are passed to flecs as is.
+///
+/// # Panics
+///
+/// Operations taking a name panic if a Rust string contains a nul byte, except for the `try_*`
+/// variants which return an error instead.
+pub trait IntoCStr {
+    /// Call `f` with the string as a C string.
+    ///
+    /// # Returns
+    ///
+    /// The result of `f`, or `None` if the string contains a nul byte.
+    #[doc(hidden)]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R>;
+
+    /// Copy the string into an owned C string, for flecs APIs that keep the pointer around.
+    ///
+    /// # Panics
+    ///
+    /// If the string contains a nul byte.
+    #[doc(hidden)]
+    fn to_cstring(&self) -> CString {
+        self.with_cstr(CStr::to_owned)
+    }
+
+    /// Call `f` with the string as a C string.
+    ///
+    /// # Panics
+    ///
+    /// If the string contains a nul byte.
+    #[doc(hidden)]
+    #[inline]
+    fn with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> R {
+        self.try_with_cstr(f)
+            .expect("string passed to flecs must not contain a nul byte")
+    }
+}
+
+#[inline]
+fn try_with_str_as_cstr<R>(s: &str, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+    let bytes = s.as_bytes();
+    if bytes.len() < STACK_BUFFER_LEN {
+        let mut buffer = [0u8; STACK_BUFFER_LEN];
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        CStr::from_bytes_with_nul(&buffer[..=bytes.len()])
+            .ok()
+            .map(f)
+    } else {
+        CString::new(s).ok().map(|s| f(&s))
+    }
+}
+
+impl IntoCStr for str {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        try_with_str_as_cstr(self, f)
+    }
+}
+
+impl IntoCStr for String {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        try_with_str_as_cstr(self, f)
+    }
+}
+
+impl IntoCStr for Cow<'_, str> {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        try_with_str_as_cstr(self, f)
+    }
+}
+
+impl IntoCStr for Box<str> {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        try_with_str_as_cstr(self, f)
+    }
+}
+
+impl IntoCStr for Rc<str> {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        try_with_str_as_cstr(self, f)
+    }
+}
+
+impl IntoCStr for Arc<str> {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        try_with_str_as_cstr(self, f)
+    }
+}
+
+impl IntoCStr for CStr {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        Some(f(self))
+    }
+}
+
+impl IntoCStr for CString {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        Some(f(self))
+    }
+
+    #[inline]
+    fn to_cstring(&self) -> CString {
+        self.clone()
+    }
+}
+
+impl IntoCStr for Cow<'_, CStr> {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        Some(f(self))
+    }
+}
+
+impl<T: IntoCStr + ?Sized> IntoCStr for &T {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        (**self).try_with_cstr(f)
+    }
+
+    #[inline]
+    fn to_cstring(&self) -> CString {
+        (**self).to_cstring()
+    }
+}
+
+impl<T: IntoCStr + ?Sized> IntoCStr for &mut T {
+    #[inline]
+    fn try_with_cstr<R>(&self, f: impl FnOnce(&CStr) -> R) -> Option<R> {
+        (**self).try_with_cstr(f)
+    }
+
+    #[inline]
+    fn to_cstring(&self) -> CString {
+        (**self).to_cstring()
+    }
+}
+
+/// A string that flecs keeps a pointer to, like the names and expressions of a query.
+///
+/// `&'static CStr` literals are passed to flecs without copying them, `CString` and `String` are
+/// moved, and borrowed Rust strings are copied into a new C string.
+///
+/// # Panics
+///
+/// If a Rust string contains a nul byte.
+pub trait IntoStaticCStr {
+    /// Convert the string into a C string that lives as long as it's needed.
+    #[doc(hidden)]
+    fn into_static_cstr(self) -> Cow<'static, CStr>;
+}
+
+#[inline]
+fn str_into_cstring(s: impl Into<Vec<u8>>) -> CString {
+    CString::new(s).expect("string passed to flecs must not contain a nul byte")
+}
+
+impl IntoStaticCStr for &'static CStr {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl IntoStaticCStr for CString {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        Cow::Owned(self)
+    }
+}
+
+impl IntoStaticCStr for &CString {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        Cow::Owned(self.clone())
+    }
+}
+
+impl IntoStaticCStr for Cow<'static, CStr> {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        self
+    }
+}
+
+impl IntoStaticCStr for &str {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        Cow::Owned(str_into_cstring(self))
+    }
+}
+
+impl IntoStaticCStr for String {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        Cow::Owned(str_into_cstring(self))
+    }
+}
+
+impl IntoStaticCStr for &String {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        Cow::Owned(str_into_cstring(self.as_str()))
+    }
+}
+
+impl IntoStaticCStr for Cow<'_, str> {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        Cow::Owned(str_into_cstring(self.into_owned()))
+    }
+}
+
+impl IntoStaticCStr for Box<str> {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        Cow::Owned(str_into_cstring(String::from(self)))
+    }
+}
+
+impl IntoStaticCStr for Rc<str> {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        Cow::Owned(str_into_cstring(&*self))
+    }
+}
+
+impl IntoStaticCStr for Arc<str> {
+    #[inline]
+    fn into_static_cstr(self) -> Cow<'static, CStr> {
+        Cow::Owned(str_into_cstring(&*self))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::{Entity, EntityView, Id, Iterable, Query, World, WorldT};
+use crate::core::{Entity, EntityView, Id, Iterable, Query, World, WorldRef, WorldT};
 
 pub trait IntoWorld {
     #[doc(hidden)]
@@ -8,8 +8,9 @@ pub trait IntoWorld {
     fn get_world_raw(&self) -> *const WorldT {
         self.world_ptr_mut() as *const WorldT
     }
+    /// Get a handle to the world, which keeps the world alive.
     #[inline]
-    fn get_world(&self) -> World {
+    fn get_world(&self) -> WorldRef {
         World::new_wrap_raw_world(self.world_ptr_mut())
     }
 }
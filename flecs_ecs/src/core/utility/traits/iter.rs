@@ -1,15 +1,46 @@
-use std::ffi::c_char;
+use std::{
+    ffi::c_char,
+    ops::{Deref, DerefMut},
+};
 
 #[cfg(any(debug_assertions, feature = "flecs_force_enable_ecs_asserts"))]
 use crate::core::FlecsErrorCode;
 use crate::{
-    core::{Entity, FilterT, Iter, IterIterable, IterT, Iterable, Term},
+    core::{
+        Entity, EntityIter, FilterT, Iter, IterBorrows, IterCursor, IterIterable, IterT, Iterable,
+        TableIter, TableLock, Term,
+    },
     ecs_assert,
 };
-use flecs_ecs_sys::{ecs_filter_str, ecs_iter_fini, ecs_os_api, ecs_table_lock, ecs_table_unlock};
+use flecs_ecs_sys::{ecs_filter_str, ecs_iter_fini, ecs_os_api, EcsIterIsValid};
 
 use super::IntoWorld;
 
+/// Iterator that is finalized when dropped before it was exhausted, e.g. when a callback panics.
+pub(crate) struct IterGuard(pub(crate) IterT);
+
+impl Deref for IterGuard {
+    type Target = IterT;
+
+    fn deref(&self) -> &IterT {
+        &self.0
+    }
+}
+
+impl DerefMut for IterGuard {
+    fn deref_mut(&mut self) -> &mut IterT {
+        &mut self.0
+    }
+}
+
+impl Drop for IterGuard {
+    fn drop(&mut self) {
+        if self.0.flags & EcsIterIsValid != 0 {
+            unsafe { ecs_iter_fini(&mut self.0) };
+        }
+    }
+}
+
 pub trait IterOperations {
     #[doc(hidden)]
     fn retrieve_iter(&self) -> IterT;
@@ -44,27 +75,24 @@ where
     /// * C++ API: `iterable::each`
     #[doc(alias = "iterable::each")]
     fn each(&self, mut func: impl FnMut(T::TupleType)) {
-        unsafe {
-            let mut iter = self.retrieve_iter();
+        let mut iter = IterGuard(self.retrieve_iter());
 
-            while self.iter_next(&mut iter) {
-                let components_data = T::create_array_ptrs_of_components(&iter);
-                let iter_count = iter.count as usize;
-                let array_components = &components_data.array_components;
+        while self.iter_next(&mut iter) {
+            let components_data = T::create_array_ptrs_of_components(&iter);
+            let iter_count = iter.count as usize;
+            let array_components = &components_data.array_components;
 
-                ecs_table_lock(self.world_ptr_mut(), iter.table);
-
-                for i in 0..iter_count {
-                    let tuple = if components_data.is_any_array_a_ref {
-                        let is_ref_array_components = &components_data.is_ref_array_components;
-                        T::create_tuple_with_ref(array_components, is_ref_array_components, i)
-                    } else {
-                        T::create_tuple(array_components, i)
-                    };
-                    func(tuple);
-                }
+            let _borrows = IterBorrows::new(&iter);
+            let _lock = TableLock::new(self.world_ptr_mut(), iter.table);
 
-                ecs_table_unlock(self.world_ptr_mut(), iter.table);
+            for i in 0..iter_count {
+                let tuple = if components_data.is_any_array_a_ref {
+                    let is_ref_array_components = &components_data.is_ref_array_components;
+                    T::create_tuple_with_ref(array_components, is_ref_array_components, i)
+                } else {
+                    T::create_tuple(array_components, i)
+                };
+                func(tuple);
             }
         }
     }
@@ -82,7 +110,7 @@ where
     #[doc(alias = "iterable::each")]
     fn each_entity(&self, mut func: impl FnMut(&mut Entity, T::TupleType)) {
         unsafe {
-            let mut iter = self.retrieve_iter();
+            let mut iter = IterGuard(self.retrieve_iter());
             let world = self.world_ptr_mut();
             while self.iter_next(&mut iter) {
                 let components_data = T::create_array_ptrs_of_components(&iter);
@@ -95,7 +123,8 @@ where
                     }
                 };
 
-                ecs_table_lock(world, iter.table);
+                let _borrows = IterBorrows::new(&iter);
+                let _lock = TableLock::new(world, iter.table);
 
                 // TODO random thought, I think I can determine the elements is a ref or not before the for loop and then pass two arrays with the indices of the ref and non ref elements
                 // I will come back to this in the future, my thoughts are somewhere else right now. If my assumption is correct, this will get rid of the branch in the for loop
@@ -114,15 +143,13 @@ where
 
                     func(&mut entity, tuple);
                 }
-
-                ecs_table_unlock(world, iter.table);
             }
         }
     }
 
     fn each_iter(&self, mut func: impl FnMut(&mut Iter, usize, T::TupleType)) {
         unsafe {
-            let mut iter = self.retrieve_iter();
+            let mut iter = IterGuard(self.retrieve_iter());
             let world = self.world_ptr_mut();
 
             while self.iter_next(&mut iter) {
@@ -136,7 +163,8 @@ where
                 };
                 let array_components = &components_data.array_components;
 
-                ecs_table_lock(world, iter.table);
+                let _borrows = IterBorrows::new(&iter);
+                let _lock = TableLock::new(world, iter.table);
 
                 let mut iter_t = Iter::new(&mut iter);
 
@@ -149,8 +177,6 @@ where
                     };
                     func(&mut iter_t, i, tuple);
                 }
-
-                ecs_table_unlock(world, iter.table);
             }
         }
     }
@@ -173,7 +199,7 @@ where
     #[doc(alias = "find_delegate::invoke_callback")]
     fn find(&self, mut func: impl FnMut(T::TupleType) -> bool) -> Option<Entity> {
         unsafe {
-            let mut iter = self.retrieve_iter();
+            let mut iter = IterGuard(self.retrieve_iter());
             let mut entity: Option<Entity> = None;
             let world = self.world_ptr_mut();
 
@@ -182,7 +208,8 @@ where
                 let iter_count = iter.count as usize;
                 let array_components = &components_data.array_components;
 
-                ecs_table_lock(world, iter.table);
+                let _borrows = IterBorrows::new(&iter);
+                let _lock = TableLock::new(world, iter.table);
 
                 for i in 0..iter_count {
                     let tuple = if components_data.is_any_array_a_ref {
@@ -199,8 +226,6 @@ where
                         break;
                     }
                 }
-
-                ecs_table_unlock(world, iter.table);
             }
             entity
         }
@@ -227,7 +252,7 @@ where
         mut func: impl FnMut(&mut Entity, T::TupleType) -> bool,
     ) -> Option<Entity> {
         unsafe {
-            let mut iter = self.retrieve_iter();
+            let mut iter = IterGuard(self.retrieve_iter());
             let mut entity_result: Option<Entity> = None;
             let world = self.world_ptr_mut();
 
@@ -236,7 +261,8 @@ where
                 let iter_count = iter.count as usize;
                 let array_components = &components_data.array_components;
 
-                ecs_table_lock(world, iter.table);
+                let _borrows = IterBorrows::new(&iter);
+                let _lock = TableLock::new(world, iter.table);
 
                 for i in 0..iter_count {
                     let mut entity =
@@ -253,8 +279,6 @@ where
                         break;
                     }
                 }
-
-                ecs_table_unlock(world, iter.table);
             }
             entity_result
         }
@@ -281,7 +305,7 @@ where
         mut func: impl FnMut(&mut Iter, usize, T::TupleType) -> bool,
     ) -> Option<Entity> {
         unsafe {
-            let mut iter = self.retrieve_iter();
+            let mut iter = IterGuard(self.retrieve_iter());
             let mut entity_result: Option<Entity> = None;
             let world = self.world_ptr_mut();
 
@@ -296,7 +320,8 @@ where
                     }
                 };
 
-                ecs_table_lock(world, iter.table);
+                let _borrows = IterBorrows::new(&iter);
+                let _lock = TableLock::new(world, iter.table);
                 let mut iter_t = Iter::new(&mut iter);
 
                 for i in 0..iter_count {
@@ -314,8 +339,6 @@ where
                         break;
                     }
                 }
-
-                ecs_table_unlock(world, iter.table);
             }
             entity_result
         }
@@ -337,7 +360,7 @@ where
     #[doc(alias = "iterable::iter")]
     fn iter(&self, mut func: impl FnMut(&mut Iter, T::TupleSliceType)) {
         unsafe {
-            let mut iter = self.retrieve_iter();
+            let mut iter = IterGuard(self.retrieve_iter());
             let world = self.world_ptr_mut();
 
             while self.iter_next(&mut iter) {
@@ -345,7 +368,8 @@ where
                 let iter_count = iter.count as usize;
                 let array_components = &components_data.array_components;
 
-                ecs_table_lock(world, iter.table);
+                let _borrows = IterBorrows::new(&iter);
+                let _lock = TableLock::new(world, iter.table);
 
                 let tuple = if components_data.is_any_array_a_ref {
                     let is_ref_array_components = &components_data.is_ref_array_components;
@@ -359,7 +383,6 @@ where
                 };
                 let mut iter_t = Iter::new(&mut iter);
                 func(&mut iter_t, tuple);
-                ecs_table_unlock(world, iter.table);
             }
         }
     }
@@ -380,13 +403,13 @@ where
     #[doc(alias = "iterable::iter")]
     fn iter_only(&self, mut func: impl FnMut(&mut Iter)) {
         unsafe {
-            let mut iter = self.retrieve_iter();
+            let mut iter = IterGuard(self.retrieve_iter());
             let world = self.world_ptr_mut();
             while self.iter_next(&mut iter) {
-                ecs_table_lock(world, iter.table);
+                let _borrows = IterBorrows::new(&iter);
+                let _lock = TableLock::new(world, iter.table);
                 let mut iter_t = Iter::new(&mut iter);
                 func(&mut iter_t);
-                ecs_table_unlock(world, iter.table);
             }
         }
     }
@@ -503,6 +526,146 @@ where
         rust_string
     }
 
+    /// Serialize the results of the iterable to JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc` - The options that determine what is serialized.
+    ///
+    /// # Returns
+    ///
+    /// The JSON string, or an error if a result could not be serialized.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `iterable::to_json`
+    #[doc(alias = "iterable::to_json")]
+    #[cfg(feature = "flecs_json")]
+    fn to_json(
+        &self,
+        desc: &crate::addons::json::IterToJsonDesc,
+    ) -> Result<String, crate::addons::json::JsonError> {
+        let world = self.world_ptr_mut();
+        let mut iter = self.retrieve_iter();
+        unsafe {
+            crate::addons::json::json_from_raw(crate::sys::ecs_iter_to_json(
+                world, &mut iter, &desc.desc,
+            ))
+        }
+    }
+
+    /// Iterate the matching entities with a Rust iterator.
+    ///
+    /// This is similar to `each_entity`, but passes an iterator to `func`, which can be combined
+    /// with iterator adapters or left early. The components can't outlive `func`, and the tables of
+    /// the entities returned by the iterator stay locked until `func` returns.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function that consumes the iterator
+    ///
+    /// # Returns
+    ///
+    /// The value returned by `func`
+    fn iter_entities<R>(&mut self, func: impl for<'i> FnOnce(EntityIter<'i, 'a, T>) -> R) -> R {
+        let mut cursor = IterCursor::new(
+            self.world_ptr_mut(),
+            self.retrieve_iter(),
+            self.iter_next_func(),
+        );
+        func(EntityIter::new(&mut cursor))
+    }
+
+    /// Iterate the matching tables with a Rust iterator.
+    ///
+    /// This is similar to `iter`, but passes an iterator over views of the tables to `func`, each
+    /// with the slices of its components. The views can't outlive `func`, and the tables stay
+    /// locked until `func` returns.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function that consumes the iterator
+    ///
+    /// # Returns
+    ///
+    /// The value returned by `func`
+    fn iter_tables<R>(&mut self, func: impl for<'i> FnOnce(TableIter<'i, 'a, T>) -> R) -> R {
+        let mut cursor = IterCursor::new(
+            self.world_ptr_mut(),
+            self.retrieve_iter(),
+            self.iter_next_func(),
+        );
+        func(TableIter::new(&mut cursor))
+    }
+
+    /// Parallel each iterator.
+    ///
+    /// This is similar to `each`, but calls `func` from the threads of the current rayon thread
+    /// pool, which is the global pool unless called from a thread of another pool. Matched tables
+    /// are split into ranges of at most `PAR_CHUNK_SIZE` entities, and are locked until all
+    /// entities are processed.
+    ///
+    /// # Panics
+    ///
+    /// If the query writes to a component that is shared between entities, like a singleton, or
+    /// to a component of entities it matches more than once, like queries with wildcard terms.
+    #[cfg(feature = "flecs_rayon")]
+    fn par_each(&self, func: impl Fn(T::TupleType) + Sync)
+    where
+        T::TupleType: Send,
+    {
+        crate::core::par_iter::par_each(self, None, func);
+    }
+
+    /// Parallel each iterator.
+    ///
+    /// This is similar to `par_each`, but calls `func` from the threads of `pool`.
+    ///
+    /// # Panics
+    ///
+    /// If the query writes to a component that is shared between entities, like a singleton, or
+    /// to a component of entities it matches more than once, like queries with wildcard terms.
+    #[cfg(feature = "flecs_rayon")]
+    fn par_each_in(&self, pool: &rayon::ThreadPool, func: impl Fn(T::TupleType) + Sync)
+    where
+        T::TupleType: Send,
+    {
+        crate::core::par_iter::par_each(self, Some(pool), func);
+    }
+
+    /// Parallel iter iterator.
+    ///
+    /// This is similar to `par_each`, but calls `func` with the slices of the components of a
+    /// range of at most `PAR_CHUNK_SIZE` entities of a table.
+    ///
+    /// # Panics
+    ///
+    /// If the query writes to a component that is shared between entities, like a singleton, or
+    /// to a component of entities it matches more than once, like queries with wildcard terms.
+    #[cfg(feature = "flecs_rayon")]
+    fn par_iter(&self, func: impl Fn(T::TupleSliceType) + Sync)
+    where
+        T::TupleSliceType: Send,
+    {
+        crate::core::par_iter::par_iter(self, None, func);
+    }
+
+    /// Parallel iter iterator.
+    ///
+    /// This is similar to `par_iter`, but calls `func` from the threads of `pool`.
+    ///
+    /// # Panics
+    ///
+    /// If the query writes to a component that is shared between entities, like a singleton, or
+    /// to a component of entities it matches more than once, like queries with wildcard terms.
+    #[cfg(feature = "flecs_rayon")]
+    fn par_iter_in(&self, pool: &rayon::ThreadPool, func: impl Fn(T::TupleSliceType) + Sync)
+    where
+        T::TupleSliceType: Send,
+    {
+        crate::core::par_iter::par_iter(self, Some(pool), func);
+    }
+
     fn iterable(&self) -> IterIterable<'a, T> {
         IterIterable::new(self.retrieve_iter(), self.iter_next_func())
     }
@@ -1,5 +1,6 @@
 mod inout_oper;
 mod into_component_id;
+mod into_cstr;
 mod into_entity;
 mod into_table;
 mod into_world;
@@ -8,6 +9,7 @@ mod reactor;
 
 pub use inout_oper::*;
 pub use into_component_id::*;
+pub use into_cstr::*;
 pub use into_entity::*;
 pub use into_table::*;
 pub use into_world::*;
@@ -20,9 +22,11 @@ use super::{ImplementsClone, ImplementsDefault};
 pub mod private {
     use std::{ffi::c_void, ptr};
 
-    use flecs_ecs_sys::{ecs_ctx_free_t, ecs_iter_t, ecs_table_lock, ecs_table_unlock};
+    use flecs_ecs_sys::{ecs_ctx_free_t, ecs_iter_t};
 
-    use crate::core::{Entity, Iter, IterT, Iterable, ObserverSystemBindingCtx};
+    use crate::core::{
+        Entity, Iter, IterBorrows, IterT, Iterable, ObserverSystemBindingCtx, TableLock,
+    };
 
     #[allow(non_camel_case_types)]
     #[doc(hidden)]
@@ -65,7 +69,8 @@ pub mod private {
                 }
             };
 
-            ecs_table_lock((*iter).world, (*iter).table);
+            let _borrows = IterBorrows::new(&*iter);
+            let _lock = TableLock::new((*iter).world, (*iter).table);
 
             for i in 0..iter_count {
                 let tuple = if components_data.is_any_array_a_ref {
@@ -76,8 +81,6 @@ pub mod private {
                 };
                 each(tuple);
             }
-
-            ecs_table_unlock((*iter).world, (*iter).table);
         }
 
         /// Callback of the `each_entity` functionality
@@ -108,7 +111,8 @@ pub mod private {
                 }
             };
 
-            ecs_table_lock((*iter).world, (*iter).table);
+            let _borrows = IterBorrows::new(&*iter);
+            let _lock = TableLock::new((*iter).world, (*iter).table);
 
             for i in 0..iter_count {
                 let mut entity =
@@ -122,7 +126,6 @@ pub mod private {
 
                 each_entity(&mut entity, tuple);
             }
-            ecs_table_unlock((*iter).world, (*iter).table);
         }
 
         /// Callback of the `each_iter` functionality
@@ -153,7 +156,8 @@ pub mod private {
                 }
             };
 
-            ecs_table_lock((*iter).world, (*iter).table);
+            let _borrows = IterBorrows::new(&*iter);
+            let _lock = TableLock::new((*iter).world, (*iter).table);
             let mut iter_t = Iter::new(&mut (*iter));
 
             for i in 0..iter_count {
@@ -166,7 +170,6 @@ pub mod private {
 
                 each_iter(&mut iter_t, i, tuple);
             }
-            ecs_table_unlock((*iter).world, (*iter).table);
         }
 
         /// Callback of the `iter_only` functionality
@@ -195,14 +198,13 @@ pub mod private {
                     }
                 };
 
-                ecs_table_lock((*iter).world, (*iter).table);
+                let _borrows = IterBorrows::new(&*iter);
+                let _lock = TableLock::new((*iter).world, (*iter).table);
 
                 for _ in 0..iter_count {
                     let mut iter_t = Iter::new(&mut *iter);
                     iter_only(&mut iter_t);
                 }
-
-                ecs_table_unlock((*iter).world, (*iter).table);
             }
         }
 
@@ -234,7 +236,8 @@ pub mod private {
                 }
             };
 
-            ecs_table_lock((*iter).world, (*iter).table);
+            let _borrows = IterBorrows::new(&*iter);
+            let _lock = TableLock::new((*iter).world, (*iter).table);
 
             let tuple = if components_data.is_any_array_a_ref {
                 let is_ref_array_components = &components_data.is_ref_array_components;
@@ -248,7 +251,6 @@ pub mod private {
             };
             let mut iter_t = Iter::new(&mut *iter);
             iter_func(&mut iter_t, tuple);
-            ecs_table_unlock((*iter).world, (*iter).table);
         }
 
         // free functions
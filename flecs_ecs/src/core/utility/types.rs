@@ -1,6 +1,6 @@
-use std::{ops::Deref, os::raw::c_void};
+use std::{any::TypeId, ops::Deref, os::raw::c_void};
 
-use crate::core::{Entity, IdT, World};
+use crate::core::{Entity, IdT, OrderByClosure, World};
 
 pub type FTime = f32;
 
@@ -224,5 +224,13 @@ impl ObserverEntityBindingCtx {
     }
 }
 
+#[derive(Default)]
+pub(crate) struct QueryBindingCtx {
+    /// The closure passed to `order_by`.
+    pub(crate) order_by: Option<Box<OrderByClosure>>,
+    /// The type of the group contexts created by the `on_group_create` closure.
+    pub(crate) group_context: Option<TypeId>,
+}
+
 pub struct ImplementsClone<T>(std::marker::PhantomData<T>);
 pub struct ImplementsDefault<T>(std::marker::PhantomData<T>);
@@ -1,6 +1,11 @@
 //! World operations.
 
-use std::{ffi::CStr, ops::Deref, os::raw::c_void};
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    os::raw::c_void,
+    sync::{Arc, RwLock, Weak},
+};
 
 #[cfg(feature = "flecs_app")]
 use crate::addons::app::App;
@@ -12,34 +17,47 @@ use crate::{
 };
 
 #[cfg(feature = "flecs_pipeline")]
-use crate::{addons::pipeline::PipelineBuilder, sys};
+use crate::{addons::pipeline::PipelineBuilder, core::c_types::ECS_PIPELINE, sys};
+
+#[cfg(feature = "flecs_snapshot")]
+use crate::{addons::snapshot::Snapshot, core::IterOperations};
+
+#[cfg(feature = "flecs_timer")]
+use crate::addons::timer::Timer;
+
+#[cfg(feature = "flecs_module")]
+use crate::{addons::module::Module, core::c_types::ECS_MODULE, sys::ecs_has_id};
 
-use crate::ecs_assert;
 use crate::sys::{
-    ecs_async_stage_free, ecs_async_stage_new, ecs_atfini, ecs_count_id, ecs_ctx_free_t,
-    ecs_defer_begin, ecs_defer_end, ecs_defer_resume, ecs_defer_suspend, ecs_delete_with, ecs_dim,
-    ecs_enable_range_check, ecs_ensure, ecs_exists, ecs_fini, ecs_fini_action_t, ecs_frame_begin,
-    ecs_frame_end, ecs_get_alive, ecs_get_ctx, ecs_get_id, ecs_get_mut_id, ecs_get_name,
-    ecs_get_scope, ecs_get_stage, ecs_get_stage_count, ecs_get_stage_id, ecs_get_target,
-    ecs_get_world, ecs_get_world_info, ecs_init, ecs_is_alive, ecs_is_deferred, ecs_is_valid,
+    ecs_async_stage_new, ecs_atfini, ecs_count_id, ecs_ctx_free_t, ecs_defer_begin, ecs_defer_end,
+    ecs_defer_resume, ecs_defer_suspend, ecs_delete_with, ecs_dim, ecs_enable_range_check,
+    ecs_ensure, ecs_exists, ecs_fini, ecs_fini_action_t, ecs_frame_begin, ecs_frame_end,
+    ecs_get_alive, ecs_get_ctx, ecs_get_id, ecs_get_mut_id, ecs_get_name, ecs_get_scope,
+    ecs_get_stage, ecs_get_stage_count, ecs_get_stage_id, ecs_get_target, ecs_get_world,
+    ecs_get_world_info, ecs_init, ecs_is_alive, ecs_is_deferred, ecs_is_valid,
     ecs_lookup_path_w_sep, ecs_merge, ecs_poly_is_, ecs_quit, ecs_readonly_begin, ecs_readonly_end,
     ecs_remove_all, ecs_run_post_frame, ecs_set_alias, ecs_set_automerge, ecs_set_ctx,
     ecs_set_entity_range, ecs_set_lookup_path, ecs_set_scope, ecs_set_stage_count, ecs_set_with,
-    ecs_should_quit, ecs_stage_is_async, ecs_stage_is_readonly, ecs_stage_t_magic,
-    ecs_world_info_t,
+    ecs_should_quit, ecs_stage_is_readonly, ecs_stage_t_magic, ecs_world_info_t,
 };
 #[cfg(any(debug_assertions, feature = "flecs_force_enable_ecs_asserts"))]
+use crate::{core::ecs_is_pair, sys::ecs_world_t_magic};
 use crate::{
-    core::{ecs_is_pair, FlecsErrorCode},
-    sys::ecs_world_t_magic,
+    core::{capture_log, init_log_capture, FlecsError, FlecsErrorCode},
+    ecs_assert,
 };
 
 use super::{
+    bundle::{despawn_batch, spawn_bundle, spawn_bundle_batch, Bundle},
     c_types::{EntityT, IdT, WorldT, SEPARATOR},
     component::{Component, UntypedComponent},
     component_ref::Ref,
-    component_registration::{ComponentId, ComponentType, Enum, Struct},
-    flecs, FlecsConstantId, IntoComponentId, IntoEntityId, IntoEntityIdExt, IterAPI, ECS_PREFAB,
+    component_registration::{
+        init_component_registry, remove_component_registry, ComponentId, ComponentType, Enum,
+        Struct,
+    },
+    flecs, FlecsConstantId, IntoCStr, IntoComponentId, IntoEntityId, IntoEntityIdExt, IterAPI,
+    ECS_PREFAB,
 };
 use super::{EmptyComponent, NotEmptyComponent};
 
@@ -53,30 +71,90 @@ use super::{
     observer_builder::ObserverBuilder,
     scoped_world::ScopedWorld,
     set_helper,
+    stage::Stage,
     term::Term,
+    world_ref::WorldRef,
     Builder, CachedEnumData, Filter, FilterBuilder, Query, QueryBuilder,
 };
 
-pub struct World {
-    pub raw_world: *mut WorldT,
-    pub is_owned: bool,
+/// Owns a flecs world and destroys it when the last strong [`World`] handle is dropped.
+pub(crate) struct WorldOwner {
+    raw_world: *mut WorldT,
+    registry_key: u64,
 }
 
-impl Clone for World {
-    fn clone(&self) -> Self {
-        World {
-            raw_world: self.raw_world,
-            // Set is_owned to false to prevent double free, meaning that the new world is not owned.
-            is_owned: false,
+// SAFETY: the owner only destroys the world, which happens once no handle can access it anymore.
+unsafe impl Send for WorldOwner {}
+unsafe impl Sync for WorldOwner {}
+
+/// the owners of the worlds created by [`World::new`] that are alive, by world pointer.
+static WORLD_OWNERS: RwLock<Option<HashMap<usize, Weak<WorldOwner>>>> = RwLock::new(None);
+
+impl WorldOwner {
+    fn new(raw_world: *mut WorldT) -> Arc<Self> {
+        let owner = Arc::new(Self {
+            raw_world,
+            registry_key: init_component_registry(raw_world),
+        });
+        WORLD_OWNERS
+            .write()
+            .unwrap()
+            .get_or_insert_with(HashMap::new)
+            .insert(raw_world as usize, Arc::downgrade(&owner));
+        owner
+    }
+
+    /// returns the owner of the world the world or stage pointer belongs to, if it is alive.
+    fn find(world: *mut WorldT) -> Option<Arc<Self>> {
+        if world.is_null() {
+            return None;
         }
+        let real_world = unsafe { ecs_get_world(world as *const c_void) };
+        WORLD_OWNERS
+            .read()
+            .unwrap()
+            .as_ref()?
+            .get(&(real_world as usize))?
+            .upgrade()
+    }
+}
+
+impl Drop for WorldOwner {
+    fn drop(&mut self) {
+        // remove the owner while the world pointer can't be reused by another world
+        if let Some(owners) = WORLD_OWNERS.write().unwrap().as_mut() {
+            owners.remove(&(self.raw_world as usize));
+        }
+        unsafe { ecs_fini(self.raw_world) };
+        remove_component_registry(self.raw_world, Some(self.registry_key));
     }
 }
 
+/// Handle to a flecs world.
+///
+/// A world created with [`World::new`] owns the underlying flecs world. The world is destroyed
+/// when the `World` and all [`WorldRef`] handles to it are dropped. Handles to the world that are
+/// obtained from entities, ids or iterators are [`WorldRef`]s as well, so they can't outlive the
+/// world either.
+///
+/// # Threading
+///
+/// Entity handles and [`WorldRef`]s are not tied to the lifetime of the `World`, so the world
+/// can't be sent to another thread while they are used. To let multiple threads work on a world
+/// at once, put the world in readonly mode with [`World::readonly_begin`] and hand every thread its
+/// own [`Stage`] from [`World::stages`], which records operations to be merged afterwards.
+pub struct World {
+    pub raw_world: *mut WorldT,
+    pub(crate) owner: Option<Arc<WorldOwner>>,
+}
+
 impl Default for World {
     fn default() -> Self {
+        let raw_world = unsafe { ecs_init() };
+        init_log_capture();
         let world = Self {
-            raw_world: unsafe { ecs_init() },
-            is_owned: true,
+            raw_world,
+            owner: Some(WorldOwner::new(raw_world)),
         };
 
         world.init_builtin_components();
@@ -92,29 +170,51 @@ impl Deref for World {
     }
 }
 
-impl Drop for World {
-    fn drop(&mut self) {
-        if self.is_owned && unsafe { ecs_stage_is_async(self.raw_world) } {
-            unsafe { ecs_async_stage_free(self.raw_world) }
-        } else if self.is_owned && !self.raw_world.is_null() {
-            unsafe { ecs_fini(self.raw_world) };
-        }
-    }
-}
-
 impl World {
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Wrapper around raw world, takes no ownership.
-    pub fn new_wrap_raw_world(world: *mut WorldT) -> Self {
+    /// Get a handle to a world from a raw world or stage pointer.
+    ///
+    /// The handle keeps the world alive if it was created by [`World::new`]. Worlds created
+    /// through the C API are not owned by the handle, and must outlive it.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world or stage pointer.
+    pub fn new_wrap_raw_world(world: *mut WorldT) -> WorldRef {
+        WorldRef::new(world, WorldOwner::find(world))
+    }
+
+    /// Wrap a raw world or stage pointer without taking ownership.
+    ///
+    /// The handle must not outlive the world, only use it as temporary.
+    pub(crate) fn new_unowned(world: *mut WorldT) -> Self {
         Self {
             raw_world: world,
-            is_owned: false,
+            owner: None,
+        }
+    }
+
+    /// Get a handle that shares this world, to store in objects that need to keep the world alive.
+    pub(crate) fn handle(&self) -> WorldRef {
+        match &self.owner {
+            Some(owner) => WorldRef::new(self.raw_world, Some(owner.clone())),
+            None => Self::new_wrap_raw_world(self.raw_world),
         }
     }
 
+    /// Test whether this handle keeps the world alive.
+    ///
+    /// # Returns
+    ///
+    /// True if the handle was created by [`World::new`], false if it wraps a world that was created
+    /// through the C API.
+    pub fn is_owned(&self) -> bool {
+        self.owner.is_some()
+    }
+
     fn init_builtin_components(&self) {
         #[cfg(feature = "flecs_system")]
         System::system_init(self);
@@ -130,6 +230,9 @@ impl World {
 
     /// deletes and recreates the world
     ///
+    /// The old world is deleted once no other strong handle refers to it, this handle is
+    /// pointed at a newly created world.
+    ///
     /// # See also
     ///
     /// * C++ API: `world::reset`
@@ -137,12 +240,12 @@ impl World {
     pub fn reset(&mut self) {
         // can only reset the world if we own the world object.
         ecs_assert!(
-            self.is_owned,
+            self.is_owned(),
             FlecsErrorCode::InvalidOperation,
             "Cannot reset a borrowed world"
         );
-        unsafe { ecs_fini(self.raw_world) };
-        self.raw_world = unsafe { ecs_init() };
+        // other strong handles keep the old world alive until they are dropped
+        *self = Self::new();
     }
 
     /// obtain pointer to C world object
@@ -524,11 +627,30 @@ impl World {
     ///
     /// * C++ API: `world::get_stage`
     #[doc(alias = "world::get_stage")]
-    pub fn stage(&self, stage_id: i32) -> Self {
-        Self {
-            raw_world: unsafe { ecs_get_stage(self.raw_world, stage_id) },
-            is_owned: false,
-        }
+    pub fn stage(&mut self, stage_id: i32) -> Stage<'_> {
+        unsafe { Stage::new(ecs_get_stage(self.raw_world, stage_id)) }
+    }
+
+    /// Get every stage of the world.
+    ///
+    /// Each stage is returned once, so the stages can be handed to different threads. Use
+    /// [`World::set_stage_count`] to set the number of stages, and put the world in readonly mode
+    /// with [`World::readonly_begin`] before using them.
+    ///
+    /// # Returns
+    ///
+    /// The stages, ordered by stage id.
+    ///
+    /// # See also
+    ///
+    /// * [`World::stage`]
+    /// * C++ API: `world::get_stage`
+    #[doc(alias = "world::get_stage")]
+    pub fn stages(&mut self) -> Vec<Stage<'_>> {
+        let count = unsafe { ecs_get_stage_count(self.raw_world) };
+        (0..count)
+            .map(|stage_id| unsafe { Stage::new(ecs_get_stage(self.raw_world, stage_id)) })
+            .collect()
     }
 
     /// Create asynchronous stage.
@@ -545,7 +667,10 @@ impl World {
     /// The application must ensure that no commands are added to the stage while the
     /// stage is being merged.
     ///
-    /// An asynchronous stage must be cleaned up by `ecs_async_stage_free`.
+    /// The asynchronous stage is cleaned up when the returned [`Stage`] is dropped, which must
+    /// happen after its commands were merged. The stage borrows the world mutably, so the world
+    /// can't be used while the stage is alive. Components must be registered with the world
+    /// before they are used through the stage.
     ///
     /// # Returns
     ///
@@ -555,11 +680,8 @@ impl World {
     ///
     /// * C++ API: `world::async_stage`
     #[doc(alias = "world::async_stage")]
-    pub fn create_async_stage(&self) -> Self {
-        Self {
-            raw_world: unsafe { ecs_async_stage_new(self.raw_world) },
-            is_owned: true,
-        }
+    pub fn create_async_stage(&mut self) -> Stage<'_> {
+        unsafe { Stage::new(ecs_async_stage_new(self.raw_world)) }
     }
 
     /// Get actual world.
@@ -575,15 +697,16 @@ impl World {
     ///
     /// * C++ API: `world::get_world`
     #[doc(alias = "world::get_world")]
-    pub fn get_world(&self) -> Self {
-        Self {
-            raw_world: if !self.raw_world.is_null() {
-                unsafe { ecs_get_world(self.raw_world as *const c_void) as *mut WorldT }
-            } else {
-                std::ptr::null_mut()
-            },
-            is_owned: false,
-        }
+    pub fn get_world(&self) -> WorldRef {
+        let world = if !self.raw_world.is_null() {
+            unsafe { ecs_get_world(self.raw_world as *const c_void) as *mut WorldT }
+        } else {
+            std::ptr::null_mut()
+        };
+        WorldRef::new(
+            world,
+            self.owner.clone().or_else(|| WorldOwner::find(world)),
+        )
     }
 
     /// Test whether the current world object is readonly.
@@ -841,8 +964,8 @@ impl World {
     ///
     /// * C++ API: `world::lookup`
     #[doc(alias = "world::lookup")]
-    pub fn lookup_name(&self, name: &CStr, search_path: bool) -> Entity {
-        let entity_id = unsafe {
+    pub fn lookup_name(&self, name: impl IntoCStr, search_path: bool) -> Entity {
+        let entity_id = name.with_cstr(|name| unsafe {
             ecs_lookup_path_w_sep(
                 self.raw_world,
                 0,
@@ -851,11 +974,61 @@ impl World {
                 SEPARATOR.as_ptr(),
                 search_path,
             )
-        };
+        });
 
         Entity::new_from_existing_raw(self.raw_world, entity_id)
     }
 
+    /// Lookup entity by name, returning an error if no entity has the name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the entity to lookup.
+    /// * `search_path` - When false, only the current scope is searched.
+    ///
+    /// # Returns
+    ///
+    /// The entity, or an error if it was not found.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::lookup`
+    #[doc(alias = "world::lookup")]
+    pub fn try_lookup_name(
+        &self,
+        name: impl IntoCStr,
+        search_path: bool,
+    ) -> Result<Entity, FlecsError> {
+        let Some((entity_id, log, name)) = name.try_with_cstr(|name| {
+            let (entity_id, log) = capture_log(|| unsafe {
+                ecs_lookup_path_w_sep(
+                    self.raw_world,
+                    0,
+                    name.as_ptr(),
+                    SEPARATOR.as_ptr(),
+                    SEPARATOR.as_ptr(),
+                    search_path,
+                )
+            });
+            (entity_id, log, name.to_string_lossy().into_owned())
+        }) else {
+            return Err(FlecsError::new(
+                FlecsErrorCode::InvalidParameter,
+                "entity name contains a nul byte",
+            ));
+        };
+
+        if entity_id == 0 {
+            Err(FlecsError::new(
+                FlecsErrorCode::InvalidParameter,
+                format!("no entity named `{}`", name),
+            )
+            .with_log(log))
+        } else {
+            Ok(Entity::new_from_existing_raw(self.raw_world, entity_id))
+        }
+    }
+
     /// Lookup entity by name
     ///
     /// # Arguments
@@ -871,8 +1044,12 @@ impl World {
     ///
     /// * C++ API: `world::lookup`
     #[doc(alias = "world::lookup")]
-    pub fn lookup_name_optional_optional(&self, name: &CStr, search_path: bool) -> Option<Entity> {
-        let entity_id = unsafe {
+    pub fn lookup_name_optional_optional(
+        &self,
+        name: impl IntoCStr,
+        search_path: bool,
+    ) -> Option<Entity> {
+        let entity_id = name.with_cstr(|name| unsafe {
             ecs_lookup_path_w_sep(
                 self.raw_world,
                 0,
@@ -881,7 +1058,7 @@ impl World {
                 SEPARATOR.as_ptr(),
                 search_path,
             )
-        };
+        });
         if entity_id == 0 {
             None
         } else {
@@ -1793,13 +1970,9 @@ impl World {
     /// * C++ API: `world::use`
     #[doc(alias = "world::use")]
     #[inline(always)]
-    pub fn set_alias_component<T: ComponentId>(&self, alias: &CStr) -> Entity {
+    pub fn set_alias_component<T: ComponentId>(&self, alias: impl IntoCStr) -> Entity {
         let id = T::get_id(self.raw_world);
-        if alias.is_empty() {
-            unsafe { ecs_set_alias(self.raw_world, id, ecs_get_name(self.raw_world, id)) };
-        } else {
-            unsafe { ecs_set_alias(self.raw_world, id, alias.as_ptr()) };
-        }
+        self.set_alias_entity(id, alias);
         Entity::new_from_existing_raw(self.raw_world, id)
     }
 
@@ -1819,8 +1992,8 @@ impl World {
     /// * C++ API: `world::use`
     #[doc(alias = "world::use")]
     #[inline(always)]
-    pub fn set_alias_entity_by_name(&self, name: &CStr, alias: &CStr) -> Entity {
-        let id = unsafe {
+    pub fn set_alias_entity_by_name(&self, name: impl IntoCStr, alias: impl IntoCStr) -> Entity {
+        let id = name.with_cstr(|name| unsafe {
             ecs_lookup_path_w_sep(
                 self.raw_world,
                 0,
@@ -1829,9 +2002,9 @@ impl World {
                 SEPARATOR.as_ptr(),
                 true,
             )
-        };
+        });
         ecs_assert!(id != 0, FlecsErrorCode::InvalidParameter);
-        unsafe { ecs_set_alias(self.raw_world, id, alias.as_ptr()) };
+        alias.with_cstr(|alias| unsafe { ecs_set_alias(self.raw_world, id, alias.as_ptr()) });
         Entity::new_from_existing_raw(self.raw_world, id)
     }
 
@@ -1847,18 +2020,20 @@ impl World {
     /// * C++ API: `world::use`
     #[doc(alias = "world::use")]
     #[inline(always)]
-    pub fn set_alias_entity(&self, entity: impl IntoEntityId, alias: &CStr) {
-        if alias.is_empty() {
-            unsafe {
-                ecs_set_alias(
-                    self.raw_world,
-                    entity.get_id(),
-                    ecs_get_name(self.raw_world, entity.get_id()),
-                );
-            };
-        } else {
-            unsafe { ecs_set_alias(self.raw_world, entity.get_id(), alias.as_ptr()) };
-        }
+    pub fn set_alias_entity(&self, entity: impl IntoEntityId, alias: impl IntoCStr) {
+        alias.with_cstr(|alias| {
+            if alias.is_empty() {
+                unsafe {
+                    ecs_set_alias(
+                        self.raw_world,
+                        entity.get_id(),
+                        ecs_get_name(self.raw_world, entity.get_id()),
+                    );
+                };
+            } else {
+                unsafe { ecs_set_alias(self.raw_world, entity.get_id(), alias.as_ptr()) };
+            }
+        });
     }
 
     /// Count entities with the provided id.
@@ -2098,7 +2273,7 @@ impl World {
     ///
     /// * C++ API: `world::scope`
     #[doc(alias = "world::scope")]
-    pub fn scope_name(&self, name: &CStr) -> ScopedWorld {
+    pub fn scope_name(&self, name: impl IntoCStr) -> ScopedWorld {
         self.scope_id(Entity::new_named(self, name).raw_id)
     }
 
@@ -2667,10 +2842,10 @@ impl World {
     ///
     /// * C++ API: `world::entity`
     #[doc(alias = "world::entity")]
-    pub fn new_entity_named_type<T: ComponentId>(&self, name: &CStr) -> Entity {
+    pub fn new_entity_named_type<T: ComponentId>(&self, name: impl IntoCStr) -> Entity {
         Entity::new_from_existing_raw(
             self.raw_world,
-            T::register_explicit_named(self.raw_world, name),
+            name.with_cstr(|name| T::register_explicit_named(self.raw_world, name)),
         )
     }
 
@@ -2688,7 +2863,7 @@ impl World {
     ///
     /// * C++ API: `world::entity`
     #[doc(alias = "world::entity")]
-    pub fn new_entity_named(&self, name: &CStr) -> Entity {
+    pub fn new_entity_named(&self, name: impl IntoCStr) -> Entity {
         Entity::new_named(self, name)
     }
 
@@ -2702,6 +2877,80 @@ impl World {
         Entity::new(self)
     }
 
+    /// Create a new entity with the components of a bundle.
+    ///
+    /// The entity is created in the table with all components of the bundle, instead of being
+    /// moved once for every component.
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle` - The components to set, for example a tuple of components.
+    pub fn spawn<B: Bundle>(&self, bundle: B) -> Entity {
+        Entity::new_from_existing_raw(self.raw_world, spawn_bundle(self.raw_world, bundle))
+    }
+
+    /// Create a new entity for every bundle of an iterator.
+    ///
+    /// Outside of deferred mode, all entities are created in their table with a single bulk
+    /// operation, which moves the values of the bundles into the table. This is considerably
+    /// faster than creating the entities one by one.
+    ///
+    /// # Arguments
+    ///
+    /// * `bundles` - The components to set for each entity, one bundle per entity.
+    ///
+    /// # Returns
+    ///
+    /// The created entities, in the order of the bundles.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `ecs_bulk_init`
+    #[doc(alias = "ecs_bulk_init")]
+    pub fn spawn_batch<B: Bundle>(&self, bundles: impl IntoIterator<Item = B>) -> Vec<Entity> {
+        spawn_bundle_batch(self.raw_world, bundles.into_iter().collect())
+            .into_iter()
+            .map(|id| Entity::new_from_existing_raw(self.raw_world, id))
+            .collect()
+    }
+
+    /// Create `count` new entities with the same component values.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of entities to create.
+    /// * `bundle` - The components to set, cloned for each entity.
+    ///
+    /// # Returns
+    ///
+    /// The created entities.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `ecs_bulk_init`
+    #[doc(alias = "ecs_bulk_init")]
+    pub fn spawn_batch_n<B: Bundle + Clone>(&self, count: usize, bundle: B) -> Vec<Entity> {
+        self.spawn_batch(std::iter::repeat(bundle).take(count))
+    }
+
+    /// Delete a batch of entities.
+    ///
+    /// The deletes are deferred until all entities were visited, and are then flushed together.
+    /// This doesn't use a bulk operation: flecs still deletes the entities one at a time, and
+    /// observers of the deletes run once the batch is flushed.
+    ///
+    /// # Arguments
+    ///
+    /// * `entities` - The entities to delete.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `entity::destruct`
+    #[doc(alias = "entity::destruct")]
+    pub fn despawn_batch(&self, entities: &[Entity]) {
+        despawn_batch(self.raw_world, entities.iter().map(|entity| entity.raw_id));
+    }
+
     /// Create a new entity with the provided id.
     ///
     /// # Arguments
@@ -2746,7 +2995,7 @@ impl World {
     ///
     /// * C++ API: `world::prefab`
     #[doc(alias = "world::prefab")]
-    pub fn prefab_named(&self, name: &CStr) -> Entity {
+    pub fn prefab_named(&self, name: impl IntoCStr) -> Entity {
         let result = Entity::new_named(self, name);
         result.add_id(ECS_PREFAB);
         result
@@ -2791,7 +3040,7 @@ impl World {
     ///
     /// * C++ API: `world::prefab`
     #[doc(alias = "world::prefab")]
-    pub fn prefab_type_named<T: ComponentId>(&self, name: &CStr) -> Entity {
+    pub fn prefab_type_named<T: ComponentId>(&self, name: impl IntoCStr) -> Entity {
         let result = Component::<T>::new_named(self, name).to_entity();
         result.add_id(ECS_PREFAB);
         result.add::<T>();
@@ -2910,7 +3159,10 @@ impl World {
     ///
     /// * C++ API: `world::component`
     #[doc(alias = "world::component")]
-    pub fn component_named<T: ComponentId>(&self, name: &CStr) -> Component<T::UnderlyingType> {
+    pub fn component_named<T: ComponentId>(
+        &self,
+        name: impl IntoCStr,
+    ) -> Component<T::UnderlyingType> {
         Component::<T::UnderlyingType>::new_named(self, name)
     }
 
@@ -2989,7 +3241,7 @@ impl World {
     /// * C++ API: `world::term`
     #[doc(alias = "world::term")]
     pub fn term<T: IntoComponentId>(&self) -> Term {
-        Term::new_type::<T>(Some(self))
+        Term::new_type::<T>(self)
     }
 }
 
@@ -3094,7 +3346,7 @@ impl World {
     #[doc(alias = "world::observer")]
     pub fn observer_builder_named<'a, Components>(
         &self,
-        name: &CStr,
+        name: impl IntoCStr,
     ) -> ObserverBuilder<'a, Components>
     where
         Components: Iterable<'a>,
@@ -3144,7 +3396,7 @@ impl World {
     ///
     /// * C++ API: `world::filter`
     #[doc(alias = "world::filter")]
-    pub fn filter_named<'a, Components>(&self, name: &CStr) -> Filter<'a, Components>
+    pub fn filter_named<'a, Components>(&self, name: impl IntoCStr) -> Filter<'a, Components>
     where
         Components: Iterable<'a>,
     {
@@ -3190,7 +3442,10 @@ impl World {
     ///
     /// * C++ API: `world::filter_builder`
     #[doc(alias = "world::filter_builder")]
-    pub fn filter_builder_named<'a, Components>(&self, name: &CStr) -> FilterBuilder<'a, Components>
+    pub fn filter_builder_named<'a, Components>(
+        &self,
+        name: impl IntoCStr,
+    ) -> FilterBuilder<'a, Components>
     where
         Components: Iterable<'a>,
     {
@@ -3263,7 +3518,7 @@ impl World {
     ///
     /// * C++ API: `world::query`
     #[doc(alias = "world::query")]
-    pub fn query_named<'a, Components>(&self, name: &CStr) -> Query<'a, Components>
+    pub fn query_named<'a, Components>(&self, name: impl IntoCStr) -> Query<'a, Components>
     where
         Components: Iterable<'a>,
     {
@@ -3309,7 +3564,10 @@ impl World {
     ///
     /// * C++ API: `world::query_builder`
     #[doc(alias = "world::query_builder")]
-    pub fn query_builder_named<'a, Components>(&self, name: &CStr) -> QueryBuilder<'a, Components>
+    pub fn query_builder_named<'a, Components>(
+        &self,
+        name: impl IntoCStr,
+    ) -> QueryBuilder<'a, Components>
     where
         Components: Iterable<'a>,
     {
@@ -3370,7 +3628,10 @@ impl World {
     ///
     /// * C++ API: `world::system_builder`
     #[doc(alias = "world::system_builder")]
-    pub fn system_builder_named<'a, Components>(&self, name: &CStr) -> SystemBuilder<'a, Components>
+    pub fn system_builder_named<'a, Components>(
+        &self,
+        name: impl IntoCStr,
+    ) -> SystemBuilder<'a, Components>
     where
         Components: Iterable<'a>,
     {
@@ -3428,7 +3689,7 @@ impl World {
     /// * C++ API: `world::pipeline`
     #[doc(alias = "world::pipeline")]
     #[inline(always)]
-    pub fn pipeline_named(&self, name: &CStr) -> PipelineBuilder<()> {
+    pub fn pipeline_named(&self, name: impl IntoCStr) -> PipelineBuilder<()> {
         PipelineBuilder::<()>::new_named(self, name)
     }
 
@@ -3467,6 +3728,42 @@ impl World {
         }
     }
 
+    /// Set a custom pipeline, returning an error instead of aborting if the entity is not a
+    /// pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `pipeline` - The pipeline to set.
+    ///
+    /// # Returns
+    ///
+    /// An error if `pipeline` is not an alive pipeline entity.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::set_pipeline`
+    #[doc(alias = "world::set_pipeline")]
+    pub fn try_set_pipeline(&self, pipeline: impl IntoEntityId) -> Result<(), FlecsError> {
+        let pipeline = pipeline.get_id();
+        let is_pipeline = unsafe {
+            ecs_is_valid(self.raw_world, pipeline)
+                && ecs_is_alive(self.raw_world, pipeline)
+                && sys::ecs_has_id(self.raw_world, pipeline, ECS_PIPELINE)
+        };
+
+        if !is_pipeline {
+            return Err(FlecsError::new(
+                FlecsErrorCode::InvalidParameter,
+                format!("entity {} is not a pipeline", pipeline),
+            ));
+        }
+
+        unsafe {
+            sys::ecs_set_pipeline(self.raw_world, pipeline);
+        }
+        Ok(())
+    }
+
     /// Set a custom pipeline by type. This operation sets the pipeline to run when `ecs_progress` is invoked.
     ///
     /// # Type Parameters
@@ -3870,11 +4167,181 @@ impl World {
     #[doc(alias = "world::app")]
     #[inline(always)]
     pub fn app(&mut self) -> App {
-        self.is_owned = false;
         App::new(self)
     }
 }
 
+/// Module mixin implementation
+#[cfg(feature = "flecs_module")]
+impl World {
+    /// Import a module.
+    ///
+    /// This operation will load a module. The module type is registered as a component named
+    /// after its Rust path, initialized with `ecs_module_init` and set as the scope of the world
+    /// while [`Module::module`] runs, so the contents of the module are nested under the module
+    /// path. The previous scope and `with` id are restored afterwards.
+    ///
+    /// Importing a module that was already imported in this world does nothing besides
+    /// returning the module entity.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `M` - The module to import.
+    ///
+    /// # Returns
+    ///
+    /// The module entity.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::import`
+    #[doc(alias = "world::import")]
+    pub fn import<M: Module>(&self) -> Entity {
+        let world = self.raw_world;
+
+        if M::is_registered_with_world(world) {
+            let module = unsafe { M::get_id_unchecked(world) };
+            if unsafe { ecs_has_id(world, module, ECS_MODULE) } {
+                return Entity::new_from_existing_raw(world, module);
+            }
+        }
+
+        let module = crate::addons::module::import::<M>(self);
+
+        Entity::new_from_existing_raw(world, module)
+    }
+}
+
+/// Snapshot mixin implementation
+#[cfg(feature = "flecs_snapshot")]
+impl World {
+    /// Take a snapshot of the world.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot, which can be restored with `Snapshot::restore`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world contains union relationships or toggled components, use
+    /// [`World::try_snapshot`] to handle this case.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::snapshot`
+    #[doc(alias = "world::snapshot")]
+    #[inline(always)]
+    pub fn snapshot(&self) -> Snapshot<'_> {
+        Snapshot::new(self)
+    }
+
+    /// Take a snapshot of the tables matched by a filter, query or rule.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The filter, query or rule that selects the tables to store.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot, which can be restored with `Snapshot::restore`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matched tables contain union relationships or toggled components, use
+    /// [`World::try_snapshot_filtered`] to handle this case.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `snapshot::take`
+    #[doc(alias = "snapshot::take")]
+    #[inline(always)]
+    pub fn snapshot_filtered(&self, filter: &impl IterOperations) -> Snapshot<'_> {
+        Snapshot::new_filtered(self, filter)
+    }
+
+    /// Take a snapshot of the world, returning an error if it contains entities that can't be
+    /// stored.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot, or an error if the world contains union relationships or toggled components.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::snapshot`
+    #[doc(alias = "world::snapshot")]
+    #[inline(always)]
+    pub fn try_snapshot(&self) -> Result<Snapshot<'_>, FlecsError> {
+        Snapshot::try_new(self)
+    }
+
+    /// Take a snapshot of the tables matched by a filter, query or rule, returning an error if
+    /// they contain entities that can't be stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The filter, query or rule that selects the tables to store.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot, or an error if the tables contain union relationships or toggled components.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `snapshot::take`
+    #[doc(alias = "snapshot::take")]
+    #[inline(always)]
+    pub fn try_snapshot_filtered(
+        &self,
+        filter: &impl IterOperations,
+    ) -> Result<Snapshot<'_>, FlecsError> {
+        Snapshot::try_new_filtered(self, filter)
+    }
+}
+
+/// Timer mixin implementation
+#[cfg(feature = "flecs_timer")]
+impl World {
+    /// Create a new timer.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::timer`
+    #[doc(alias = "world::timer")]
+    #[inline(always)]
+    pub fn timer(&self) -> Timer {
+        Timer::new(self)
+    }
+
+    /// Create a timer on the entity of a type.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type whose entity is used as timer.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::timer`
+    #[doc(alias = "world::timer")]
+    #[inline(always)]
+    pub fn timer_from<T: ComponentId>(&self) -> Timer {
+        Timer::new_from_existing(self, T::get_id(self))
+    }
+
+    /// Enable randomizing the initial time value of timers.
+    /// This can improve scheduling, as systems and timers with the same interval
+    /// will no longer all tick on the same frame.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::randomize_timers`
+    #[doc(alias = "world::randomize_timers")]
+    #[inline(always)]
+    pub fn randomize_timers(&self) {
+        unsafe { crate::sys::ecs_randomize_timers(self.raw_world) }
+    }
+}
+
 /// Rules mixin implementation
 #[cfg(feature = "flecs_rules")]
 impl World {
@@ -3911,7 +4378,7 @@ impl World {
     /// * C++ API: `world::rule`
     #[doc(alias = "world::rule")]
     #[inline(always)]
-    pub fn rule_named<'a, T>(&self, name: &CStr) -> crate::addons::rules::Rule<'a, T>
+    pub fn rule_named<'a, T>(&self, name: impl IntoCStr) -> crate::addons::rules::Rule<'a, T>
     where
         T: Iterable<'a>,
     {
@@ -3951,10 +4418,261 @@ impl World {
     /// * C++ API: `world::rule_builder`
     #[doc(alias = "world::rule_builder")]
     #[inline(always)]
-    pub fn rule_builder_named<'a, T>(&self, name: &CStr) -> crate::addons::rules::RuleBuilder<'a, T>
+    pub fn rule_builder_named<'a, T>(
+        &self,
+        name: impl IntoCStr,
+    ) -> crate::addons::rules::RuleBuilder<'a, T>
     where
         T: Iterable<'a>,
     {
         crate::addons::rules::RuleBuilder::<'a, T>::new_named(self, name)
     }
 }
+
+/// Meta mixin implementation
+#[cfg(feature = "flecs_meta")]
+impl World {
+    /// Create a meta cursor for a value of a reflected component type.
+    ///
+    /// The type must have reflection data, for example by deriving `Component` with `#[meta]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to navigate and edit with the cursor.
+    ///
+    /// # Returns
+    ///
+    /// The cursor, or an error if the type has no reflection data.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::cursor`
+    #[doc(alias = "world::cursor")]
+    #[inline(always)]
+    pub fn cursor<'w, T: ComponentId>(
+        &'w self,
+        value: &'w mut T,
+    ) -> Result<crate::addons::meta::MetaCursor<'w>, crate::addons::meta::MetaCursorError> {
+        let type_id = T::get_id(self);
+        unsafe {
+            crate::addons::meta::MetaCursor::new(
+                self.raw_world,
+                type_id,
+                value as *mut T as *mut c_void,
+            )
+        }
+    }
+
+    /// Create a meta cursor for a value of the specified type.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_id` - The type of the value.
+    /// * `ptr` - Pointer to the value.
+    ///
+    /// # Returns
+    ///
+    /// The cursor, or an error if the type has no reflection data.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid value of type `type_id` which is not accessed through other
+    /// references for as long as the cursor is alive.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::cursor`
+    #[doc(alias = "world::cursor")]
+    #[inline(always)]
+    pub unsafe fn cursor_id(
+        &self,
+        type_id: impl IntoEntityId,
+        ptr: *mut c_void,
+    ) -> Result<crate::addons::meta::MetaCursor<'_>, crate::addons::meta::MetaCursorError> {
+        crate::addons::meta::MetaCursor::new(self.raw_world, type_id.get_id(), ptr)
+    }
+}
+
+/// Json mixin implementation
+#[cfg(feature = "flecs_json")]
+impl World {
+    /// Serialize the world to JSON.
+    ///
+    /// The result contains the entities of the world grouped by table, together with their
+    /// component values.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc` - The serialization options, the defaults are used if `None`.
+    ///
+    /// # Returns
+    ///
+    /// The JSON string, or an error if the world could not be serialized.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::to_json`
+    #[doc(alias = "world::to_json")]
+    pub fn to_json(
+        &self,
+        desc: Option<&crate::addons::json::WorldToJsonDesc>,
+    ) -> Result<String, crate::addons::json::JsonError> {
+        let desc = desc.copied().unwrap_or_default();
+        unsafe {
+            crate::addons::json::json_from_raw(crate::sys::ecs_world_to_json(
+                self.raw_world,
+                &desc.desc,
+            ))
+        }
+    }
+
+    /// Serialize the world to JSON and write the result to `writer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The destination of the JSON.
+    /// * `desc` - The serialization options, the defaults are used if `None`.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::to_json`
+    #[doc(alias = "world::to_json")]
+    pub fn to_json_writer(
+        &self,
+        mut writer: impl std::io::Write,
+        desc: Option<&crate::addons::json::WorldToJsonDesc>,
+    ) -> Result<(), crate::addons::json::JsonError> {
+        let desc = desc.copied().unwrap_or_default();
+        // an all zero buffer is an empty string buffer, like ECS_STRBUF_INIT
+        let mut buf: crate::sys::ecs_strbuf_t = unsafe { std::mem::zeroed() };
+        if unsafe { crate::sys::ecs_world_to_json_buf(self.raw_world, &mut buf, &desc.desc) } != 0 {
+            unsafe { crate::sys::ecs_strbuf_reset(&mut buf) };
+            return Err(crate::addons::json::JsonError::SerializeFailed);
+        }
+
+        // write the JSON as flecs produced it, without converting it to a Rust string first
+        let json = unsafe { crate::sys::ecs_strbuf_get(&mut buf) };
+        if json.is_null() {
+            return Err(crate::addons::json::JsonError::SerializeFailed);
+        }
+        let result = writer.write_all(unsafe { std::ffi::CStr::from_ptr(json) }.to_bytes());
+        unsafe {
+            if let Some(free_func) = crate::sys::ecs_os_api.free_ {
+                free_func(json as *mut c_void);
+            }
+        }
+        Ok(result?)
+    }
+
+    /// Load entities and component values from JSON, as produced by `World::to_json`.
+    ///
+    /// Components in the JSON are looked up by name, so they must be registered with the world
+    /// before loading.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON string to deserialize.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::from_json`
+    #[doc(alias = "world::from_json")]
+    pub fn from_json(&self, json: &str) -> Result<(), crate::addons::json::JsonError> {
+        let json = crate::addons::json::json_to_cstring(json)?;
+        self.load_json_cstr(&json)
+    }
+
+    /// Read JSON from `reader` and load it into the world.
+    ///
+    /// The bytes are passed to flecs as they are read, without validating them as UTF-8.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The source of the JSON.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::from_json`
+    #[doc(alias = "world::from_json")]
+    pub fn from_json_reader(
+        &self,
+        mut reader: impl std::io::Read,
+    ) -> Result<(), crate::addons::json::JsonError> {
+        let mut json = Vec::new();
+        reader.read_to_end(&mut json)?;
+        let json = std::ffi::CString::new(json)
+            .map_err(|_| crate::addons::json::JsonError::InvalidString)?;
+        self.load_json_cstr(&json)
+    }
+
+    fn load_json_cstr(&self, json: &std::ffi::CStr) -> Result<(), crate::addons::json::JsonError> {
+        let desc = crate::sys::ecs_from_json_desc_t::default();
+        crate::addons::json::json_parse(|| unsafe {
+            crate::sys::ecs_world_from_json(self.raw_world, json.as_ptr(), &desc)
+        })
+    }
+
+    /// Serialize a component value to JSON.
+    ///
+    /// The type of the value must have reflection data.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to serialize.
+    ///
+    /// # Returns
+    ///
+    /// The JSON string, or an error if the value could not be serialized.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::to_json`
+    #[doc(alias = "world::to_json")]
+    pub fn to_json_value<T: ComponentId>(
+        &self,
+        value: &T,
+    ) -> Result<String, crate::addons::json::JsonError> {
+        unsafe {
+            crate::addons::json::json_from_raw(crate::sys::ecs_ptr_to_json(
+                self.raw_world,
+                T::get_id(self),
+                value as *const T as *const c_void,
+            ))
+        }
+    }
+
+    /// Deserialize a component value from JSON.
+    ///
+    /// Members that are not present in the JSON keep their default value.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON string to deserialize.
+    ///
+    /// # Returns
+    ///
+    /// The value, or an error if the JSON could not be parsed into the type.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::from_json`
+    #[doc(alias = "world::from_json")]
+    pub fn from_json_value<T: ComponentId + Default>(
+        &self,
+        json: &str,
+    ) -> Result<T, crate::addons::json::JsonError> {
+        let json = crate::addons::json::json_to_cstring(json)?;
+        let desc = crate::sys::ecs_from_json_desc_t::default();
+        let mut value = T::default();
+        crate::addons::json::json_parse(|| unsafe {
+            crate::sys::ecs_ptr_from_json(
+                self.raw_world,
+                T::get_id(self),
+                &mut value as *mut T as *mut c_void,
+                json.as_ptr(),
+                &desc,
+            )
+        })?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,135 @@
+//! Shared handles to a world that can be stored independently of the [`World`] they came from.
+
+use std::{
+    marker::PhantomData,
+    ops::Deref,
+    sync::{Arc, Weak},
+};
+
+use super::{
+    world::{World, WorldOwner},
+    IntoWorld, WorldT,
+};
+
+/// Strong handle to a world.
+///
+/// The world stays alive until the last strong handle, including the owning [`World`] and its
+/// clones, is dropped. This makes it safe to store the handle in long-lived objects without
+/// depending on the order in which they are dropped.
+///
+/// Created with [`World::to_ref`] or by upgrading a [`WeakWorldRef`].
+///
+/// The handle shares the world with other handles, so unlike [`World`] it can't be sent to
+/// another thread.
+pub struct WorldRef {
+    world: World,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Clone for WorldRef {
+    fn clone(&self) -> Self {
+        Self::new(self.world.raw_world, self.world.owner.clone())
+    }
+}
+
+impl WorldRef {
+    pub(crate) fn new(raw_world: *mut WorldT, owner: Option<Arc<WorldOwner>>) -> Self {
+        Self {
+            world: World { raw_world, owner },
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Create a weak handle to the world.
+    ///
+    /// # Returns
+    ///
+    /// A handle that does not keep the world alive.
+    pub fn downgrade(&self) -> WeakWorldRef {
+        self.world.downgrade()
+    }
+
+    /// Get the world handle.
+    ///
+    /// # Returns
+    ///
+    /// The world, which stays alive for as long as this handle exists.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+}
+
+impl Deref for WorldRef {
+    type Target = World;
+
+    fn deref(&self) -> &Self::Target {
+        &self.world
+    }
+}
+
+impl IntoWorld for WorldRef {
+    #[inline]
+    #[doc(hidden)]
+    fn world_ptr_mut(&self) -> *mut WorldT {
+        self.world.raw_world
+    }
+}
+
+/// Weak handle to a world.
+///
+/// Does not keep the world alive. Use [`WeakWorldRef::upgrade`] to access the world, which fails
+/// once the world was destroyed. Like [`WorldRef`], it can't be sent to another thread.
+#[derive(Clone)]
+pub struct WeakWorldRef {
+    raw_world: *mut WorldT,
+    owner: Weak<WorldOwner>,
+}
+
+impl WeakWorldRef {
+    /// Get a strong handle to the world.
+    ///
+    /// # Returns
+    ///
+    /// The handle, or `None` if the world was already destroyed.
+    pub fn upgrade(&self) -> Option<WorldRef> {
+        self.owner
+            .upgrade()
+            .map(|owner| WorldRef::new(self.raw_world, Some(owner)))
+    }
+
+    /// Test whether the world is still alive.
+    ///
+    /// # Returns
+    ///
+    /// True if the world was not destroyed yet.
+    pub fn is_alive(&self) -> bool {
+        self.owner.strong_count() > 0
+    }
+}
+
+impl World {
+    /// Create a strong handle to the world.
+    ///
+    /// # Returns
+    ///
+    /// A handle that keeps the world alive until it is dropped, or `None` if this handle does not
+    /// own the world, like handles returned by [`World::new_wrap_raw_world`].
+    pub fn to_ref(&self) -> Option<WorldRef> {
+        self.owner
+            .as_ref()
+            .map(|owner| WorldRef::new(self.raw_world, Some(owner.clone())))
+    }
+
+    /// Create a weak handle to the world.
+    ///
+    /// # Returns
+    ///
+    /// A handle that does not keep the world alive. It can't be upgraded if this handle does
+    /// not own the world.
+    pub fn downgrade(&self) -> WeakWorldRef {
+        WeakWorldRef {
+            raw_world: self.raw_world,
+            owner: self.owner.as_ref().map(Arc::downgrade).unwrap_or_default(),
+        }
+    }
+}
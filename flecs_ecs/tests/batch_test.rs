@@ -0,0 +1,166 @@
+use flecs_ecs::{
+    core::{flecs, world::World, ReactorAPI},
+    sys::ecs_get_world_info,
+};
+use flecs_ecs_derive::Component;
+
+mod common;
+use common::*;
+
+#[derive(Component, Clone, Default)]
+struct Tile {
+    kind: String,
+}
+
+fn tables_created(world: &World) -> i64 {
+    unsafe { (*ecs_get_world_info(world.raw_world)).table_create_total }
+}
+
+#[test]
+fn batch_spawn_from_iter() {
+    let world = World::new();
+    let entities =
+        world.spawn_batch((0..100).map(|i| (Position { x: i, y: -i }, Velocity { x: 1, y: 2 })));
+
+    assert_eq!(entities.len(), 100);
+    assert_eq!(world.count::<Position>(), 100);
+    assert_eq!(world.count::<Velocity>(), 100);
+    for (i, entity) in entities.iter().enumerate() {
+        let pos = entity.get::<Position>().unwrap();
+        assert_eq!((pos.x, pos.y), (i as i32, -(i as i32)));
+    }
+}
+
+#[test]
+fn batch_spawn_n_clones_values() {
+    let world = World::new();
+    let entities = world.spawn_batch_n(
+        1000,
+        (Tile {
+            kind: "grass".to_string(),
+        },),
+    );
+
+    assert_eq!(entities.len(), 1000);
+    assert_eq!(world.count::<Tile>(), 1000);
+    assert!(entities
+        .iter()
+        .all(|entity| entity.get::<Tile>().unwrap().kind == "grass"));
+}
+
+#[test]
+fn batch_spawn_unique_ids() {
+    let world = World::new();
+    let mut ids: Vec<_> = world
+        .spawn_batch((0..500).map(|_| (Mass { value: 1 },)))
+        .iter()
+        .map(|entity| entity.raw_id)
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), 500);
+}
+
+#[test]
+fn batch_spawn_creates_only_final_table() {
+    let world = World::new();
+    world.component::<Position>();
+    world.component::<Velocity>();
+    world.component::<Mass>();
+
+    let before = tables_created(&world);
+    world.spawn_batch((0..10).map(|_| {
+        (
+            Position { x: 1, y: 2 },
+            Velocity { x: 3, y: 4 },
+            Mass { value: 5 },
+        )
+    }));
+    assert_eq!(tables_created(&world) - before, 1);
+}
+
+#[test]
+fn batch_spawn_empty() {
+    let world = World::new();
+    assert!(world
+        .spawn_batch(std::iter::empty::<(Position,)>())
+        .is_empty());
+    assert!(world.spawn_batch_n(3, ()).len() == 3);
+}
+
+#[test]
+fn batch_spawn_triggers_observers() {
+    let world = World::new();
+    let mut sum = 0;
+    world
+        .observer_builder::<(&Position,)>()
+        .add_event::<flecs::OnSet>()
+        .on_each(|(pos,)| sum += pos.x);
+
+    world.spawn_batch((1..=4).map(|x| (Position { x, y: 0 },)));
+    assert_eq!(sum, 10);
+}
+
+#[test]
+fn batch_spawn_sets_each_value_once() {
+    let world = World::new();
+    let mut kinds = Vec::new();
+    world
+        .observer_builder::<(&Tile,)>()
+        .add_event::<flecs::OnSet>()
+        .on_each(|(tile,)| kinds.push(tile.kind.clone()));
+
+    let entities = world.spawn_batch((0..5).map(|i| {
+        (
+            Position { x: i, y: 0 },
+            Tile {
+                kind: format!("tile {i}"),
+            },
+        )
+    }));
+    assert_eq!(kinds, ["tile 0", "tile 1", "tile 2", "tile 3", "tile 4"]);
+    assert_eq!(entities[2].get::<Tile>().unwrap().kind, "tile 2");
+    assert_eq!(entities[4].get::<Position>().unwrap().x, 4);
+}
+
+#[test]
+fn batch_spawn_deferred() {
+    let world = World::new();
+    world.defer_begin();
+    let entities = world.spawn_batch((0..10).map(|i| {
+        (Tile {
+            kind: format!("tile {i}"),
+        },)
+    }));
+    assert!(!entities[0].has::<Tile>());
+    world.defer_end();
+
+    assert_eq!(world.count::<Tile>(), 10);
+    assert_eq!(entities[3].get::<Tile>().unwrap().kind, "tile 3");
+}
+
+#[test]
+fn batch_despawn() {
+    let world = World::new();
+    let entities = world.spawn_batch((0..50).map(|_| (Position { x: 0, y: 0 },)));
+    let keep = world.new_entity().set(Position { x: 1, y: 1 });
+
+    world.despawn_batch(&entities[..40]);
+    assert_eq!(world.count::<Position>(), 11);
+    assert!(!world.is_alive(entities[0]));
+    assert!(world.is_alive(entities[45]));
+    assert!(world.is_alive(keep));
+}
+
+#[test]
+fn batch_despawn_drops_values() {
+    let world = World::new();
+    let entities = world.spawn_batch((0..20).map(|i| {
+        (Tile {
+            kind: i.to_string(),
+        },)
+    }));
+
+    world.despawn_batch(&entities);
+    assert_eq!(world.count::<Tile>(), 0);
+}
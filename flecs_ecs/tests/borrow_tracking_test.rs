@@ -0,0 +1,130 @@
+#![cfg(all(feature = "flecs_borrow_tracking", debug_assertions))]
+
+use flecs_ecs::core::{world::World, IterAPI};
+
+mod common;
+use common::*;
+
+#[test]
+#[should_panic(expected = "mutably: it is already borrowed")]
+fn borrow_nested_each_mut_same_component() {
+    let world = World::new();
+    world.new_entity().set(Position { x: 1, y: 2 });
+
+    let outer = world.query::<(&mut Position,)>();
+    let inner = world.query::<(&mut Position,)>();
+
+    outer.each(|(_pos,)| {
+        inner.each(|(_aliased,)| {});
+    });
+}
+
+#[test]
+#[should_panic(expected = "it is already borrowed mutably")]
+fn borrow_nested_each_read_while_written() {
+    let world = World::new();
+    world.new_entity().set(Position { x: 1, y: 2 });
+
+    let outer = world.query::<(&mut Position,)>();
+    let inner = world.query::<(&Position,)>();
+
+    outer.each(|(_pos,)| {
+        inner.each(|(_aliased,)| {});
+    });
+}
+
+#[test]
+fn borrow_nested_each_shared_and_disjoint() {
+    let world = World::new();
+    world
+        .new_entity()
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 1, y: 1 });
+
+    let outer = world.query::<(&Position, &mut Velocity)>();
+    let reads = world.query::<(&Position,)>();
+    let writes = world.query::<(&mut Mass,)>();
+
+    let mut count = 0;
+    outer.each(|(_pos, _vel)| {
+        reads.each(|(_pos,)| count += 1);
+        writes.each(|(_mass,)| {});
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn borrow_released_after_each() {
+    let world = World::new();
+    world.new_entity().set(Position { x: 1, y: 2 });
+
+    let query = world.query::<(&mut Position,)>();
+    query.each(|(pos,)| pos.x += 1);
+    query.each(|(pos,)| pos.x += 1);
+
+    let mut x = 0;
+    query.each(|(pos,)| x = pos.x);
+    assert_eq!(x, 3);
+}
+
+#[test]
+#[should_panic(expected = "mutably: it is already borrowed")]
+fn borrow_get_mut_during_each() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: 1, y: 2 });
+
+    let query = world.query::<(&Position,)>();
+    query.each(|(_pos,)| {
+        let mut handle = entity;
+        let _aliased = handle.get_mut::<Position>();
+    });
+}
+
+#[test]
+fn borrow_get_during_each() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: 1, y: 2 });
+
+    let query = world.query::<(&Position,)>();
+    query.each(|(pos,)| {
+        assert_eq!(entity.get::<Position>().unwrap().x, pos.x);
+    });
+}
+
+#[test]
+fn borrow_get_mut_of_different_entities() {
+    let world = World::new();
+    let mut first = world.new_entity().set(Position { x: 1, y: 2 });
+    let mut second = world.new_entity().set(Position { x: 3, y: 4 });
+
+    let mut a = first.get_mut::<Position>();
+    let mut b = second.get_mut::<Position>();
+    std::mem::swap(&mut a.x, &mut b.x);
+    drop((a, b));
+
+    assert_eq!(first.get::<Position>().unwrap().x, 3);
+    assert_eq!(second.get::<Position>().unwrap().x, 1);
+}
+
+#[test]
+#[should_panic(expected = "it is already borrowed mutably")]
+fn borrow_get_while_get_mut() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: 1, y: 2 });
+
+    let mut handle = entity;
+    let _pos = handle.get_mut::<Position>();
+    let _aliased = entity.get::<Position>();
+}
+
+#[test]
+#[should_panic(expected = "mutably: it is already borrowed")]
+fn borrow_get_mut_through_copy() {
+    let world = World::new();
+
+    let mut entity = world.new_entity().set(Position { x: 10, y: 20 });
+    let mut copy = entity;
+
+    let _pos = entity.get_mut::<Position>();
+    let _aliased = copy.get_mut::<Position>();
+}
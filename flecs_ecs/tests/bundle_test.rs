@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+use flecs_ecs::{
+    core::{flecs, world::World, Bundle, ReactorAPI},
+    sys::{ecs_delete_empty_tables, ecs_get_world_info},
+};
+use flecs_ecs_derive::{Bundle, Component};
+
+mod common;
+use common::*;
+
+#[derive(Component, Default)]
+struct Name {
+    value: String,
+}
+
+#[derive(Component, Default)]
+struct Handle {
+    value: Option<Arc<()>>,
+}
+
+#[derive(Bundle)]
+struct Particle {
+    position: Position,
+    velocity: Velocity,
+}
+
+#[derive(Bundle)]
+struct NamedParticle {
+    #[bundle]
+    particle: Particle,
+    name: Name,
+    tag: TagA,
+}
+
+#[derive(Bundle)]
+struct Pair(Position, Mass);
+
+#[derive(Bundle)]
+struct OverlappingParticle {
+    #[bundle]
+    particle: Particle,
+    position: Position,
+}
+
+fn tables_created(world: &World) -> i64 {
+    unsafe { (*ecs_get_world_info(world.raw_world)).table_create_total }
+}
+
+#[test]
+fn bundle_spawn_tuple() {
+    let world = World::new();
+    let entity = world.spawn((Position { x: 1, y: 2 }, Velocity { x: 3, y: 4 }));
+
+    assert_eq!(entity.get::<Position>().unwrap().x, 1);
+    assert_eq!(entity.get::<Velocity>().unwrap().y, 4);
+}
+
+#[test]
+fn bundle_spawn_creates_only_final_table() {
+    let world = World::new();
+    world.component::<Position>();
+    world.component::<Velocity>();
+    world.component::<Mass>();
+
+    let before = tables_created(&world);
+    world.spawn((
+        Position { x: 1, y: 2 },
+        Velocity { x: 3, y: 4 },
+        Mass { value: 5 },
+    ));
+    assert_eq!(tables_created(&world) - before, 1);
+
+    let before = tables_created(&world);
+    world
+        .new_entity()
+        .set(Mass { value: 5 })
+        .set(Velocity { x: 3, y: 4 })
+        .set(Position { x: 1, y: 2 });
+    assert!(tables_created(&world) - before > 1);
+}
+
+#[test]
+fn bundle_spawn_derived() {
+    let world = World::new();
+    let entity = world.spawn(NamedParticle {
+        particle: Particle {
+            position: Position { x: 1, y: 2 },
+            velocity: Velocity { x: 3, y: 4 },
+        },
+        name: Name {
+            value: "spark".to_string(),
+        },
+        tag: TagA {},
+    });
+
+    assert_eq!(entity.get::<Position>().unwrap().x, 1);
+    assert_eq!(entity.get::<Velocity>().unwrap().x, 3);
+    assert_eq!(entity.get::<Name>().unwrap().value, "spark");
+    assert!(entity.has::<TagA>());
+}
+
+#[test]
+fn bundle_spawn_tuple_struct() {
+    let world = World::new();
+    let entity = world.spawn(Pair(Position { x: 1, y: 2 }, Mass { value: 3 }));
+
+    assert_eq!(entity.get::<Mass>().unwrap().value, 3);
+}
+
+#[test]
+fn bundle_insert_overwrites_and_adds() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: 1, y: 2 });
+
+    entity.insert((Position { x: 5, y: 6 }, Velocity { x: 7, y: 8 }, TagA {}));
+
+    assert_eq!(entity.get::<Position>().unwrap().x, 5);
+    assert_eq!(entity.get::<Velocity>().unwrap().x, 7);
+    assert!(entity.has::<TagA>());
+}
+
+#[test]
+fn bundle_remove() {
+    let world = World::new();
+    let entity = world.spawn((
+        Position { x: 1, y: 2 },
+        Velocity { x: 3, y: 4 },
+        Mass { value: 5 },
+    ));
+
+    entity.remove_bundle::<Particle>();
+
+    assert!(!entity.has::<Position>());
+    assert!(!entity.has::<Velocity>());
+    assert!(entity.has::<Mass>());
+}
+
+#[test]
+fn bundle_observers() {
+    let world = World::new();
+
+    let mut added = 0;
+    world
+        .observer_builder::<(&Position,)>()
+        .add_event::<flecs::OnAdd>()
+        .on_each(|_| added += 1);
+    let mut set = 0;
+    world
+        .observer_builder::<(&Velocity,)>()
+        .add_event::<flecs::OnSet>()
+        .on_each(|(vel,)| {
+            assert_eq!(vel.x, 3);
+            set += 1;
+        });
+
+    world.spawn((Position { x: 1, y: 2 }, Velocity { x: 3, y: 4 }));
+    assert_eq!(added, 1);
+    assert_eq!(set, 1);
+}
+
+#[test]
+fn bundle_deferred() {
+    let world = World::new();
+    let entity = world.new_entity();
+
+    world.defer_begin();
+    let spawned = world.spawn((Position { x: 1, y: 2 }, TagA {}));
+    entity.insert((Velocity { x: 3, y: 4 },));
+    assert!(!spawned.has::<Position>());
+    world.defer_end();
+
+    assert_eq!(spawned.get::<Position>().unwrap().x, 1);
+    assert!(spawned.has::<TagA>());
+    assert_eq!(entity.get::<Velocity>().unwrap().x, 3);
+}
+
+#[test]
+fn bundle_spawn_many_owned_values() {
+    fn spawn_named<B: Bundle>(world: &World, bundle: B) {
+        world.spawn(bundle);
+    }
+
+    let world = World::new();
+    for i in 0..1000 {
+        spawn_named(
+            &world,
+            (
+                Position { x: i, y: 0 },
+                Name {
+                    value: i.to_string(),
+                },
+            ),
+        );
+    }
+
+    let mut count = 0;
+    world.each::<(&Position, &Name)>(|(pos, name)| {
+        assert_eq!(pos.x.to_string(), name.value);
+        count += 1;
+    });
+    assert_eq!(count, 1000);
+}
+
+#[test]
+fn bundle_remove_all_components() {
+    let world = World::new();
+    let entity = world.spawn((Position { x: 1, y: 2 },));
+
+    entity.remove_bundle::<(Position, Velocity)>();
+
+    assert!(entity.is_alive());
+    assert!(!entity.has::<Position>());
+}
+
+#[test]
+fn bundle_spawn_moves_values_once() {
+    let world = World::new();
+    let handle = Arc::new(());
+
+    let entities: Vec<_> = (0..10)
+        .map(|i| {
+            world.spawn((
+                Position { x: i, y: 0 },
+                Handle {
+                    value: Some(handle.clone()),
+                },
+            ))
+        })
+        .collect();
+    assert_eq!(Arc::strong_count(&handle), 11);
+    assert!(entities[3].get::<Handle>().unwrap().value.is_some());
+
+    entities[0].destruct();
+    assert_eq!(Arc::strong_count(&handle), 10);
+
+    drop(world);
+    assert_eq!(Arc::strong_count(&handle), 1);
+}
+
+#[test]
+fn bundle_spawn_after_table_deleted() {
+    let world = World::new();
+    world
+        .spawn((Position { x: 1, y: 2 }, Velocity { x: 3, y: 4 }))
+        .destruct();
+    world
+        .new_entity()
+        .insert((Position { x: 1, y: 2 }, Velocity { x: 3, y: 4 }))
+        .destruct();
+
+    let deleted = unsafe {
+        ecs_delete_empty_tables(world.raw_world, 0, 0, 1, 0, 0.0);
+        ecs_delete_empty_tables(world.raw_world, 0, 0, 1, 0, 0.0);
+        (*ecs_get_world_info(world.raw_world)).table_delete_total
+    };
+    assert!(deleted > 0);
+
+    let spawned = world.spawn((Position { x: 5, y: 6 }, Velocity { x: 7, y: 8 }));
+    let inserted = world
+        .new_entity()
+        .insert((Position { x: 9, y: 10 }, Velocity { x: 11, y: 12 }));
+    assert_eq!(spawned.get::<Velocity>().unwrap().x, 7);
+    assert_eq!(inserted.get::<Position>().unwrap().y, 10);
+    assert!(inserted.has::<Velocity>());
+}
+
+#[test]
+fn bundle_spawn_duplicate_component_drops_values() {
+    let world = World::new();
+    let handle = Arc::new(());
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        world.spawn((
+            Handle {
+                value: Some(handle.clone()),
+            },
+            Handle {
+                value: Some(handle.clone()),
+            },
+        ));
+    }));
+    assert!(result.is_err());
+    assert_eq!(Arc::strong_count(&handle), 1);
+}
+
+#[test]
+#[should_panic(expected = "contains component `Position` more than once")]
+fn bundle_spawn_nested_duplicate_component() {
+    let world = World::new();
+    world.spawn(OverlappingParticle {
+        particle: Particle {
+            position: Position { x: 1, y: 2 },
+            velocity: Velocity { x: 3, y: 4 },
+        },
+        position: Position { x: 5, y: 6 },
+    });
+}
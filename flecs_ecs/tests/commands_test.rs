@@ -0,0 +1,211 @@
+use std::sync::mpsc;
+
+use flecs_ecs::core::{
+    flecs, world::World, CommandEntity, Commands, FilterBuilderImpl, FlecsConstantId, ReactorAPI,
+};
+use flecs_ecs_derive::Component;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default)]
+struct Label {
+    text: String,
+}
+
+#[derive(Component)]
+struct Ping;
+
+#[test]
+fn commands_spawn_and_set() {
+    let world = World::new();
+    let mut commands = Commands::new();
+
+    let id = commands
+        .spawn()
+        .set(Position { x: 1, y: 2 })
+        .set(Label {
+            text: "player".to_string(),
+        })
+        .id();
+    assert_eq!(id, CommandEntity::Spawned(0));
+    assert_eq!(commands.len(), 3);
+
+    let spawned = commands.apply(&world);
+    assert!(commands.is_empty());
+    assert_eq!(spawned.len(), 1);
+
+    let entity = spawned[0];
+    assert_eq!(entity.get::<Position>().unwrap().x, 1);
+    assert_eq!(entity.get::<Label>().unwrap().text, "player");
+}
+
+#[test]
+fn commands_existing_entity() {
+    let world = World::new();
+    let entity = world
+        .new_entity()
+        .set(Position { x: 1, y: 2 })
+        .add::<Velocity>();
+
+    let mut commands = Commands::new();
+    commands
+        .entity(entity)
+        .remove::<Velocity>()
+        .set(Position { x: 3, y: 4 })
+        .add::<Ping>();
+
+    assert!(entity.has::<Velocity>());
+    commands.apply(&world);
+
+    assert!(!entity.has::<Velocity>());
+    assert!(entity.has::<Ping>());
+    assert_eq!(entity.get::<Position>().unwrap().x, 3);
+}
+
+#[test]
+fn commands_despawn() {
+    let world = World::new();
+    let entity = world.new_entity();
+
+    let mut commands = Commands::new();
+    commands.entity(entity).despawn();
+    commands.apply(&world);
+
+    assert!(!entity.is_alive());
+}
+
+#[test]
+fn commands_apply_in_recorded_order() {
+    let world = World::new();
+    let entity = world.new_entity();
+
+    let mut commands = Commands::new();
+    commands.entity(entity).set(Position { x: 1, y: 0 });
+    commands.entity(entity).remove::<Position>();
+    commands.entity(entity).set(Position { x: 2, y: 0 });
+    commands.apply(&world);
+
+    assert_eq!(entity.get::<Position>().unwrap().x, 2);
+}
+
+#[test]
+fn commands_child_of_spawned_parent() {
+    let world = World::new();
+    let mut commands = Commands::new();
+
+    let parent = commands.spawn().id();
+    commands.spawn().child_of(parent);
+    let spawned = commands.apply(&world);
+
+    assert_eq!(spawned[1].parent(), spawned[0]);
+}
+
+#[test]
+fn commands_recorded_on_threads() {
+    let world = World::new();
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::scope(|scope| {
+        for job in 0..4 {
+            let sender = sender.clone();
+            scope.spawn(move || {
+                let mut commands = Commands::new();
+                for x in 0..5 {
+                    commands.spawn().set(Position { x, y: job }).set(Label {
+                        text: format!("{job}-{x}"),
+                    });
+                }
+                sender.send((job, commands)).unwrap();
+            });
+        }
+    });
+    drop(sender);
+
+    let mut jobs: Vec<_> = receiver.into_iter().collect();
+    jobs.sort_by_key(|(job, _)| *job);
+
+    let mut commands = Commands::new();
+    for (_, job) in jobs {
+        commands.append(job);
+    }
+    let spawned = commands.apply(&world);
+
+    assert_eq!(spawned.len(), 20);
+    for (index, entity) in spawned.iter().enumerate() {
+        let label = entity.get::<Label>().unwrap();
+        assert_eq!(label.text, format!("{}-{}", index / 5, index % 5));
+    }
+}
+
+#[test]
+fn commands_append_keeps_spawned_ids() {
+    let world = World::new();
+    let mut first = Commands::new();
+    first.spawn();
+
+    let mut second = Commands::new();
+    let parent = second.spawn().id();
+    second.spawn().child_of(parent);
+
+    first.append(second);
+    let spawned = first.apply(&world);
+
+    assert_eq!(spawned.len(), 3);
+    assert_eq!(spawned[2].parent(), spawned[1]);
+}
+
+#[test]
+fn commands_emit() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: 1, y: 2 });
+
+    let mut count = 0;
+    world
+        .observer_builder::<()>()
+        .add_event::<Ping>()
+        .term_with_id(flecs::Any::ID)
+        .on_each_entity(|e, _| {
+            assert_eq!(*e, entity);
+            count += 1;
+        });
+
+    let mut commands = Commands::new();
+    commands.entity(entity).emit::<Ping>();
+    assert_eq!(count, 0);
+
+    commands.apply(&world);
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn commands_apply_to_stage() {
+    let mut world = World::new();
+    world.component::<Position>();
+    let entity = world.new_entity();
+
+    let mut commands = Commands::new();
+    commands.entity(entity).set(Position { x: 1, y: 2 });
+
+    world.readonly_begin();
+    commands.apply(world.stage(0));
+    assert!(!entity.has::<Position>());
+    world.readonly_end();
+
+    assert_eq!(entity.get::<Position>().unwrap().x, 1);
+}
+
+#[test]
+fn commands_clear() {
+    let world = World::new();
+    let mut commands = Commands::new();
+    commands.spawn().set(Position { x: 1, y: 2 });
+    commands.clear();
+
+    assert!(commands.is_empty());
+    assert!(commands.apply(&world).is_empty());
+
+    let mut count = 0;
+    world.each::<(&Position,)>(|_| count += 1);
+    assert_eq!(count, 0);
+}
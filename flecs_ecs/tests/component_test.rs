@@ -1,4 +1,5 @@
-use flecs_ecs::core::{entity::Entity, world::World};
+use flecs_ecs::core::{entity::Entity, world::World, CachedEnumData, ComponentId};
+use flecs_ecs_derive::Component;
 
 mod common;
 use common::*;
@@ -36,20 +37,25 @@ fn temp_test_hook() {
         let pos_e1 = entity.get::<Position>().unwrap();
         assert_eq!(pos_e1.x, 10);
         assert_eq!(pos_e1.y, 20);
+        // structural changes are deferred while a component is borrowed
+        drop(pos_e1);
 
         entity.add::<Position>();
         assert_eq!(unsafe { COUNT }, 1);
         let pos_e1 = entity.get::<Position>().unwrap();
         assert_eq!(pos_e1.x, 10);
         assert_eq!(pos_e1.y, 20);
+        drop(pos_e1);
 
         let entity2 = world.new_entity().add::<Position>();
         assert_eq!(unsafe { COUNT }, 2);
+        let pos_e1 = entity.get::<Position>().unwrap();
         let pos_e2 = entity2.get::<Position>().unwrap();
         assert_eq!(pos_e1.x, 10);
         assert_eq!(pos_e1.y, 20);
         assert_eq!(pos_e2.x, 10);
         assert_eq!(pos_e2.y, 20);
+        drop((pos_e1, pos_e2));
 
         entity.add::<Velocity>();
         assert_eq!(unsafe { COUNT2 }, 0);
@@ -57,6 +63,7 @@ fn temp_test_hook() {
         // dangerous uninitialized values
         assert_ne!(vel_e1.x, 0);
         assert_ne!(vel_e1.y, 0);
+        drop(vel_e1);
         entity.remove::<Velocity>();
 
         entity.remove::<Position>();
@@ -67,6 +74,7 @@ fn temp_test_hook() {
         assert_eq!(vel_e2.x, 30);
         assert_eq!(vel_e2.y, 50);
         assert_eq!(unsafe { COUNT2 }, 1);
+        drop(vel_e2);
 
         entity.remove::<Position>();
         assert_eq!(unsafe { COUNT }, 1);
@@ -74,3 +82,122 @@ fn temp_test_hook() {
         assert_eq!(unsafe { COUNT }, 0);
     }
 }
+
+#[derive(Component, Default)]
+struct RegistryA {
+    _value: i32,
+}
+
+#[derive(Component, Default)]
+struct RegistryB {
+    _value: i32,
+}
+
+#[repr(C)]
+#[derive(Component)]
+enum RegistryEnum {
+    First,
+    Second,
+}
+
+#[test]
+fn component_ids_per_world() {
+    let world_a = World::new();
+    let world_b = World::new();
+
+    // register in a different order, so the ids differ between the worlds
+    let a_in_a = world_a.component::<RegistryA>().raw_id;
+    let b_in_a = world_a.component::<RegistryB>().raw_id;
+    world_b.new_entity_named(c"Filler");
+    let b_in_b = world_b.component::<RegistryB>().raw_id;
+    let a_in_b = world_b.component::<RegistryA>().raw_id;
+
+    assert_ne!(a_in_a, a_in_b);
+    assert_eq!(RegistryA::get_id(&world_a), a_in_a);
+    assert_eq!(RegistryB::get_id(&world_a), b_in_a);
+    assert_eq!(RegistryA::get_id(&world_b), a_in_b);
+    assert_eq!(RegistryB::get_id(&world_b), b_in_b);
+
+    let entity = world_b.new_entity().add::<RegistryA>();
+    assert!(entity.has::<RegistryA>());
+    assert!(!entity.has::<RegistryB>());
+    assert_eq!(entity.archetype().count(), 1);
+
+    let world_c = World::new();
+    assert!(!RegistryA::is_registered_with_world(&world_c));
+    assert!(RegistryA::is_registered_with_world(&world_a));
+}
+
+#[test]
+fn component_enum_variants_per_world() {
+    let world_a = World::new();
+    let world_b = World::new();
+
+    world_b.component::<RegistryB>();
+    let first_in_b = RegistryEnum::First.get_id_variant(&world_b);
+    let first_in_a = RegistryEnum::First.get_id_variant(&world_a);
+
+    assert_eq!(first_in_a.path().unwrap(), first_in_b.path().unwrap());
+    assert!(RegistryEnum::are_fields_registered_as_entities(&world_a));
+    assert!(RegistryEnum::Second.is_field_registered_as_entity(&world_b));
+    assert!(!RegistryEnum::are_fields_registered_as_entities(
+        World::new()
+    ));
+
+    let entity = world_a.new_entity().add_enum(RegistryEnum::Second);
+    assert!(entity.has_enum(RegistryEnum::Second));
+}
+
+#[test]
+fn component_ids_concurrent_worlds() {
+    let handles: Vec<_> = (0..4)
+        .map(|index| {
+            std::thread::spawn(move || {
+                let world = World::new();
+                for _ in 0..index {
+                    world.new_entity();
+                }
+                if index % 2 == 0 {
+                    world.component::<RegistryB>();
+                }
+                let entity = world.new_entity().set(RegistryA { _value: index });
+                assert!(entity.has::<RegistryA>());
+                assert_eq!(entity.get::<RegistryA>().unwrap()._value, index);
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn component_ids_independent_of_binding_ctx() {
+    let world = World::new();
+    let id = world.component::<RegistryA>().raw_id;
+
+    unsafe { flecs_ecs::sys::ecs_set_binding_ctx(world.raw_world, std::ptr::null_mut(), None) };
+
+    assert_eq!(RegistryA::get_id(&world), id);
+    assert!(world.new_entity().add::<RegistryA>().has::<RegistryA>());
+}
+
+#[test]
+fn component_ids_reused_world_pointer() {
+    // a world created after another one is destroyed may get the same pointer
+    for _ in 0..4 {
+        let world = World::new();
+        assert!(!RegistryB::is_registered_with_world(&world));
+        world.new_entity_named(c"Filler");
+        let id = world.component::<RegistryB>().raw_id;
+        assert_eq!(RegistryB::get_id(&world), id);
+    }
+}
+
+#[test]
+#[should_panic(expected = "is not registered with the world")]
+fn component_id_unchecked_not_registered() {
+    let world = World::new();
+    unsafe { RegistryA::get_id_unchecked(&world) };
+}
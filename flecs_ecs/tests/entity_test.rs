@@ -20,7 +20,7 @@ fn entity_new_named() {
     let world = World::new();
     let entity = world.new_entity_named(c"test");
     assert!(entity.is_valid());
-    assert_eq!(entity.name(), "test");
+    assert_eq!(entity.name(), Ok("test"));
 }
 
 #[test]
@@ -35,7 +35,7 @@ fn entity_new_named_from_scope() {
 
     world.set_scope_with_id(prev);
 
-    assert_eq!(child.name(), "Bar");
+    assert_eq!(child.name(), Ok("Bar"));
     assert_eq!(child.path().unwrap(), "::Foo::Bar");
 }
 
@@ -49,7 +49,7 @@ fn entity_new_nested_named_from_nested_scope() {
 
     // Verify that the entity exists and its name and path are correct
     assert!(entity.is_valid());
-    assert_eq!(entity.name(), "Bar");
+    assert_eq!(entity.name(), Ok("Bar"));
     assert_eq!(entity.path().unwrap(), "::Foo::Bar");
 
     // Set the current scope to `entity`
@@ -65,7 +65,7 @@ fn entity_new_nested_named_from_nested_scope() {
     world.set_scope_with_id(prev);
 
     // Verify the name and hierarchical path of the child entity
-    assert_eq!(child.name(), "World");
+    assert_eq!(child.name(), Ok("World"));
     assert_eq!(child.path().unwrap(), "::Foo::Bar::Hello::World");
 }
 
@@ -627,7 +627,7 @@ fn entity_null_string() {
 
     let entity = world.new_entity();
 
-    assert_eq!(entity.name(), "");
+    assert_eq!(entity.name(), Ok(""));
 }
 
 #[test]
@@ -647,7 +647,7 @@ fn entity_set_name() {
 
     entity.set_name(c"Foo");
 
-    assert_eq!(entity.name(), "Foo");
+    assert_eq!(entity.name(), Ok("Foo"));
 }
 
 #[test]
@@ -658,7 +658,7 @@ fn entity_set_name_optional() {
 
     entity.set_name(c"Foo");
 
-    assert_eq!(entity.name_optional(), Some("Foo"));
+    assert_eq!(entity.name_optional(), Some(Ok("Foo")));
 }
 
 #[test]
@@ -666,13 +666,13 @@ fn entity_change_name() {
     let world = World::new();
 
     let entity = world.new_entity_named(c"Bar");
-    assert_eq!(entity.name(), "Bar");
+    assert_eq!(entity.name(), Ok("Bar"));
 
     entity.set_name(c"Foo");
-    assert_eq!(entity.name(), "Foo");
+    assert_eq!(entity.name(), Ok("Foo"));
 
     entity.set_name(c"Bar");
-    assert_eq!(entity.name(), "Bar");
+    assert_eq!(entity.name(), Ok("Bar"));
 }
 
 #[test]
@@ -1275,8 +1275,8 @@ fn entity_name() {
 
     let entity = world.new_entity_named(c"Foo");
 
-    assert_eq!(entity.name(), "Foo");
-    assert_eq!(entity.name_optional(), Some("Foo"));
+    assert_eq!(entity.name(), Ok("Foo"));
+    assert_eq!(entity.name_optional(), Some(Ok("Foo")));
     assert_eq!(entity.name_cstr(), c"Foo");
     assert_eq!(entity.name_cstr_optional(), Some(c"Foo"));
 }
@@ -1287,7 +1287,7 @@ fn entity_name_empty() {
 
     let entity = world.new_entity();
 
-    assert_eq!(entity.name(), "");
+    assert_eq!(entity.name(), Ok(""));
     assert_eq!(entity.name_optional(), None);
     assert_eq!(entity.name_cstr(), c"");
     assert_eq!(entity.name_cstr_optional(), None);
@@ -1384,7 +1384,7 @@ fn entity_implicit_path_to_char() {
 
     let entity = world.new_entity_named(c"Foo::Bar");
     assert!(entity.is_valid());
-    assert_eq!(entity.name(), "Bar");
+    assert_eq!(entity.name(), Ok("Bar"));
     assert_eq!(entity.path().unwrap(), "::Foo::Bar");
 }
 
@@ -1462,16 +1462,17 @@ fn entity_entity_view_to_entity_world() {
 
 #[test]
 fn entity_entity_view_to_entity_stage() {
-    let world = World::new();
+    let mut world = World::new();
 
     let entity_view: EntityView = world.new_entity().into();
-    let stage = world.stage(0);
 
     world.readonly_begin();
 
+    let stage = world.stage(0);
     let entity_mut = entity_view.mut_current_stage(&stage);
     entity_mut.set(Position { x: 10, y: 20 });
     assert!(!entity_mut.has::<Position>());
+    drop(stage);
 
     world.readonly_end();
 
@@ -1485,11 +1486,10 @@ fn entity_entity_view_to_entity_stage() {
 
 #[test]
 fn entity_create_entity_view_from_stage() {
-    let world = World::new();
-    let stage = world.stage(0);
+    let mut world = World::new();
 
     world.readonly_begin();
-    let entity_view: EntityView = stage.new_entity().into();
+    let entity_view: EntityView = (*world.stage(0).new_entity()).into();
 
     world.readonly_end();
 
@@ -1632,6 +1632,66 @@ fn entity_get_mut_component_w_callback_nested() {
     }));
 }
 
+#[test]
+fn entity_get_defers_structural_changes() {
+    let world = World::new();
+
+    let e = world.new_entity().set(Position { x: 10, y: 20 });
+
+    let p = e.get::<Position>().unwrap();
+    e.set(Velocity { x: 1, y: 2 });
+    let e_2 = world.new_entity().set(Position { x: 11, y: 22 });
+
+    // the component can't move while it is borrowed
+    assert!(!e.has::<Velocity>());
+    assert!(!e_2.has::<Position>());
+    assert_eq!(p.x, 10);
+    assert_eq!(p.y, 20);
+    drop(p);
+
+    assert!(e.has::<Velocity>());
+    assert_eq!(e_2.get::<Position>().unwrap().x, 11);
+}
+
+#[test]
+fn entity_get_mut_guard() {
+    let world = World::new();
+
+    let mut e = world.new_entity().set(Position { x: 10, y: 20 });
+    let id = e.raw_id;
+
+    let mut p = e.get_mut::<Position>();
+    p.x += 1;
+    // structural changes are deferred while the component is borrowed
+    let other = world.get_alive(id);
+    other.add::<Velocity>();
+    assert!(!other.has::<Velocity>());
+    p.y += 2;
+    drop(p);
+
+    assert!(e.has::<Velocity>());
+    let p = e.get::<Position>().unwrap();
+    assert_eq!(p.x, 11);
+    assert_eq!(p.y, 22);
+}
+
+#[test]
+fn entity_get_callback_defers_structural_changes() {
+    let world = World::new();
+
+    let e = world.new_entity().set(Position { x: 10, y: 20 });
+
+    let mut sum = 0;
+    assert!(e.get_callback::<Position>(|p| {
+        e.add::<Velocity>();
+        assert!(!e.has::<Velocity>());
+        sum = p.x + p.y;
+    }));
+
+    assert_eq!(sum, 30);
+    assert!(e.has::<Velocity>());
+}
+
 // TODO set callbacks
 
 #[test]
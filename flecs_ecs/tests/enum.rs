@@ -129,6 +129,6 @@ fn enum_standard_enum_reflection() {
 
     assert_ne!(red, 0);
     assert_ne!(green, 0);
-    assert!(StandardEnum::Red.is_field_registered_as_entity());
+    assert!(StandardEnum::Red.is_field_registered_as_entity(&world));
     assert_eq!(red.path().unwrap(), "::enum::StandardEnum::Red");
 }
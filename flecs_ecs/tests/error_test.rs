@@ -0,0 +1,108 @@
+use flecs_ecs::core::{world::World, FilterBuilderImpl, FlecsErrorCode, IterAPI, QueryBuilder};
+
+mod common;
+use common::*;
+
+#[test]
+fn error_filter_try_build_invalid_expr() {
+    let world = World::new();
+
+    let result = world
+        .filter_builder::<()>()
+        .expr(c"DoesNotExist")
+        .try_build();
+
+    let err = result
+        .err()
+        .expect("filter with unknown identifier should fail");
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+    assert!(err.log().contains("DoesNotExist"));
+    assert!(err.to_string().starts_with("ECS_INVALID_PARAMETER"));
+}
+
+#[test]
+fn error_filter_try_build_valid_expr() {
+    let world = World::new();
+    world.new_entity().set(Position { x: 1, y: 2 });
+
+    let mut filter = world.filter_builder::<(&Position,)>().try_build().unwrap();
+
+    assert_eq!(filter.count(), 1);
+}
+
+#[test]
+fn error_query_try_build_invalid_expr() {
+    let world = World::new();
+
+    let mut builder: QueryBuilder<()> = world.query_builder::<()>();
+    let err = builder.expr(c"Position,,").try_build().err().unwrap();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+    assert!(!err.log().is_empty());
+}
+
+#[test]
+fn error_rule_try_build_invalid_expr() {
+    let world = World::new();
+
+    let err = world
+        .rule_builder::<()>()
+        .expr(c"DoesNotExist($x)")
+        .try_build()
+        .err()
+        .unwrap();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+    assert!(err.log().contains("DoesNotExist"));
+}
+
+#[test]
+fn error_try_lookup_name() {
+    let world = World::new();
+    let entity = world.new_entity_named(c"Foo");
+
+    assert_eq!(world.try_lookup_name(c"Foo", true).unwrap(), entity);
+
+    let err = world.try_lookup_name(c"Bar", true).unwrap_err();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+    assert!(err.message().contains("Bar"));
+}
+
+#[test]
+fn error_try_set_name() {
+    let world = World::new();
+    let parent = world.new_entity();
+    world.new_entity_named(c"Foo");
+
+    let child = world.new_entity().child_of_id(parent);
+    let child = child.try_set_name(c"Foo").unwrap();
+    assert_eq!(child.name(), Ok("Foo"));
+
+    // renaming to the current name is fine
+    child.try_set_name(c"Foo").unwrap();
+
+    let sibling = world.new_entity().child_of_id(parent);
+    let err = sibling.try_set_name(c"Foo").unwrap_err();
+    assert_eq!(err.code(), FlecsErrorCode::AlreadyDefined);
+    assert_eq!(sibling.name(), Ok(""));
+}
+
+#[test]
+fn error_try_set_name_dead_entity() {
+    let world = World::new();
+    let entity = world.new_entity();
+    entity.destruct();
+
+    let err = entity.try_set_name(c"Foo").unwrap_err();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+}
+
+#[test]
+fn error_try_set_pipeline() {
+    let world = World::new();
+    let pipeline = world.get_pipeline();
+    world.try_set_pipeline(pipeline).unwrap();
+
+    let not_a_pipeline = world.new_entity();
+    let err = world.try_set_pipeline(not_a_pipeline).unwrap_err();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+    assert_eq!(world.get_pipeline(), pipeline);
+}
@@ -0,0 +1,217 @@
+use std::{
+    cell::Cell,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use flecs_ecs::core::{
+    flecs::Wildcard, world::World, Builder, ComponentId, FilterBuilderImpl, IterAPI,
+    QueryBuilderImpl,
+};
+use flecs_ecs_derive::Component;
+
+mod common;
+use common::*;
+
+#[derive(Component)]
+struct InCell;
+
+struct CellState {
+    cell: u64,
+    visits: Cell<i32>,
+}
+
+#[test]
+fn group_by_closure() {
+    let world = World::new();
+    world
+        .new_entity()
+        .set(Position { x: 1, y: 0 })
+        .set(Velocity { x: 1, y: 0 });
+    world.new_entity().set(Position { x: 2, y: 0 });
+
+    let fast = 1;
+    let slow = 2;
+    let grouped_by = Arc::new(AtomicU64::new(0));
+    let captured = grouped_by.clone();
+    let query = world
+        .query_builder::<(&Position,)>()
+        .group_by_with::<Position>(move |_world, table, id| {
+            captured.store(id, Ordering::Relaxed);
+            if table.has_type::<Velocity>() {
+                fast
+            } else {
+                slow
+            }
+        })
+        .build();
+
+    let mut groups = Vec::new();
+    query.iter(|it, (pos,)| groups.push((it.group_id(), pos[0].x)));
+    groups.sort();
+    assert_eq!(groups, [(fast, 1), (slow, 2)]);
+    assert_eq!(
+        grouped_by.load(Ordering::Relaxed),
+        Position::get_id(world.raw_world)
+    );
+}
+
+#[test]
+fn group_by_closure_dropped_with_query() {
+    let world = World::new();
+    world.new_entity().set(Position { x: 1, y: 0 });
+
+    let state = Arc::new(());
+    let captured = state.clone();
+    let query = world
+        .query_builder::<(&Position,)>()
+        .group_by_with::<Position>(move |_, _, _| {
+            let _ = &captured;
+            1
+        })
+        .build();
+    assert_eq!(Arc::strong_count(&state), 2);
+
+    drop(query);
+    assert_eq!(Arc::strong_count(&state), 1);
+}
+
+#[test]
+fn group_context_typed() {
+    let world = World::new();
+    let cells: Vec<_> = (0..3).map(|_| world.new_entity()).collect();
+    for cell in &cells {
+        world
+            .new_entity()
+            .set(Position { x: 0, y: 0 })
+            .add_pair_first::<InCell>(*cell);
+    }
+
+    let query = world
+        .query_builder::<(&Position,)>()
+        .group_by::<InCell>()
+        .on_group_create(|_, group_id| CellState {
+            cell: group_id,
+            visits: Cell::new(0),
+        })
+        .build();
+
+    query.iter(|it, _| {
+        let state = query.group_context::<CellState>(it.group_id()).unwrap();
+        state.visits.set(state.visits.get() + 1);
+    });
+
+    for cell in &cells {
+        let state = query.group_context::<CellState>(*cell).unwrap();
+        assert_eq!(state.cell, cell.raw_id);
+        assert_eq!(state.visits.get(), 1);
+    }
+
+    assert!(query.group_context::<i32>(cells[0]).is_none());
+    assert!(query
+        .group_context::<CellState>(world.new_entity())
+        .is_none());
+}
+
+#[test]
+fn group_context_dropped_with_query() {
+    let world = World::new();
+    for _ in 0..2 {
+        let cell = world.new_entity();
+        world
+            .new_entity()
+            .set(Position { x: 0, y: 0 })
+            .add_pair_first::<InCell>(cell);
+    }
+
+    let state = Arc::new(());
+    let captured = state.clone();
+    let query = world
+        .query_builder::<(&Position,)>()
+        .group_by::<InCell>()
+        .on_group_create(move |_, _| captured.clone())
+        .build();
+    // one context per group and one in the closure
+    assert_eq!(Arc::strong_count(&state), 4);
+
+    drop(query);
+    assert_eq!(Arc::strong_count(&state), 1);
+}
+
+#[test]
+fn group_context_on_group_delete() {
+    let world = World::new();
+    let cells: Vec<_> = (0..2).map(|_| world.new_entity()).collect();
+    for cell in &cells {
+        world
+            .new_entity()
+            .set(Position { x: 0, y: 0 })
+            .add_pair_first::<InCell>(*cell);
+    }
+
+    let deleted = Arc::new(Mutex::new(Vec::new()));
+    let captured = deleted.clone();
+    let query = world
+        .query_builder::<(&Position, &(InCell, Wildcard))>()
+        .group_by::<InCell>()
+        .on_group_create(|_, group_id| CellState {
+            cell: group_id,
+            visits: Cell::new(0),
+        })
+        .on_group_delete(move |_, group_id, state: CellState| {
+            assert_eq!(group_id, state.cell);
+            captured.lock().unwrap().push(state.cell);
+        })
+        .build();
+    assert!(deleted.lock().unwrap().is_empty());
+
+    // deleting the cell deletes the tables of its group
+    cells[0].destruct();
+    assert_eq!(*deleted.lock().unwrap(), [cells[0].raw_id]);
+    assert!(query.group_context::<CellState>(cells[0]).is_none());
+    assert!(query.group_context::<CellState>(cells[1]).is_some());
+
+    drop(query);
+    assert_eq!(*deleted.lock().unwrap(), [cells[0].raw_id, cells[1].raw_id]);
+}
+
+#[test]
+fn group_by_closures_dropped_with_builder() {
+    let world = World::new();
+
+    let state = Arc::new(());
+    let group_by_state = state.clone();
+    let create_state = state.clone();
+    let mut builder = world.query_builder::<(&Position,)>();
+    builder
+        .group_by_with::<Position>(move |_, _, _| {
+            let _ = &group_by_state;
+            0
+        })
+        .on_group_create(move |_, _| create_state.clone());
+    assert_eq!(Arc::strong_count(&state), 3);
+
+    drop(builder);
+    assert_eq!(Arc::strong_count(&state), 1);
+}
+
+#[test]
+fn group_by_closures_dropped_after_failed_build() {
+    let world = World::new();
+
+    let state = Arc::new(());
+    let captured = state.clone();
+    let mut builder = world.query_builder::<()>();
+    builder
+        .group_by_with::<Position>(move |_, _, _| {
+            let _ = &captured;
+            0
+        })
+        .expr(c"Position,,");
+    assert!(builder.try_build().is_err());
+
+    drop(builder);
+    assert_eq!(Arc::strong_count(&state), 1);
+}
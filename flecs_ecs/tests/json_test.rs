@@ -0,0 +1,276 @@
+#![cfg(feature = "flecs_json")]
+
+use flecs_ecs::{
+    addons::json::{EntityToJsonDesc, IterToJsonDesc, JsonError, WorldToJsonDesc},
+    core::{world::World, IterAPI},
+    macros::Component,
+};
+
+mod common;
+
+#[derive(Component, Default, Debug, PartialEq)]
+#[meta]
+#[repr(C)]
+struct JsonPosition {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Component, Default, Debug, PartialEq)]
+#[meta]
+#[repr(C)]
+struct JsonHealth {
+    value: i32,
+}
+
+#[test]
+fn json_value_roundtrip() {
+    let world = World::new();
+    let position = JsonPosition { x: 10.0, y: 20.0 };
+
+    let json = world.to_json_value(&position).unwrap();
+    assert_eq!(json, r#"{"x":10, "y":20}"#);
+
+    let value: JsonPosition = world.from_json_value(&json).unwrap();
+    assert_eq!(value, position);
+}
+
+#[test]
+fn json_value_partial() {
+    let world = World::new();
+
+    let value: JsonPosition = world.from_json_value(r#"{"y":5}"#).unwrap();
+    assert_eq!(value, JsonPosition { x: 0.0, y: 5.0 });
+}
+
+#[test]
+fn json_value_invalid() {
+    let world = World::new();
+
+    assert!(matches!(
+        world.from_json_value::<JsonPosition>(r#"{"z":5}"#),
+        Err(JsonError::Parse(err)) if err.log().contains("z")
+    ));
+    assert!(matches!(
+        world.from_json_value::<JsonPosition>("{\0}"),
+        Err(JsonError::InvalidString)
+    ));
+}
+
+#[test]
+fn json_entity_to_json() {
+    let world = World::new();
+    let entity = world
+        .new_entity_named(c"player")
+        .set(JsonPosition { x: 1.0, y: 2.0 })
+        .set(JsonHealth { value: 100 });
+
+    let json = entity.to_json(&EntityToJsonDesc::default()).unwrap();
+    assert!(json.contains(r#""path":"player""#));
+    assert!(json.contains("JsonPosition"));
+    assert!(!json.contains(r#""x":1"#));
+
+    let json = entity
+        .to_json(EntityToJsonDesc::new().serialize_values(true))
+        .unwrap();
+    assert!(json.contains(r#"{"x":1, "y":2}"#));
+    assert!(json.contains(r#"{"value":100}"#));
+
+    let json = entity
+        .to_json(EntityToJsonDesc::new().serialize_path(false))
+        .unwrap();
+    assert!(!json.contains("player"));
+}
+
+#[test]
+fn json_entity_roundtrip() {
+    let world = World::new();
+    let entity = world
+        .new_entity()
+        .set(JsonPosition { x: 1.0, y: 2.0 })
+        .set(JsonHealth { value: 100 });
+
+    let json = entity
+        .to_json(
+            EntityToJsonDesc::new()
+                .serialize_path(false)
+                .serialize_values(true),
+        )
+        .unwrap();
+
+    let copy = world.new_entity().from_json(&json).unwrap();
+    assert_eq!(
+        copy.get::<JsonPosition>().as_deref(),
+        Some(&JsonPosition { x: 1.0, y: 2.0 })
+    );
+    assert_eq!(
+        copy.get::<JsonHealth>().as_deref(),
+        Some(&JsonHealth { value: 100 })
+    );
+
+    assert!(world.new_entity().from_json("{").is_err());
+}
+
+#[test]
+fn json_world_roundtrip() {
+    let world = World::new();
+    world
+        .new_entity_named(c"player")
+        .set(JsonPosition { x: 1.0, y: 2.0 })
+        .set(JsonHealth { value: 100 });
+    world
+        .new_entity_named(c"enemy")
+        .set(JsonPosition { x: 3.0, y: 4.0 });
+
+    let json = world.to_json(None).unwrap();
+    assert!(json.contains("player"));
+    assert!(json.contains("enemy"));
+
+    let loaded = World::new();
+    loaded.component::<JsonPosition>();
+    loaded.component::<JsonHealth>();
+    loaded.from_json(&json).unwrap();
+
+    let player = loaded.lookup_name(c"player", false);
+    assert_eq!(
+        player.get::<JsonPosition>().as_deref(),
+        Some(&JsonPosition { x: 1.0, y: 2.0 })
+    );
+    assert_eq!(
+        player.get::<JsonHealth>().as_deref(),
+        Some(&JsonHealth { value: 100 })
+    );
+    let enemy = loaded.lookup_name(c"enemy", false);
+    assert_eq!(
+        enemy.get::<JsonPosition>().as_deref(),
+        Some(&JsonPosition { x: 3.0, y: 4.0 })
+    );
+}
+
+struct FailingReader;
+
+impl std::io::Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::ErrorKind::BrokenPipe.into())
+    }
+}
+
+#[test]
+fn json_world_writer_reader() {
+    let world = World::new();
+    world
+        .new_entity_named(c"player")
+        .set(JsonHealth { value: 42 });
+
+    let mut buffer = Vec::new();
+    world.to_json_writer(&mut buffer, None).unwrap();
+    assert_eq!(buffer, world.to_json(None).unwrap().into_bytes());
+
+    let loaded = World::new();
+    loaded.component::<JsonHealth>();
+    loaded.from_json_reader(buffer.as_slice()).unwrap();
+    assert_eq!(
+        loaded
+            .lookup_name(c"player", false)
+            .get::<JsonHealth>()
+            .as_deref(),
+        Some(&JsonHealth { value: 42 })
+    );
+
+    assert!(matches!(
+        loaded.from_json_reader(FailingReader),
+        Err(JsonError::Io(_))
+    ));
+    assert!(matches!(
+        loaded.from_json_reader(&b"{\"results\": ["[..]),
+        Err(JsonError::Parse(_))
+    ));
+}
+
+#[test]
+fn json_world_builtin() {
+    let world = World::new();
+
+    let json = world.to_json(None).unwrap();
+    assert!(!json.contains(r#""flecs.core""#));
+
+    let json = world
+        .to_json(Some(
+            WorldToJsonDesc::new()
+                .serialize_builtin(true)
+                .serialize_modules(true),
+        ))
+        .unwrap();
+    assert!(json.contains("flecs.core"));
+}
+
+#[test]
+fn json_query_to_json() {
+    let world = World::new();
+    world
+        .new_entity_named(c"a")
+        .set(JsonPosition { x: 1.0, y: 2.0 });
+    world
+        .new_entity_named(c"b")
+        .set(JsonPosition { x: 3.0, y: 4.0 })
+        .set(JsonHealth { value: 10 });
+
+    let query = world.query::<(&JsonPosition,)>();
+    let json = query.to_json(&IterToJsonDesc::default()).unwrap();
+    assert!(json.contains(r#""entities":["a"]"#));
+    assert!(json.contains(r#""entities":["b"]"#));
+    assert!(json.contains(r#"{"x":1, "y":2}"#));
+    assert!(json.contains(r#"{"x":3, "y":4}"#));
+
+    let filter = world.filter::<(&JsonPosition, &JsonHealth)>();
+    let json = filter
+        .to_json(IterToJsonDesc::new().serialize_values(false))
+        .unwrap();
+    assert!(json.contains(r#""entities":["b"]"#));
+    assert!(!json.contains(r#""a""#));
+    assert!(!json.contains(r#""values""#));
+}
+
+#[test]
+fn json_rule_to_json() {
+    let world = World::new();
+    world.new_entity_named(c"a").set(JsonHealth { value: 5 });
+
+    let rule = world.rule::<(&JsonHealth,)>();
+    let json = rule.to_json(&IterToJsonDesc::default()).unwrap();
+    assert!(json.contains(r#""entities":["a"]"#));
+    assert!(json.contains(r#"{"value":5}"#));
+}
+
+#[test]
+fn json_iter_to_json() {
+    let world = World::new();
+    world
+        .new_entity_named(c"a")
+        .set(JsonPosition { x: 1.0, y: 2.0 });
+    world
+        .new_entity_named(c"b")
+        .set(JsonPosition { x: 3.0, y: 4.0 })
+        .set(JsonHealth { value: 10 });
+
+    let query = world.query::<(&JsonPosition,)>();
+    let mut count = 0;
+    let mut tables = Vec::new();
+    let mut no_results = IterToJsonDesc::default();
+    no_results.dont_serialize_results(true);
+    query.iter(|it, (_position,)| {
+        tables.push(it.to_json(&IterToJsonDesc::default()));
+        assert!(it.to_json(&no_results).contains(r#""results":[]"#));
+        count += it.count();
+    });
+
+    // serializing a result does not advance the iterator
+    assert_eq!(count, 2);
+    assert_eq!(tables.len(), 2);
+    assert!(tables
+        .iter()
+        .any(|json| json.contains(r#""entities":["a"]"#) && !json.contains(r#""b""#)));
+    assert!(tables
+        .iter()
+        .any(|json| json.contains(r#""entities":["b"]"#) && !json.contains(r#""a""#)));
+}
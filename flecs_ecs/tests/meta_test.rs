@@ -0,0 +1,238 @@
+#![cfg(feature = "flecs_meta")]
+
+use std::ffi::CStr;
+
+use flecs_ecs::{
+    addons::meta::MetaCursorError,
+    core::{world::World, ComponentId},
+    macros::Component,
+    sys::{ecs_lookup_child, ecs_os_api, ecs_ptr_to_expr, EcsMember, FLECS_IDEcsMemberID_},
+};
+
+mod common;
+
+#[derive(Component, Default)]
+#[meta]
+#[repr(C)]
+struct MetaPosition {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Component, Default)]
+#[meta]
+struct MetaTransform {
+    scale: f64,
+    layer: u8,
+    position: MetaPosition,
+    flags: [i32; 2],
+}
+
+#[derive(Component, Default)]
+#[meta]
+struct MetaRawNames {
+    r#type: u8,
+    value: u64,
+    r#loop: u16,
+}
+
+fn ptr_to_expr<T: ComponentId>(world: &World, value: &T) -> String {
+    unsafe {
+        let expr = ecs_ptr_to_expr(
+            world.raw_world,
+            T::get_id(world),
+            value as *const T as *const _,
+        );
+        let result = CStr::from_ptr(expr).to_str().unwrap().to_string();
+        ecs_os_api.free_.unwrap()(expr as *mut _);
+        result
+    }
+}
+
+#[test]
+fn meta_struct_members_registered() {
+    let world = World::new();
+    let id = MetaPosition::get_id(&world);
+
+    let member_x = unsafe { ecs_lookup_child(world.raw_world, id, c"x".as_ptr()) };
+    let member_y = unsafe { ecs_lookup_child(world.raw_world, id, c"y".as_ptr()) };
+    assert_ne!(member_x, 0);
+    assert_ne!(member_y, 0);
+
+    let member_y = world.new_entity_from_id(member_y);
+    let member = member_y.get_untyped(unsafe { FLECS_IDEcsMemberID_ }) as *const EcsMember;
+    assert_eq!(unsafe { (*member).offset }, 4);
+}
+
+#[test]
+fn meta_struct_to_expr() {
+    let world = World::new();
+    let position = MetaPosition { x: 10.0, y: 20.0 };
+
+    assert_eq!(ptr_to_expr(&world, &position), "{x: 10, y: 20}");
+}
+
+#[test]
+fn meta_nested_struct_to_expr() {
+    let world = World::new();
+    let transform = MetaTransform {
+        scale: 2.0,
+        layer: 3,
+        position: MetaPosition { x: 1.0, y: 2.0 },
+        flags: [4, 5],
+    };
+
+    assert_eq!(
+        ptr_to_expr(&world, &transform),
+        "{scale: 2, layer: 3, position: {x: 1, y: 2}, flags: [4, 5]}"
+    );
+}
+
+#[test]
+fn meta_struct_members_in_declaration_order() {
+    let world = World::new();
+    let value = MetaRawNames {
+        r#type: 1,
+        value: 2,
+        r#loop: 3,
+    };
+
+    assert_eq!(ptr_to_expr(&world, &value), "{type: 1, value: 2, loop: 3}");
+}
+
+#[test]
+fn meta_struct_members_explicit_offsets() {
+    let world = World::new();
+    let id = MetaRawNames::get_id(&world);
+
+    let members = [
+        (c"type", std::mem::offset_of!(MetaRawNames, r#type)),
+        (c"value", std::mem::offset_of!(MetaRawNames, value)),
+        (c"loop", std::mem::offset_of!(MetaRawNames, r#loop)),
+    ];
+    for (name, offset) in members {
+        let member = unsafe { ecs_lookup_child(world.raw_world, id, name.as_ptr()) };
+        let member = world.new_entity_from_id(member);
+        let member = member.get_untyped(unsafe { FLECS_IDEcsMemberID_ }) as *const EcsMember;
+        assert_eq!(unsafe { (*member).offset }, offset as i32);
+    }
+}
+
+#[test]
+fn meta_cursor_set_members() {
+    let world = World::new();
+    let mut position = MetaPosition::default();
+
+    let mut cursor = world.cursor(&mut position).unwrap();
+    cursor.push().unwrap();
+    cursor.set_float(10.0).unwrap();
+    cursor.next().unwrap();
+    cursor.set_int(20).unwrap();
+    cursor.pop().unwrap();
+    drop(cursor);
+
+    assert_eq!(position.x, 10.0);
+    assert_eq!(position.y, 20.0);
+}
+
+#[test]
+fn meta_cursor_member_by_name() {
+    let world = World::new();
+    let mut position = MetaPosition { x: 1.0, y: 2.0 };
+
+    let mut cursor = world.cursor(&mut position).unwrap();
+    cursor.push().unwrap();
+    cursor.member("y").unwrap();
+    assert_eq!(cursor.get_member(), Some("y"));
+    assert_eq!(cursor.get_float().unwrap(), 2.0);
+    cursor.set(5.0f32).unwrap();
+
+    assert_eq!(
+        cursor.member("z"),
+        Err(MetaCursorError::OperationFailed("member"))
+    );
+    // a failed operation leaves the cursor in place
+    assert_eq!(cursor.get::<f32>().unwrap(), 5.0);
+    drop(cursor);
+
+    assert_eq!(position.y, 5.0);
+}
+
+#[test]
+fn meta_cursor_dotmember() {
+    let world = World::new();
+    let mut transform = MetaTransform::default();
+
+    let mut cursor = world.cursor(&mut transform).unwrap();
+    cursor.push().unwrap();
+    cursor.dotmember("position.y").unwrap();
+    cursor.set_float(3.0).unwrap();
+    cursor.member("layer").unwrap();
+    cursor.set(7u8).unwrap();
+    drop(cursor);
+
+    assert_eq!(transform.position.y, 3.0);
+    assert_eq!(transform.layer, 7);
+}
+
+#[test]
+fn meta_cursor_collection() {
+    let world = World::new();
+    let mut transform = MetaTransform {
+        flags: [1, 2],
+        ..Default::default()
+    };
+
+    let mut cursor = world.cursor(&mut transform).unwrap();
+    cursor.push().unwrap();
+    cursor.member("flags").unwrap();
+    cursor.push().unwrap();
+    assert!(cursor.is_collection());
+    assert_eq!(cursor.elem_count().unwrap(), 2);
+
+    let mut values = Vec::new();
+    cursor
+        .each_elem(|cursor| {
+            values.push(cursor.get::<i32>()?);
+            cursor.set_int(-1)
+        })
+        .unwrap();
+    assert_eq!(values, vec![1, 2]);
+    assert!(cursor.elem(2).is_err());
+    cursor.pop().unwrap();
+    drop(cursor);
+
+    assert_eq!(transform.flags, [-1, -1]);
+}
+
+#[test]
+fn meta_cursor_invalid_conversion() {
+    let world = World::new();
+    let mut transform = MetaTransform {
+        layer: 200,
+        ..Default::default()
+    };
+
+    let mut cursor = world.cursor(&mut transform).unwrap();
+    cursor.push().unwrap();
+    assert_eq!(
+        cursor.get_entity().err(),
+        Some(MetaCursorError::InvalidConversion("entity"))
+    );
+    cursor.member("layer").unwrap();
+    assert_eq!(cursor.get::<i8>(), Err(MetaCursorError::OutOfRange));
+    assert_eq!(cursor.get::<u8>(), Ok(200));
+    cursor.member("position").unwrap();
+    assert!(cursor.get_float().is_err());
+}
+
+#[test]
+fn meta_cursor_type_without_reflection() {
+    let world = World::new();
+    let mut position = common::Position { x: 1, y: 2 };
+
+    assert!(matches!(
+        world.cursor(&mut position),
+        Err(MetaCursorError::InvalidType(_))
+    ));
+}
@@ -0,0 +1,228 @@
+#![cfg(feature = "flecs_module")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use flecs_ecs::{
+    addons::module::Module,
+    core::{flecs, world::World, ComponentId},
+    macros::{Component, Module},
+};
+
+mod common;
+use common::*;
+
+#[derive(Component)]
+struct SimpleModule;
+
+impl Module for SimpleModule {
+    fn module(_world: &World) {}
+}
+
+#[derive(Component)]
+struct PhysicsModule;
+
+impl Module for PhysicsModule {
+    fn module(world: &World) {
+        world.component::<Position>();
+        world.component::<Velocity>();
+        world.new_entity_named(c"Gravity");
+    }
+}
+
+static COUNTED_MODULE_IMPORTS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Component)]
+struct CountedModule;
+
+impl Module for CountedModule {
+    fn module(_world: &World) {
+        COUNTED_MODULE_IMPORTS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Component)]
+struct BaseModule;
+
+impl Module for BaseModule {
+    fn module(_world: &World) {}
+}
+
+#[derive(Component)]
+struct DependentModule;
+
+impl Module for DependentModule {
+    fn module(world: &World) {
+        world.import::<BaseModule>();
+        world.component::<Mass>();
+    }
+}
+
+#[derive(Component)]
+struct ImplicitModule;
+
+impl Module for ImplicitModule {
+    fn module(world: &World) {
+        world.new_entity_named(c"Planet").set(Mass { value: 10 });
+    }
+}
+
+#[derive(Component, Module)]
+struct DerivedBaseModule;
+
+#[derive(Component, Module)]
+#[module(
+    imports(DerivedBaseModule),
+    components(TypeA, MyStruct),
+    init(Self::init)
+)]
+struct DerivedModule;
+
+impl DerivedModule {
+    fn init(world: &World) {
+        world.new_entity_named(c"Spawner");
+    }
+}
+
+#[test]
+fn module_import() {
+    let world = World::new();
+    let module = world.import::<SimpleModule>();
+
+    assert!(module.is_valid());
+    assert!(module.has::<flecs::Module>());
+    assert_eq!(module.raw_id, SimpleModule::get_id(&world));
+    assert_eq!(module.path().unwrap(), "::module_test::SimpleModule");
+}
+
+#[test]
+fn module_components_nested_in_module() {
+    let world = World::new();
+    let module = world.import::<PhysicsModule>();
+
+    let position = world.component::<Position>();
+    assert_eq!(
+        position.path().unwrap(),
+        "::module_test::PhysicsModule::Position"
+    );
+    assert_eq!(position.parent(), module);
+    assert_eq!(
+        world
+            .lookup_name(c"module_test::PhysicsModule::Velocity", false)
+            .raw_id,
+        Velocity::get_id(&world)
+    );
+    assert_eq!(
+        world
+            .lookup_name(c"module_test::PhysicsModule::Gravity", false)
+            .parent(),
+        module
+    );
+}
+
+#[test]
+fn module_import_restores_scope() {
+    let world = World::new();
+    let parent = world.new_entity_named(c"Parent");
+    world.set_scope_with_id(parent);
+
+    world.import::<PhysicsModule>();
+
+    assert_eq!(world.get_scope::<Position>(), parent);
+    world.set_scope_with_id(0);
+    assert_eq!(
+        world.component::<PhysicsModule>().path().unwrap(),
+        "::module_test::PhysicsModule"
+    );
+}
+
+#[test]
+fn module_reimport_is_idempotent() {
+    let world = World::new();
+    let first = world.import::<CountedModule>();
+    let imports = COUNTED_MODULE_IMPORTS.load(Ordering::SeqCst);
+    let second = world.import::<CountedModule>();
+
+    assert_eq!(first, second);
+    assert_eq!(COUNTED_MODULE_IMPORTS.load(Ordering::SeqCst), imports);
+}
+
+#[test]
+fn module_import_dependency() {
+    let world = World::new();
+    let module = world.import::<DependentModule>();
+    let dependency = world.import::<BaseModule>();
+
+    assert!(dependency.has::<flecs::Module>());
+    assert_eq!(dependency.path().unwrap(), "::module_test::BaseModule");
+    assert_eq!(
+        world.component::<Mass>().path().unwrap(),
+        "::module_test::DependentModule::Mass"
+    );
+    assert_eq!(world.component::<Mass>().parent(), module);
+}
+
+#[test]
+fn module_derive() {
+    let world = World::new();
+    let module = world.import::<DerivedModule>();
+
+    assert!(module.has::<flecs::Module>());
+    assert_eq!(module.path().unwrap(), "::module_test::DerivedModule");
+    assert!(world
+        .lookup_name_optional_optional(c"module_test::DerivedBaseModule", false)
+        .is_some());
+    assert_eq!(world.component::<TypeA>().parent(), module);
+    assert_eq!(
+        world.component::<MyStruct>().path().unwrap(),
+        "::module_test::DerivedModule::MyStruct"
+    );
+    assert_eq!(
+        world
+            .lookup_name(c"module_test::DerivedModule::Spawner", false)
+            .parent(),
+        module
+    );
+}
+
+#[test]
+fn module_implicit_components_nested_in_module() {
+    let world = World::new();
+    let module = world.import::<ImplicitModule>();
+
+    assert_eq!(world.component::<Mass>().parent(), module);
+    assert_eq!(
+        world
+            .lookup_name(c"module_test::ImplicitModule::Planet", false)
+            .get::<Mass>()
+            .map(|mass| mass.value),
+        Some(10)
+    );
+}
+
+#[test]
+fn module_scope_outside_import_not_used() {
+    let world = World::new();
+    let parent = world.new_entity_named(c"Parent");
+    world.set_scope_with_id(parent);
+
+    let position = world.component::<Position>();
+
+    world.set_scope_with_id(0);
+    assert_eq!(position.path().unwrap(), "::module_test::common::Position");
+}
+
+#[test]
+fn module_import_ignores_with() {
+    let world = World::new();
+
+    world.with_id(TagA::get_id(&world), || {
+        world.import::<PhysicsModule>();
+    });
+
+    let module = world.component::<PhysicsModule>();
+    assert!(!module.has::<TagA>());
+    assert!(!world.component::<Position>().has::<TagA>());
+    assert!(!world
+        .lookup_name(c"module_test::PhysicsModule::Gravity", false)
+        .has::<TagA>());
+}
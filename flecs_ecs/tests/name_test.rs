@@ -0,0 +1,175 @@
+use std::{borrow::Cow, ffi::CString, rc::Rc};
+
+use flecs_ecs::core::{
+    world::World, Builder, FilterBuilderImpl, FlecsErrorCode, IterAPI, TermBuilder,
+};
+use flecs_ecs_derive::Component;
+
+mod common;
+use common::*;
+
+#[derive(Component)]
+struct Eats;
+
+#[derive(Component)]
+struct Healthy;
+
+#[test]
+fn name_from_str_types() {
+    let world = World::new();
+    let name = String::from("Owned");
+
+    assert_eq!(world.new_entity_named("Literal").name(), Ok("Literal"));
+    assert_eq!(world.new_entity_named(&name).name(), Ok("Owned"));
+    assert_eq!(world.new_entity_named(name.clone()).name(), Ok("Owned"));
+    assert_eq!(
+        world.new_entity_named(Cow::Borrowed("Borrowed")).name(),
+        Ok("Borrowed")
+    );
+    assert_eq!(
+        world.new_entity_named(Rc::<str>::from("Shared")).name(),
+        Ok("Shared")
+    );
+    assert_eq!(world.new_entity_named(c"CStr").name(), Ok("CStr"));
+    assert_eq!(
+        world
+            .new_entity_named(CString::new("CString").unwrap())
+            .name(),
+        Ok("CString")
+    );
+}
+
+#[test]
+fn name_runtime_strings() {
+    let world = World::new();
+    let entities: Vec<_> = (0..10)
+        .map(|i| world.new_entity().set_name(format!("Tile{i}")))
+        .collect();
+
+    for (i, entity) in entities.iter().enumerate() {
+        assert_eq!(world.lookup_name(format!("Tile{i}"), true), *entity);
+    }
+}
+
+#[test]
+fn name_longer_than_stack_buffer() {
+    let world = World::new();
+    let name = "x".repeat(1000);
+    let entity = world.new_entity_named(name.as_str());
+
+    assert_eq!(entity.name(), Ok(name.as_str()));
+    assert_eq!(world.lookup_name(&name, true), entity);
+}
+
+#[test]
+fn name_path_lookup_with_str() {
+    let world = World::new();
+    let parent = world.new_entity_named("Parent");
+    let child = world.new_entity_named("Parent::Child");
+
+    assert_eq!(parent.lookup_name_optional("Child", false), Some(child));
+    assert_eq!(
+        world.lookup_name_optional_optional("Parent::Child", true),
+        Some(child)
+    );
+    assert_eq!(child.path_w_sep(".", "").unwrap(), "Parent.Child");
+}
+
+#[test]
+fn name_try_set_name_with_nul_byte() {
+    let world = World::new();
+    let entity = world.new_entity();
+
+    let err = entity.try_set_name("Foo\0Bar").err().unwrap();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+    assert_eq!(entity.name_optional(), None);
+
+    let err = world.try_lookup_name("Foo\0Bar", true).err().unwrap();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+}
+
+#[test]
+#[should_panic(expected = "must not contain a nul byte")]
+fn name_set_name_with_nul_byte() {
+    let world = World::new();
+    world.new_entity().set_name("Foo\0Bar");
+}
+
+#[test]
+fn name_owned_copy_outlives_rename() {
+    let world = World::new();
+    let entity = world.new_entity_named("Foo");
+    let name = entity.name().unwrap().to_owned();
+
+    entity.set_name("Bar");
+    assert_eq!(name, "Foo");
+    assert_eq!(entity.name(), Ok("Bar"));
+}
+
+#[test]
+fn name_invalid_utf8() {
+    let world = World::new();
+    let entity = world.new_entity_named(c"Foo\xff");
+
+    assert!(entity.name().is_err());
+    assert!(matches!(entity.name_optional(), Some(Err(_))));
+    assert_eq!(entity.name_cstr(), c"Foo\xff");
+}
+
+#[test]
+fn name_filter_expr_from_string() {
+    let world = World::new();
+    let tag = world.new_entity_named("Walkable");
+    world.new_entity().add_id(tag);
+    world.new_entity().set(Velocity { x: 1, y: 2 });
+
+    let expr = String::from("Walkable");
+    let mut builder = world.filter_builder::<()>();
+    builder.expr(&expr);
+    drop(expr);
+
+    assert_eq!(builder.build().count(), 1);
+}
+
+#[test]
+fn name_rule_variables_from_string() {
+    let world = World::new();
+    let apples = world.new_entity_named("Apples").add::<Healthy>();
+    let pizza = world.new_entity_named("Pizza");
+    world
+        .new_entity_named("Bob")
+        .add_pair_first::<Eats>(apples)
+        .add_pair_first::<Eats>(pizza);
+
+    let var = String::from("Food");
+    let rule = world
+        .rule_builder::<()>()
+        .with_pair_name::<Eats>(format!("${var}"))
+        .with_type::<&Healthy>()
+        .select_src_name(format!("${var}"))
+        .build();
+
+    let food_var = rule.find_var(&var);
+    let mut foods = Vec::new();
+    rule.each_iter(|it, _, ()| foods.push(it.get_var(food_var).name().unwrap().to_owned()));
+    assert_eq!(foods, ["Apples"]);
+}
+
+#[test]
+fn name_rule_variables_from_static_cstr() {
+    let world = World::new();
+    let apples = world.new_entity_named("Apples").add::<Healthy>();
+    world.new_entity_named("Bob").add_pair_first::<Eats>(apples);
+
+    let rule = world
+        .rule_builder::<()>()
+        .with_pair_name::<Eats>(c"$Food")
+        .with_type::<&Healthy>()
+        .select_src_name(c"$Food")
+        .build();
+
+    let food_var = rule.find_var(c"Food");
+    let mut foods = Vec::new();
+    rule.each_iter(|it, _, ()| foods.push(it.get_var(food_var)));
+    assert_eq!(foods, [apples]);
+}
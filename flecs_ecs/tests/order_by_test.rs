@@ -0,0 +1,264 @@
+use std::{
+    cmp::Ordering,
+    ffi::c_int,
+    sync::{
+        atomic::{self, AtomicI32},
+        Arc, Mutex,
+    },
+};
+
+use flecs_ecs::core::{
+    world::World, Builder, EntityT, FilterBuilderImpl, IterAPI, Query, QueryBuilderImpl, ReactorAPI,
+};
+
+mod common;
+use common::*;
+
+fn positions(query: &Query<'_, (&Position,)>) -> Vec<i32> {
+    let mut xs = Vec::new();
+    query.each(|(pos,)| xs.push(pos.x));
+    xs
+}
+
+#[test]
+fn order_by_closure() {
+    let world = World::new();
+    for x in [3, 1, 4, 1, 5, 9, 2, 6] {
+        world.new_entity().set(Position { x, y: 0 });
+    }
+
+    let query = world
+        .query_builder::<(&Position,)>()
+        .order_by(|_, a: &Position, _, b: &Position| a.x.cmp(&b.x))
+        .build();
+
+    assert_eq!(positions(&query), [1, 1, 2, 3, 4, 5, 6, 9]);
+}
+
+#[test]
+fn order_by_closure_captures_state() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: -10, y: 0 });
+    for x in [-3, 0, 4, 8] {
+        world.new_entity().set(Position { x, y: 0 });
+    }
+
+    let camera = Arc::new(AtomicI32::new(5));
+    let camera_query = camera.clone();
+    let query = world
+        .query_builder::<(&Position,)>()
+        .order_by(move |_, a: &Position, _, b: &Position| {
+            let camera = camera_query.load(atomic::Ordering::Relaxed);
+            (a.x - camera).abs().cmp(&(b.x - camera).abs())
+        })
+        .build();
+    assert_eq!(positions(&query), [4, 8, 0, -3, -10]);
+
+    // the order is only updated when the sorted component changes
+    camera.store(-4, atomic::Ordering::Relaxed);
+    entity.set(Position { x: -10, y: 0 });
+    assert_eq!(positions(&query), [-3, 0, -10, 4, 8]);
+}
+
+#[test]
+fn order_by_closure_entities() {
+    let world = World::new();
+    let entities: Vec<_> = (0..5)
+        .map(|_| world.new_entity().set(Position { x: 0, y: 0 }))
+        .collect();
+
+    let query = world
+        .query_builder::<(&Position,)>()
+        .order_by(|e1, _: &Position, e2, _: &Position| e2.raw_id.cmp(&e1.raw_id))
+        .build();
+
+    let mut found = Vec::new();
+    query.each_entity(|entity, _| found.push(entity.raw_id));
+    let expected: Vec<_> = entities.iter().rev().map(|entity| entity.raw_id).collect();
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn order_by_closure_dropped_with_query() {
+    let world = World::new();
+    world.new_entity().set(Position { x: 1, y: 0 });
+
+    let state = Arc::new(());
+    let captured = state.clone();
+    let query = world
+        .query_builder::<(&Position,)>()
+        .order_by(move |_, a: &Position, _, b: &Position| {
+            let _ = &captured;
+            a.x.cmp(&b.x)
+        })
+        .build();
+    assert_eq!(Arc::strong_count(&state), 2);
+
+    drop(query);
+    assert_eq!(Arc::strong_count(&state), 1);
+}
+
+#[test]
+fn order_by_closure_dropped_with_builder() {
+    let world = World::new();
+
+    let state = Arc::new(());
+    let captured = state.clone();
+    let mut builder = world.query_builder::<(&Position,)>();
+    builder.order_by(move |_, a: &Position, _, b: &Position| {
+        let _ = &captured;
+        a.x.cmp(&b.x)
+    });
+    assert_eq!(Arc::strong_count(&state), 2);
+
+    drop(builder);
+    assert_eq!(Arc::strong_count(&state), 1);
+}
+
+#[test]
+fn order_by_closure_dropped_after_failed_build() {
+    let world = World::new();
+
+    let state = Arc::new(());
+    let captured = state.clone();
+    let mut builder = world.query_builder::<()>();
+    builder
+        .order_by(move |_, a: &Position, _, b: &Position| {
+            let _ = &captured;
+            a.x.cmp(&b.x)
+        })
+        .expr(c"Position,,");
+    assert!(builder.try_build().is_err());
+    assert_eq!(Arc::strong_count(&state), 2);
+
+    drop(builder);
+    assert_eq!(Arc::strong_count(&state), 1);
+}
+
+#[test]
+fn order_by_closure_system_dropped_with_builder() {
+    let world = World::new();
+
+    let state = Arc::new(());
+    let captured = state.clone();
+    let mut builder = world.system_builder::<(&Position,)>();
+    builder.order_by(move |_, a: &Position, _, b: &Position| {
+        let _ = &captured;
+        a.x.cmp(&b.x)
+    });
+
+    drop(builder);
+    assert_eq!(Arc::strong_count(&state), 1);
+}
+
+#[test]
+fn order_by_closure_replaced() {
+    let world = World::new();
+    for x in [2, 3, 1] {
+        world.new_entity().set(Position { x, y: 0 });
+    }
+
+    let state = Arc::new(());
+    let captured = state.clone();
+    let query = world
+        .query_builder::<(&Position,)>()
+        .order_by(move |_, a: &Position, _, b: &Position| {
+            let _ = &captured;
+            a.x.cmp(&b.x)
+        })
+        .order_by(|_, a: &Position, _, b: &Position| b.x.cmp(&a.x))
+        .build();
+
+    assert_eq!(Arc::strong_count(&state), 1);
+    assert_eq!(positions(&query), [3, 2, 1]);
+}
+
+#[test]
+fn order_by_closure_many_queries() {
+    let world = World::new();
+    for x in [2, 1] {
+        world.new_entity().set(Position { x, y: 0 });
+    }
+
+    for _ in 0..500 {
+        let query = world
+            .query_builder::<(&Position,)>()
+            .order_by(|_, a: &Position, _, b: &Position| a.x.cmp(&b.x))
+            .build();
+        assert_eq!(positions(&query), [1, 2]);
+    }
+}
+
+#[test]
+fn order_by_closure_nested_queries() {
+    let world = World::new();
+    for x in [2, 3, 1] {
+        world.new_entity().set(Position { x, y: 0 });
+        world.new_entity().set(Mass { value: x });
+    }
+
+    let positions = world
+        .query_builder::<(&Position,)>()
+        .order_by(|_, a: &Position, _, b: &Position| a.x.cmp(&b.x))
+        .build();
+
+    let mut outer = Vec::new();
+    let mut inner = Vec::new();
+    positions.each(|(pos,)| {
+        outer.push(pos.x);
+        // the nested query is created and sorted while the outer query is iterated
+        let masses = world
+            .query_builder::<(&Mass,)>()
+            .order_by(|_, a: &Mass, _, b: &Mass| b.value.cmp(&a.value))
+            .build();
+        let mut values = Vec::new();
+        masses.each(|(mass,)| values.push(mass.value));
+        inner.push(values);
+    });
+
+    assert_eq!(outer, [1, 2, 3]);
+    assert_eq!(inner, [[3, 2, 1], [3, 2, 1], [3, 2, 1]]);
+}
+
+extern "C" fn compare_mass(_e1: EntityT, m1: *const Mass, _e2: EntityT, m2: *const Mass) -> c_int {
+    let (m1, m2) = unsafe { (&*m1, &*m2) };
+    match m1.value.cmp(&m2.value) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+#[test]
+fn order_by_fn() {
+    let world = World::new();
+    for value in [3, 1, 2] {
+        world.new_entity().set(Mass { value });
+    }
+
+    let query = world
+        .query_builder::<(&Mass,)>()
+        .order_by_fn(compare_mass)
+        .build();
+
+    let mut values = Vec::new();
+    query.each(|(mass,)| values.push(mass.value));
+    assert_eq!(values, [1, 2, 3]);
+}
+
+#[test]
+fn order_by_fn_system() {
+    let world = World::new();
+    for value in [5, 2, 7] {
+        world.new_entity().set(Mass { value });
+    }
+
+    let values = Arc::new(Mutex::new(Vec::new()));
+    let system_values = values.clone();
+    let system = world
+        .system_builder::<(&Mass,)>()
+        .order_by_fn(compare_mass)
+        .on_each(move |(mass,)| system_values.lock().unwrap().push(mass.value));
+    system.run();
+    assert_eq!(*values.lock().unwrap(), [2, 5, 7]);
+}
@@ -0,0 +1,39 @@
+use std::{
+    ffi::c_char,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use flecs_ecs::{
+    core::{world::World, FilterBuilderImpl},
+    sys::{ecs_log_, ecs_os_get_api, ecs_os_set_api, ecs_os_set_api_defaults},
+};
+
+static LOGGED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn count_log(level: i32, _file: *const c_char, _line: i32, _msg: *const c_char) {
+    if level < 0 {
+        LOGGED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+// the OS API is global, so this is the only test in this file
+#[test]
+fn os_api_custom_log_is_kept() {
+    unsafe {
+        ecs_os_set_api_defaults();
+        let mut api = ecs_os_get_api();
+        api.log_ = Some(count_log);
+        ecs_os_set_api(&mut api);
+    }
+
+    let world = World::new();
+    let result = world
+        .filter_builder::<()>()
+        .expr(c"DoesNotExist")
+        .try_build();
+    assert!(result.is_err());
+    assert_eq!(LOGGED.load(Ordering::SeqCst), 0);
+
+    unsafe { ecs_log_(-3, c"os_api_test.rs".as_ptr(), 1, c"not captured".as_ptr()) };
+    assert_eq!(LOGGED.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,142 @@
+use flecs_ecs::core::{flecs::Wildcard, world::World, IterAPI};
+use flecs_ecs_derive::Component;
+
+mod common;
+use common::*;
+
+#[derive(Component)]
+struct Likes;
+
+#[derive(Component)]
+struct Apples;
+
+#[derive(Component)]
+struct Eats {
+    amount: i32,
+}
+
+#[derive(Component)]
+struct Begin;
+
+#[derive(Component)]
+struct Timestamp {
+    value: i32,
+}
+
+#[test]
+fn pair_query_tag_pair() {
+    let world = World::new();
+    world
+        .new_entity()
+        .set(Position { x: 1, y: 2 })
+        .add::<(Likes, Apples)>();
+    world
+        .new_entity()
+        .set(Position { x: 3, y: 4 })
+        .add::<Likes>();
+
+    let query = world.query::<(&Position, &(Likes, Apples))>();
+    let mut count = 0;
+    query.each(|(pos, _)| {
+        assert_eq!(pos.x, 1);
+        count += 1;
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn pair_query_data_in_first() {
+    let world = World::new();
+    let apples = world.new_entity_named("Apples");
+    let bob = world
+        .new_entity()
+        .set_pair_first_id(Eats { amount: 3 }, apples)
+        .add::<Position>();
+    world
+        .new_entity()
+        .set_pair_first::<Eats, Apples>(Eats { amount: 5 });
+
+    let mut query = world.query::<(&mut (Eats, Apples),)>();
+    query.each(|(eats,)| eats.amount *= 2);
+    assert_eq!(query.count(), 1);
+
+    let filter = world.filter::<(&(Eats, Apples),)>();
+    let mut amounts = Vec::new();
+    filter.each(|(eats,)| amounts.push(eats.amount));
+    assert_eq!(amounts, [10]);
+    assert!(bob.has_pair_first::<Eats>(apples));
+}
+
+#[test]
+fn pair_query_data_in_second() {
+    let world = World::new();
+    world
+        .new_entity()
+        .set_pair_second::<Begin, Timestamp>(Timestamp { value: 42 });
+
+    let query = world.query::<(&(Begin, Timestamp),)>();
+    let mut values = Vec::new();
+    query.each(|(timestamp,)| values.push(timestamp.value));
+    assert_eq!(values, [42]);
+}
+
+#[test]
+fn pair_query_wildcard_target() {
+    let world = World::new();
+    let apples = world.new_entity_named("Apples");
+    let pears = world.new_entity_named("Pears");
+    world
+        .new_entity()
+        .set(Position { x: 0, y: 0 })
+        .set_pair_first_id(Eats { amount: 1 }, apples);
+    world
+        .new_entity()
+        .set(Position { x: 0, y: 0 })
+        .set_pair_first_id(Eats { amount: 2 }, pears);
+
+    let query = world.query::<(&Position, &mut (Eats, Wildcard))>();
+    let mut eaten = Vec::new();
+    query.each_iter(|it, _, (_, eats)| {
+        eats.amount += 10;
+        let target = it.pair(2).unwrap().second();
+        eaten.push((target.name().unwrap().to_owned(), eats.amount));
+    });
+    eaten.sort();
+    assert_eq!(eaten, [("Apples".to_owned(), 11), ("Pears".to_owned(), 12)]);
+}
+
+#[test]
+fn pair_query_optional_pair() {
+    let world = World::new();
+    let apples = world.new_entity_named("Apples");
+    world
+        .new_entity()
+        .set(Mass { value: 1 })
+        .set_pair_first_id(Eats { amount: 4 }, apples);
+    world.new_entity().set(Mass { value: 2 });
+
+    let query = world.query::<(&Mass, Option<&(Eats, Wildcard)>)>();
+    let mut found = Vec::new();
+    query.each(|(mass, eats)| found.push((mass.value, eats.map(|eats| eats.amount))));
+    found.sort();
+    assert_eq!(found, [(1, Some(4)), (2, None)]);
+}
+
+#[test]
+fn pair_query_slices() {
+    let world = World::new();
+    let apples = world.new_entity_named("Apples");
+    for amount in 0..4 {
+        world
+            .new_entity()
+            .set_pair_first_id(Eats { amount }, apples);
+    }
+
+    let query = world.query::<(&(Eats, Wildcard),)>();
+    let mut total = 0;
+    query.iter(|it, (eats,)| {
+        assert_eq!(eats.len(), it.count());
+        total += eats.iter().map(|eats| eats.amount).sum::<i32>();
+    });
+    assert_eq!(total, 6);
+}
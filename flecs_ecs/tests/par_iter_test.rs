@@ -0,0 +1,200 @@
+#![cfg(feature = "flecs_rayon")]
+
+use std::sync::{
+    atomic::{AtomicI64, AtomicUsize, Ordering},
+    Mutex,
+};
+
+use flecs_ecs::core::{
+    flecs::Wildcard, world::World, Builder, FilterBuilderImpl, IterAPI, TermBuilder, PAR_CHUNK_SIZE,
+};
+use flecs_ecs_derive::Component;
+
+mod common;
+use common::*;
+
+const COUNT: i32 = 5000;
+
+#[derive(Component)]
+struct Likes;
+
+fn spawn_moving(world: &World) {
+    for x in 0..COUNT {
+        let entity = world
+            .new_entity()
+            .set(Position { x, y: 0 })
+            .set(Velocity { x: 1, y: 2 });
+        if x % 2 == 0 {
+            entity.set(Mass { value: 1 });
+        }
+    }
+}
+
+#[test]
+fn par_each_writes_components() {
+    let world = World::new();
+    spawn_moving(&world);
+
+    let query = world.query::<(&mut Position, &Velocity)>();
+    query.par_each(|(pos, vel)| {
+        pos.x += vel.x;
+        pos.y += vel.y;
+    });
+
+    let mut xs = Vec::new();
+    let mut ys = 0;
+    query.each(|(pos, _)| {
+        xs.push(pos.x);
+        ys += pos.y;
+    });
+    xs.sort();
+    assert_eq!(xs, (1..=COUNT).collect::<Vec<_>>());
+    assert_eq!(ys, 2 * COUNT);
+}
+
+#[test]
+fn par_each_reads_components() {
+    let world = World::new();
+    spawn_moving(&world);
+
+    let sum = AtomicI64::new(0);
+    world.query::<(&Position,)>().par_each(|(pos,)| {
+        sum.fetch_add(pos.x as i64, Ordering::Relaxed);
+    });
+    assert_eq!(sum.into_inner(), (0..COUNT as i64).sum::<i64>());
+}
+
+#[test]
+fn par_iter_splits_tables() {
+    let world = World::new();
+    spawn_moving(&world);
+
+    let chunks = Mutex::new(Vec::new());
+    world
+        .query::<(&Position, &mut Velocity)>()
+        .par_iter(|(positions, velocities)| {
+            assert_eq!(positions.len(), velocities.len());
+            for (pos, vel) in positions.iter().zip(velocities.iter_mut()) {
+                vel.x = pos.x;
+            }
+            chunks.lock().unwrap().push(positions.len());
+        });
+
+    let chunks = chunks.into_inner().unwrap();
+    assert!(chunks.len() > 2);
+    assert!(chunks.iter().all(|&len| len <= PAR_CHUNK_SIZE));
+    assert_eq!(chunks.iter().sum::<usize>(), COUNT as usize);
+
+    let mut matching = 0;
+    world.query::<(&Position, &Velocity)>().each(|(pos, vel)| {
+        assert_eq!(pos.x, vel.x);
+        matching += 1;
+    });
+    assert_eq!(matching, COUNT);
+}
+
+#[test]
+fn par_each_on_thread_pool() {
+    let world = World::new();
+    spawn_moving(&world);
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .unwrap();
+    let outside_pool = AtomicUsize::new(0);
+    let query = world.query::<(&Position,)>();
+    query.par_each_in(&pool, |_| {
+        if pool.current_thread_index().is_none() {
+            outside_pool.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    assert_eq!(outside_pool.into_inner(), 0);
+}
+
+#[test]
+fn par_each_unlocks_tables() {
+    let world = World::new();
+    let entity = world
+        .new_entity()
+        .set(Position { x: 0, y: 0 })
+        .set(Velocity { x: 0, y: 0 });
+
+    world
+        .query::<(&mut Position,)>()
+        .par_each(|(pos,)| pos.x = 1);
+
+    entity.remove::<Velocity>();
+    assert_eq!(entity.get::<Position>().unwrap().x, 1);
+}
+
+#[test]
+fn par_each_reads_singleton() {
+    let world = World::new();
+    spawn_moving(&world);
+    world.set(Mass { value: 3 });
+
+    let query = world
+        .query_builder::<(&mut Position, &Mass)>()
+        .term_at(2)
+        .singleton()
+        .build();
+    query.par_each(|(pos, mass)| pos.y = mass.value);
+
+    let mut ys = 0;
+    world.query::<(&Position,)>().each(|(pos,)| ys += pos.y);
+    assert_eq!(ys, 3 * COUNT);
+}
+
+#[test]
+#[should_panic(expected = "shared between entities")]
+fn par_each_writes_singleton() {
+    let world = World::new();
+    spawn_moving(&world);
+    world.set(Mass { value: 3 });
+
+    let query = world
+        .query_builder::<(&Position, &mut Mass)>()
+        .term_at(2)
+        .singleton()
+        .build();
+    query.par_each(|(_, mass)| mass.value += 1);
+}
+
+#[test]
+#[should_panic(expected = "matches its entities more than once")]
+fn par_each_writes_with_wildcard_term() {
+    let world = World::new();
+    let apples = world.new_entity();
+    let pears = world.new_entity();
+    world
+        .new_entity()
+        .set(Position { x: 0, y: 0 })
+        .add_pair_first::<Likes>(apples)
+        .add_pair_first::<Likes>(pears);
+
+    let query = world.query::<(&mut Position, &(Likes, Wildcard))>();
+    query.par_each(|(pos, _)| pos.x += 1);
+}
+
+#[test]
+fn par_each_writes_wildcard_pair() {
+    let world = World::new();
+    let small = world.new_entity();
+    let large = world.new_entity();
+    for _ in 0..COUNT {
+        world
+            .new_entity()
+            .set_pair_first_id(Mass { value: 1 }, small)
+            .set_pair_first_id(Mass { value: 10 }, large);
+    }
+
+    let query = world.query::<(&mut (Mass, Wildcard),)>();
+    query.par_each(|(mass,)| mass.value += 1);
+
+    let sum = AtomicI64::new(0);
+    query.par_each(|(mass,)| {
+        sum.fetch_add(mass.value as i64, Ordering::Relaxed);
+    });
+    assert_eq!(sum.into_inner(), 13 * COUNT as i64);
+}
@@ -0,0 +1,198 @@
+use flecs_ecs::core::{world::World, IterAPI, TableView};
+
+mod common;
+use common::*;
+
+fn spawn_positions(world: &World, xs: &[i32]) {
+    for &x in xs {
+        world.new_entity().set(Position { x, y: 0 });
+    }
+}
+
+#[test]
+fn query_iter_entities_adapters() {
+    let world = World::new();
+    spawn_positions(&world, &[1, 2, 3, 4, 5]);
+
+    let mut query = world.query::<(&Position,)>();
+    let mut even: Vec<i32> = query.iter_entities(|iter| {
+        iter.map(|(_, (pos,))| pos.x)
+            .filter(|x| x % 2 == 0)
+            .collect()
+    });
+    even.sort();
+    assert_eq!(even, [2, 4]);
+    assert_eq!(query.iter_entities(|iter| iter.count()), 5);
+}
+
+#[test]
+fn query_iter_entities_matches_each_entity() {
+    let world = World::new();
+    spawn_positions(&world, &[1, 2]);
+    world
+        .new_entity()
+        .set(Position { x: 3, y: 0 })
+        .set(Velocity { x: 0, y: 0 });
+
+    let mut query = world.query::<(&Position,)>();
+    let mut expected = Vec::new();
+    query.each_entity(|entity, (pos,)| expected.push((entity.raw_id, pos.x)));
+
+    let found: Vec<_> = query.iter_entities(|iter| {
+        iter.map(|(entity, (pos,))| (entity.raw_id, pos.x))
+            .collect()
+    });
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn query_iter_entities_early_exit() {
+    let world = World::new();
+    spawn_positions(&world, &[1, 2, 3]);
+    world
+        .new_entity()
+        .set(Position { x: 4, y: 0 })
+        .set(Velocity { x: 0, y: 0 });
+
+    let mut query = world.query::<(&Position,)>();
+    let found = query.iter_entities(|iter| {
+        for (entity, (pos,)) in iter {
+            if pos.x == 2 {
+                return Some(entity);
+            }
+        }
+        None
+    });
+
+    // the tables are unlocked once the closure returns
+    let entity = found.unwrap();
+    entity.set(Velocity { x: 1, y: 1 });
+    assert!(entity.has::<Velocity>());
+
+    let first = query.iter_entities(|mut iter| iter.next().map(|(_, (pos,))| pos.x));
+    assert!(first.is_some());
+    assert_eq!(query.iter_entities(|iter| iter.count()), 4);
+}
+
+#[test]
+fn query_iter_entities_mutable() {
+    let world = World::new();
+    for x in 0..4 {
+        world
+            .new_entity()
+            .set(Position { x, y: 0 })
+            .set(Velocity { x: 1, y: 2 });
+    }
+
+    let mut query = world.query::<(&mut Position, &Velocity)>();
+    query.iter_entities(|iter| {
+        for (_, (pos, vel)) in iter {
+            pos.x += vel.x;
+            pos.y += vel.y;
+        }
+    });
+
+    let mut positions: Vec<_> = world
+        .query::<(&Position,)>()
+        .iter_entities(|iter| iter.map(|(_, (pos,))| (pos.x, pos.y)).collect());
+    positions.sort();
+    assert_eq!(positions, [(1, 2), (2, 2), (3, 2), (4, 2)]);
+}
+
+#[test]
+fn query_iter_entities_zip() {
+    let world = World::new();
+    for x in 0..3 {
+        world
+            .new_entity()
+            .set(Position { x, y: 0 })
+            .set(Mass { value: x * 10 });
+    }
+
+    let mut positions = world.query::<(&Position,)>();
+    let mut masses = world.query::<(&Mass,)>();
+    positions.iter_entities(|positions| {
+        masses.iter_entities(|masses| {
+            for ((e1, (pos,)), (e2, (mass,))) in positions.zip(masses) {
+                assert_eq!(e1, e2);
+                assert_eq!(pos.x * 10, mass.value);
+            }
+        })
+    });
+}
+
+#[test]
+fn query_iter_tables() {
+    let world = World::new();
+    spawn_positions(&world, &[1, 2]);
+    world
+        .new_entity()
+        .set(Position { x: 3, y: 0 })
+        .set(Velocity { x: 0, y: 0 });
+
+    let mut query = world.query::<(&mut Position,)>();
+    let mut counts = Vec::new();
+    query.iter_tables(|iter| {
+        for view in iter {
+            counts.push(view.count());
+            let entity = view.entity(0);
+            let TableView {
+                entities,
+                components: (positions,),
+                ..
+            } = view;
+
+            assert_eq!(entities.len(), positions.len());
+            assert_eq!(entity.raw_id, entities[0]);
+            for pos in positions {
+                pos.y = pos.x * 2;
+            }
+        }
+    });
+    counts.sort();
+    assert_eq!(counts, [1, 2]);
+
+    let ys: i32 = query.iter_entities(|iter| iter.map(|(_, (pos,))| pos.y).sum());
+    assert_eq!(ys, 12);
+}
+
+#[test]
+fn query_iter_tables_early_exit() {
+    let world = World::new();
+    spawn_positions(&world, &[1]);
+    let entity = world
+        .new_entity()
+        .set(Position { x: 2, y: 0 })
+        .set(Velocity { x: 0, y: 0 });
+
+    let mut query = world.query::<(&Position,)>();
+    assert!(query.iter_tables(|mut iter| iter.any(|view| view.count() == 1)));
+
+    entity.remove::<Velocity>();
+    assert_eq!(query.iter_tables(|iter| iter.count()), 1);
+}
+
+#[test]
+fn filter_and_rule_iter_entities() {
+    let world = World::new();
+    spawn_positions(&world, &[1, 2, 3]);
+
+    let mut filter = world.filter::<(&Position,)>();
+    let total: i32 = filter.iter_entities(|iter| iter.map(|(_, (pos,))| pos.x).sum());
+    assert_eq!(total, 6);
+
+    let mut rule = world.rule::<(&Position,)>();
+    let max = rule.iter_entities(|iter| iter.map(|(_, (pos,))| pos.x).max());
+    assert_eq!(max, Some(3));
+}
+
+#[test]
+fn rule_builder_populates_terms_once() {
+    let world = World::new();
+    spawn_positions(&world, &[1]);
+
+    let rule = world.rule::<(&Position,)>();
+    let mut field_count = 0;
+    rule.iter(|it, _| field_count = it.field_count());
+    assert_eq!(field_count, 1);
+}
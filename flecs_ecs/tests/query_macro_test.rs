@@ -0,0 +1,230 @@
+use flecs_ecs::core::{
+    flecs, world::World, Builder, FilterBuilderImpl, FlecsConstantId, IterAPI, TermBuilder,
+};
+use flecs_ecs_derive::{query, Component};
+
+mod common;
+use common::*;
+
+#[derive(Component)]
+struct Likes;
+
+#[derive(Component)]
+struct Apples;
+
+#[derive(Component)]
+struct Healthy;
+
+#[test]
+fn query_macro_data_terms() {
+    let world = World::new();
+    world
+        .new_entity()
+        .set(Position { x: 1, y: 0 })
+        .set(Velocity { x: 1, y: 2 });
+    world
+        .new_entity()
+        .set(Position { x: 2, y: 0 })
+        .set(Velocity { x: 1, y: 2 })
+        .add::<TagA>();
+    world.new_entity().set(Position { x: 3, y: 0 });
+
+    let query = query!(world, &mut Position, &Velocity, !TagA).build();
+    let mut count = 0;
+    query.each(|(pos, vel)| {
+        pos.x += vel.x;
+        pos.y += vel.y;
+        count += 1;
+    });
+    assert_eq!(count, 1);
+
+    let mut positions: Vec<_> = world
+        .query::<(&Position,)>()
+        .iter_entities(|iter| iter.map(|(_, (pos,))| (pos.x, pos.y)).collect());
+    positions.sort();
+    assert_eq!(positions, [(2, 0), (2, 2), (3, 0)]);
+}
+
+#[test]
+fn query_macro_optional_and_or() {
+    let world = World::new();
+    world
+        .new_entity()
+        .set(Position { x: 1, y: 0 })
+        .add::<TagA>();
+    world
+        .new_entity()
+        .set(Position { x: 2, y: 0 })
+        .set(Velocity { x: 5, y: 0 })
+        .add::<TagB>();
+    world
+        .new_entity()
+        .set(Position { x: 3, y: 0 })
+        .add::<TagC>();
+
+    let query = query!(world, &Position, ?&Velocity, TagA || TagB).build();
+    let mut found = Vec::new();
+    query.each(|(pos, vel)| found.push((pos.x, vel.map(|vel| vel.x))));
+    found.sort();
+    assert_eq!(found, [(1, None), (2, Some(5))]);
+}
+
+#[test]
+fn query_macro_pairs() {
+    let world = World::new();
+    let pears = world.new_entity();
+    world
+        .new_entity()
+        .set(Position { x: 1, y: 0 })
+        .add::<(Likes, Apples)>();
+    world
+        .new_entity()
+        .set(Position { x: 2, y: 0 })
+        .add_pair_first::<Likes>(pears);
+    world.new_entity().set(Position { x: 3, y: 0 });
+
+    let mut found = Vec::new();
+    query!(world, &Position, (Likes, *))
+        .build()
+        .each(|(pos,)| found.push(pos.x));
+    found.sort();
+    assert_eq!(found, [1, 2]);
+
+    let mut found = Vec::new();
+    query!(world, &Position, &(Likes, Apples))
+        .build()
+        .each(|(pos, _)| found.push(pos.x));
+    assert_eq!(found, [1]);
+}
+
+#[test]
+fn query_macro_traversal() {
+    let world = World::new();
+    let root = world.new_entity().set(Mass { value: 1 });
+    let child = world
+        .new_entity()
+        .child_of_id(root)
+        .set(Mass { value: 2 })
+        .set(Position { x: 0, y: 0 });
+    world
+        .new_entity()
+        .child_of_id(child)
+        .set(Position { x: 0, y: 0 });
+    world.new_entity().set(Position { x: 0, y: 0 });
+
+    let query = query!(world, &mut Position, &Mass(up(flecs::ChildOf))).build();
+    query.each(|(pos, mass)| pos.x = mass.value);
+
+    let mut xs: Vec<_> = world
+        .query::<(&Position,)>()
+        .iter_entities(|iter| iter.map(|(_, (pos,))| pos.x).collect());
+    xs.sort();
+    assert_eq!(xs, [0, 1, 2]);
+
+    let mut depths = Vec::new();
+    query!(world, &Position, ?&Mass(parent|cascade))
+        .build()
+        .each(|(_, mass)| depths.push(mass.map(|mass| mass.value)));
+    assert_eq!(depths, [None, Some(1), Some(2)]);
+}
+
+#[test]
+fn query_macro_singleton() {
+    let world = World::new();
+    world.set(Mass { value: 3 });
+    world.new_entity().set(Position { x: 0, y: 0 });
+
+    let query = query!(world, &mut Position, &Mass($)).build();
+    query.each(|(pos, mass)| pos.y = mass.value);
+    query.each(|(pos, _)| assert_eq!(pos.y, 3));
+}
+
+#[test]
+fn query_macro_rule_variables() {
+    let world = World::new();
+    let apples = world.new_entity_named(c"Apples").add::<Healthy>();
+    let pizza = world.new_entity_named(c"Pizza");
+    let bob = world
+        .new_entity_named(c"Bob")
+        .set(Position { x: 1, y: 0 })
+        .add_pair_first::<Likes>(apples)
+        .add_pair_first::<Likes>(pizza);
+    world
+        .new_entity_named(c"Alice")
+        .set(Position { x: 2, y: 0 })
+        .add_pair_first::<Likes>(pizza);
+
+    let rule = query!(world, &Position, (Likes, $food), Healthy($food)).build();
+    let food_var = rule.find_var(c"food");
+
+    let mut found = Vec::new();
+    rule.each_iter(|it, index, (pos,)| {
+        found.push((it.entity(index), pos.x, it.get_var(food_var)));
+    });
+    assert_eq!(found, [(bob, 1, apples)]);
+
+    // predicate notation of the same pair
+    let rule = query!(world, Likes($this, $food), Healthy($food)).build();
+    assert_eq!(rule.iterable().count(), 1);
+}
+
+#[test]
+fn query_macro_rule_variable_source() {
+    let world = World::new();
+    let parent = world.new_entity().set(Mass { value: 4 });
+    world
+        .new_entity()
+        .child_of_id(parent)
+        .set(Position { x: 0, y: 0 });
+    world
+        .new_entity()
+        .child_of_id(world.new_entity())
+        .set(Position { x: 0, y: 0 });
+
+    let rule = query!(world, &mut Position, &Mass($parent), (flecs::ChildOf, $parent)).build();
+    let mut count = 0;
+    rule.each(|(pos, mass)| {
+        pos.x = mass.value;
+        count += 1;
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn query_macro_matches_builder() {
+    let world = World::new();
+
+    let from_macro = query!(
+        world,
+        [inout] &mut Position,
+        [in] &Velocity(self|up(flecs::ChildOf)),
+        [out] Mass(),
+        [none] (Likes, _),
+    )
+    .build();
+
+    let from_builder = world
+        .query_builder::<(&mut Position, &Velocity)>()
+        .term_at(1)
+        .inout()
+        .term_at(2)
+        .self_term()
+        .up_type::<flecs::ChildOf>()
+        .in_()
+        .write_type::<&Mass>()
+        .term_with_pair_first::<Likes>(flecs::Any::ID)
+        .inout_none()
+        .build();
+
+    assert_eq!(from_macro.to_string(), from_builder.to_string());
+}
+
+#[test]
+fn query_macro_data_terms_first() {
+    let world = World::new();
+
+    let filter_first = query!(world, TagA, &Position, !TagB, &Velocity).build();
+    let data_first = query!(world, &Position, &Velocity, TagA, !TagB).build();
+
+    assert_eq!(filter_first.to_string(), data_first.to_string());
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "flecs_snapshot")]
+
+use flecs_ecs::core::{world::World, FlecsErrorCode};
+
+mod common;
+use common::*;
+
+#[test]
+#[ignore = "flecs asserts on the number of relationship targets in the snapshot tables when the
+world is destroyed, see the limitations of `Snapshot`"]
+fn snapshot_restore() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: 10, y: 20 });
+    world.component::<Velocity>();
+
+    let snapshot = world.snapshot();
+
+    entity.set(Position { x: 30, y: 40 }).add::<Velocity>();
+    let created = world.new_entity().add::<Position>();
+
+    snapshot.restore();
+
+    let position = entity.get::<Position>().unwrap();
+    assert_eq!((position.x, position.y), (10, 20));
+    assert!(!entity.has::<Velocity>());
+    assert!(!created.is_alive());
+}
+
+#[test]
+#[ignore = "flecs asserts on the number of relationship targets in the snapshot tables when the
+world is destroyed, see the limitations of `Snapshot`"]
+fn snapshot_drop_without_restore() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: 10, y: 20 });
+
+    {
+        let _snapshot = world.snapshot();
+        entity.set(Position { x: 30, y: 40 });
+    }
+
+    let position = entity.get::<Position>().unwrap();
+    assert_eq!((position.x, position.y), (30, 40));
+}
+
+#[test]
+fn snapshot_filtered_restore() {
+    let world = World::new();
+    let with_position = world.new_entity().set(Position { x: 10, y: 20 });
+    let with_velocity = world.new_entity().set(Velocity { x: 1, y: 2 });
+
+    let filter = world.filter::<(&Position,)>();
+    let snapshot = world.snapshot_filtered(&filter);
+
+    with_position.set(Position { x: 30, y: 40 });
+    with_velocity.set(Velocity { x: 3, y: 4 });
+
+    snapshot.restore();
+
+    let position = with_position.get::<Position>().unwrap();
+    assert_eq!((position.x, position.y), (10, 20));
+    let velocity = with_velocity.get::<Velocity>().unwrap();
+    assert_eq!((velocity.x, velocity.y), (3, 4));
+}
+
+#[test]
+fn snapshot_iter() {
+    let world = World::new();
+    let first = world.new_entity().set(Position { x: 10, y: 20 });
+    let second = world.new_entity().set(Position { x: 30, y: 40 });
+
+    let filter = world.filter::<(&Position,)>();
+    let snapshot = world.snapshot_filtered(&filter);
+
+    let mut tables = 0;
+    let mut entities = Vec::new();
+    snapshot.iter(|it| {
+        tables += 1;
+        assert!(it.table().has_type::<Position>());
+        for row in 0..it.count() {
+            entities.push(it.entity(row));
+        }
+    });
+
+    assert_eq!(tables, 1);
+    assert_eq!(entities, vec![first, second]);
+}
+
+#[test]
+#[should_panic(expected = "snapshots do not support union relationships or toggled components")]
+fn snapshot_toggled_component() {
+    let world = World::new();
+    world.new_entity().add::<Position>().enable::<Position>();
+
+    world.snapshot();
+}
+
+#[test]
+fn snapshot_try_new_toggled_component() {
+    let world = World::new();
+    let entity = world.new_entity().add::<Position>().enable::<Position>();
+
+    let err = world.try_snapshot().err().unwrap();
+    assert_eq!(err.code(), FlecsErrorCode::Unsupported);
+
+    // the world is left untouched
+    entity.disable::<Position>();
+    assert!(!entity.is_enabled::<Position>());
+}
+
+#[test]
+fn snapshot_try_new_filtered_skips_unmatched_toggles() {
+    let world = World::new();
+    world.new_entity().add::<Position>().enable::<Position>();
+    let entity = world.new_entity().set(Velocity { x: 1, y: 2 });
+
+    let velocities = world.filter::<(&Velocity,)>();
+    let snapshot = world.try_snapshot_filtered(&velocities).unwrap();
+    entity.set(Velocity { x: 3, y: 4 });
+    snapshot.restore();
+    assert_eq!(entity.get::<Velocity>().unwrap().x, 1);
+
+    let positions = world.filter::<(&Position,)>();
+    assert!(world.try_snapshot_filtered(&positions).is_err());
+}
@@ -0,0 +1,134 @@
+use flecs_ecs::core::{world::World, Stage, StageEntity};
+
+mod common;
+use common::*;
+
+fn assert_send<T: Send>() {}
+
+#[test]
+fn stage_is_send() {
+    assert_send::<Stage>();
+}
+
+#[test]
+fn stage_entities_from_worker_threads() {
+    let mut world = World::new();
+    world.component::<Velocity>();
+    world.set_stage_count(2);
+    let entities: Vec<_> = (0..20)
+        .map(|_| world.new_entity().set(Position { x: 0, y: 0 }).raw_id)
+        .collect();
+
+    world.readonly_begin();
+    std::thread::scope(|scope| {
+        for ((id, chunk), stage) in entities.chunks(10).enumerate().zip(world.stages()) {
+            scope.spawn(move || {
+                assert_eq!(stage.id(), id as i32);
+                assert!(stage.is_deferred());
+                for &entity in chunk {
+                    stage.entity_from_id(entity).set(Velocity {
+                        x: entity as i32,
+                        y: 1,
+                    });
+                }
+            });
+        }
+    });
+    world.readonly_end();
+
+    for &entity in &entities {
+        let entity = world.get_alive(entity);
+        assert_eq!(entity.get::<Velocity>().unwrap().x, entity.raw_id as i32);
+    }
+}
+
+#[test]
+fn stage_async_merge() {
+    let mut world = World::new();
+    world.component::<Position>();
+    let entity = world.new_entity();
+
+    let mut stage = world.create_async_stage();
+    assert!(stage.is_async());
+    assert!(stage.is_deferred());
+
+    stage.entity_from_id(entity).set(Position { x: 10, y: 20 });
+    assert!(!entity.has::<Position>());
+
+    stage.merge();
+    assert_eq!(entity.get::<Position>().unwrap().x, 10);
+}
+
+#[test]
+fn stage_async_from_worker_thread() {
+    let mut world = World::new();
+    let entity = world.new_entity().set(Position { x: 0, y: 0 });
+    let raw_entity = entity.raw_id;
+
+    let mut stage = std::thread::scope(|scope| {
+        let stage = world.create_async_stage();
+        scope
+            .spawn(move || {
+                stage
+                    .entity_from_id(raw_entity)
+                    .set(Position { x: 1, y: 2 });
+                stage
+            })
+            .join()
+            .unwrap()
+    });
+
+    stage.merge();
+    assert_eq!(entity.get::<Position>().unwrap().x, 1);
+}
+
+#[test]
+fn stage_new_entity_merged_on_readonly_end() {
+    let mut world = World::new();
+    world.component::<Position>();
+
+    world.readonly_begin();
+    let raw_entity = {
+        let stage = world.stage(0);
+        let entity: StageEntity = stage.new_entity();
+        entity.set(Position { x: 5, y: 6 });
+        entity.raw_id
+    };
+    world.readonly_end();
+
+    let entity = world.get_alive(raw_entity);
+    assert_eq!(entity.get::<Position>().unwrap().y, 6);
+}
+
+#[test]
+#[should_panic(expected = "stage operations must be deferred")]
+fn stage_new_entity_outside_readonly() {
+    let mut world = World::new();
+    let stage = world.stage(0);
+    stage.new_entity();
+}
+
+#[test]
+fn stage_world_ref_keeps_world_alive() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: 1, y: 2 });
+    let world_ref = world.to_ref().unwrap();
+    drop(world);
+
+    assert!(world_ref.is_owned());
+    assert!(world_ref.is_alive(entity));
+    assert_eq!(entity.get::<Position>().unwrap().x, 1);
+}
+
+#[test]
+fn stage_world_reset_while_shared() {
+    let mut world = World::new();
+    let entity = world.new_entity().set(Position { x: 1, y: 2 });
+    let world_ref = world.to_ref().unwrap();
+
+    world.reset();
+
+    assert_ne!(world.raw_world, world_ref.raw_world);
+    assert!(world_ref.is_alive(entity));
+    assert_eq!(entity.get::<Position>().unwrap().x, 1);
+}
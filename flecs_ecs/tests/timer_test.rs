@@ -0,0 +1,157 @@
+#![cfg(feature = "flecs_timer")]
+
+use std::{cell::Cell, rc::Rc};
+
+use flecs_ecs::{
+    addons::system::System,
+    core::{world::World, ReactorAPI},
+};
+
+mod common;
+
+fn counting_system(world: &World, count: &Rc<Cell<i32>>) -> System {
+    let count = count.clone();
+    world
+        .system_builder::<()>()
+        .on_iter_only(move |_| count.set(count.get() + 1))
+}
+
+#[test]
+fn timer_interval() {
+    let world = World::new();
+    let timer = world.timer().interval(1.0);
+    assert_eq!(timer.get_interval(), 1.0);
+
+    let count = Rc::new(Cell::new(0));
+    counting_system(&world, &count).set_tick_source(timer);
+
+    for _ in 0..4 {
+        world.progress_time(0.5);
+    }
+    assert_eq!(count.get(), 2);
+}
+
+#[test]
+fn timer_timeout() {
+    let world = World::new();
+    let timer = world.timer().timeout(1.0);
+    assert_eq!(timer.get_timeout(), 1.0);
+
+    let count = Rc::new(Cell::new(0));
+    counting_system(&world, &count).set_tick_source(timer);
+
+    for _ in 0..6 {
+        world.progress_time(0.5);
+    }
+    assert_eq!(count.get(), 1);
+}
+
+#[test]
+fn timer_stop_start_reset() {
+    let world = World::new();
+    let timer = world.timer().interval(1.0);
+
+    let count = Rc::new(Cell::new(0));
+    counting_system(&world, &count).set_tick_source(timer);
+
+    timer.stop();
+    world.progress_time(1.0);
+    assert_eq!(count.get(), 0);
+
+    timer.start();
+    world.progress_time(1.0);
+    assert_eq!(count.get(), 1);
+
+    world.progress_time(0.5);
+    timer.reset();
+    world.progress_time(0.5);
+    assert_eq!(count.get(), 1);
+    world.progress_time(0.5);
+    assert_eq!(count.get(), 2);
+}
+
+#[test]
+fn timer_rate() {
+    let world = World::new();
+    let timer = world.timer().rate(2, 0);
+
+    let count = Rc::new(Cell::new(0));
+    counting_system(&world, &count).set_tick_source(timer);
+
+    for _ in 0..4 {
+        world.progress_time(0.5);
+    }
+    assert_eq!(count.get(), 2);
+}
+
+#[test]
+fn timer_rate_of_timer() {
+    let world = World::new();
+    let timer = world.timer().interval(1.0);
+    let filter = world.timer().rate(2, timer);
+
+    let count = Rc::new(Cell::new(0));
+    world
+        .system_builder::<()>()
+        .tick_source_id(filter)
+        .on_iter_only({
+            let count = count.clone();
+            move |_| count.set(count.get() + 1)
+        });
+
+    for _ in 0..4 {
+        world.progress_time(1.0);
+    }
+    assert_eq!(count.get(), 2);
+}
+
+#[test]
+fn system_interval() {
+    let world = World::new();
+    let count = Rc::new(Cell::new(0));
+    let system = counting_system(&world, &count);
+
+    system.set_interval(1.0);
+    assert_eq!(system.get_interval(), 1.0);
+
+    for _ in 0..4 {
+        world.progress_time(0.5);
+    }
+    assert_eq!(count.get(), 2);
+
+    system.stop();
+    world.progress_time(1.0);
+    assert_eq!(count.get(), 2);
+
+    system.start();
+    world.progress_time(1.0);
+    assert_eq!(count.get(), 3);
+}
+
+#[test]
+fn system_timeout() {
+    let world = World::new();
+    let count = Rc::new(Cell::new(0));
+    let system = counting_system(&world, &count);
+
+    system.set_timeout(1.0);
+    assert_eq!(system.get_timeout(), 1.0);
+
+    for _ in 0..4 {
+        world.progress_time(0.5);
+    }
+    assert_eq!(count.get(), 1);
+}
+
+#[test]
+fn system_rate() {
+    let world = World::new();
+    let count = Rc::new(Cell::new(0));
+    let system = counting_system(&world, &count);
+
+    system.set_rate(3, 0);
+    for _ in 0..6 {
+        world.progress_time(0.5);
+    }
+    assert_eq!(count.get(), 2);
+}
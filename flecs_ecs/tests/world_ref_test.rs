@@ -0,0 +1,101 @@
+use flecs_ecs::core::world::World;
+
+mod common;
+use common::*;
+
+#[test]
+fn world_ref_keeps_world_alive() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: 1, y: 2 });
+
+    let world_ref = world.to_ref().unwrap();
+    let weak = world.downgrade();
+    drop(world);
+
+    assert!(weak.is_alive());
+    assert!(world_ref.is_alive(entity));
+    assert_eq!(entity.get::<Position>().unwrap().x, 1);
+
+    drop(world_ref);
+    assert!(!weak.is_alive());
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn world_ref_weak_upgrade() {
+    let world = World::new();
+    let entity = world.new_entity();
+
+    let weak = world.downgrade();
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(upgraded.raw_world, world.raw_world);
+    assert!(upgraded.is_alive(entity));
+
+    drop(upgraded);
+    drop(world);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn world_ref_wrap_raw_world_keeps_world_alive() {
+    let world = World::new();
+    let entity = world.new_entity().set(Position { x: 1, y: 2 });
+    let wrapped = World::new_wrap_raw_world(world.raw_world);
+    drop(world);
+
+    assert!(wrapped.is_owned());
+    assert!(wrapped.is_alive(entity));
+}
+
+#[test]
+fn world_ref_weak_from_borrowed_world() {
+    let raw_world = unsafe { flecs_ecs::sys::ecs_init() };
+    let borrowed = World::new_wrap_raw_world(raw_world);
+
+    assert!(!borrowed.is_owned());
+    assert!(borrowed.to_ref().is_none());
+    assert!(borrowed.downgrade().upgrade().is_none());
+
+    drop(borrowed);
+    unsafe { flecs_ecs::sys::ecs_fini(raw_world) };
+}
+
+#[test]
+fn world_ref_reset_keeps_old_world() {
+    let mut world = World::new();
+    let entity = world.new_entity().set(Position { x: 1, y: 2 });
+    let weak = world.downgrade();
+    let world_ref = world.to_ref().unwrap();
+
+    world.reset();
+
+    let old = weak.upgrade().unwrap();
+    assert_eq!(old.raw_world, world_ref.raw_world);
+    assert_ne!(old.raw_world, world.raw_world);
+    assert_eq!(entity.get::<Position>().unwrap().x, 1);
+
+    drop((old, world_ref));
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn world_ref_dropped_in_any_order() {
+    struct Widget {
+        world: flecs_ecs::core::WorldRef,
+    }
+
+    let world = World::new();
+    let widgets: Vec<_> = (0..3)
+        .map(|_| Widget {
+            world: world.to_ref().unwrap(),
+        })
+        .collect();
+    let weak = widgets[0].world.downgrade();
+
+    drop(world);
+    for widget in widgets {
+        assert!(weak.is_alive());
+        widget.world.new_entity();
+    }
+    assert!(!weak.is_alive());
+}
@@ -4,10 +4,13 @@ use proc_macro::TokenStream as ProcMacroTokenStream;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream},
     parse_macro_input, Data, DeriveInput, Fields, Ident,
 };
 
+mod query;
+
 /// `Component` macro for defining ECS components with optional register attribute when the type is generic over a single T.
 ///
 /// When a type is decorated with `#[derive(Component)]`, several trait implementations are automatically added based on its structure:
@@ -19,6 +22,11 @@ use syn::{
 /// The `register` attribute can be used to handle `ComponentId` implementation trait over a specific T in a generic component with the world.
 /// This attribute is only supported when the type is generic over a single T.
 ///
+/// The `meta` attribute registers the named fields of a (non generic) struct with the meta addon the first time
+/// the component is registered with a world, which enables JSON serialization, expression parsing and the explorer.
+/// Every field type must implement `MetaMemberType`: primitives, fixed size arrays and other components are supported.
+/// Requires the `flecs_meta` feature. At most 32 fields can be registered.
+///
 /// ## Requirements:
 ///
 /// - Types deriving `ComponentId` should also implement `Clone` and `Default` when the Type needs a `Drop`.
@@ -47,6 +55,13 @@ use syn::{
 /// }
 ///
 /// #[derive(Component)]
+/// #[meta] //this will register the members `x` and `y` with the meta addon
+/// struct Velocity {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Component)]
 /// enum State {
 ///     #[default]
 ///     Idle,
@@ -54,11 +69,13 @@ use syn::{
 ///     Jumping,
 /// }
 /// ```
-#[proc_macro_derive(Component, attributes(register))]
+#[proc_macro_derive(Component, attributes(register, meta))]
 pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
     let mut input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
 
+    let has_meta = input.attrs.iter().any(|attr| attr.path().is_ident("meta"));
+
     let is_not_generic = input.generics.params.is_empty();
     let has_more_than_one_generic = input.generics.params.len() > 1;
 
@@ -100,8 +117,11 @@ pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
     let common_traits: TokenStream = {
         match input.data.clone() {
             Data::Struct(data_struct) => {
-                impl_cached_component_data_struct(&data_struct, &mut input)
+                impl_cached_component_data_struct(&data_struct, &mut input, has_meta)
             }
+            Data::Enum(_) if has_meta => quote! {
+                compile_error!("the meta attribute is only supported on structs, enums are reflected automatically");
+            },
             Data::Enum(_) => impl_cached_component_data_enum(&mut input),
             _ => quote! {
                 compile_error!("The type is neither a struct nor an enum!");
@@ -121,11 +141,268 @@ pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
     output.into()
 }
 
+/// `Module` macro for implementing the `Module` trait, which imports the listed dependencies and registers the listed
+/// components when the module is imported with `World::import`.
+///
+/// The module type must also implement `ComponentId`, usually via `#[derive(Component)]`. The module entity is named
+/// after the Rust path of the type and is set as the scope of the world while the module is imported, so the listed
+/// components are nested under the module path.
+///
+/// The `module` attribute accepts the following (all optional) arguments:
+///
+/// - `imports(...)`: modules that are imported before the contents of this module are registered.
+/// - `components(...)`: components that are registered in the scope of the module.
+/// - `init(...)`: a function with the signature `fn(&World)` that is invoked last, in the scope of the module,
+///   to register anything else such as systems or observers.
+///
+/// Requires the `flecs_module` feature.
+///
+/// ## Example:
+///
+#[cfg_attr(doctest, doc = " ````no_test")]
+/// ```ignore
+/// #[derive(Component, Module)]
+/// #[module(imports(TransformModule), components(Position, Velocity), init(Self::init))]
+/// struct PhysicsModule;
+///
+/// impl PhysicsModule {
+///     fn init(world: &World) {
+///         world.system_builder_named::<(&mut Position, &Velocity)>(c"Move");
+///     }
+/// }
+/// ```
+#[proc_macro_derive(Module, attributes(module))]
+pub fn module_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return quote! {
+            compile_error!("the Module derive is not supported on generic types");
+        }
+        .into();
+    }
+
+    let mut module_attrs = ModuleAttributes::default();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("module"))
+    {
+        if let Err(err) = module_attrs.parse_attribute(attr) {
+            return err.to_compile_error().into();
+        }
+    }
+
+    let ModuleAttributes {
+        imports,
+        components,
+        init,
+    } = module_attrs;
+    let init = init.map(|init| quote! { #init(world); });
+
+    let output = quote! {
+        impl flecs_ecs::addons::module::Module for #name {
+            fn module(world: &flecs_ecs::core::world::World) {
+                #(world.import::<#imports>();)*
+                #(world.component::<#components>();)*
+                #init
+            }
+        }
+    };
+
+    output.into()
+}
+
+#[derive(Default)]
+struct ModuleAttributes {
+    imports: Vec<syn::Path>,
+    components: Vec<syn::Path>,
+    init: Option<syn::Path>,
+}
+
+impl ModuleAttributes {
+    fn parse_attribute(&mut self, attr: &syn::Attribute) -> syn::Result<()> {
+        attr.parse_nested_meta(|meta| {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let paths = content.parse_terminated(syn::Path::parse, syn::Token![,])?;
+
+            if meta.path.is_ident("imports") {
+                self.imports.extend(paths);
+            } else if meta.path.is_ident("components") {
+                self.components.extend(paths);
+            } else if meta.path.is_ident("init") {
+                if paths.len() != 1 || self.init.is_some() {
+                    return Err(meta.error("expected a single init function"));
+                }
+                self.init = paths.into_iter().next();
+            } else {
+                return Err(meta.error("expected `imports`, `components` or `init`"));
+            }
+            Ok(())
+        })
+    }
+}
+
+/// `Bundle` macro for implementing the `Bundle` trait on a struct, so its fields are set on an entity in a single
+/// table move with `World::spawn` and `Entity::insert`, or removed together with `Entity::remove_bundle`.
+///
+/// Every field must be a component. Fields marked with the `bundle` attribute are nested bundles instead, whose
+/// components are added together with the components of the struct.
+///
+/// ## Example:
+///
+#[cfg_attr(doctest, doc = " ````no_test")]
+/// ```ignore
+/// #[derive(Bundle)]
+/// struct Particle {
+///     position: Position,
+///     velocity: Velocity,
+/// }
+///
+/// #[derive(Bundle)]
+/// struct Spark {
+///     #[bundle]
+///     particle: Particle,
+///     glow: Glow,
+/// }
+///
+/// world.spawn(Spark { particle, glow });
+/// ```
+#[proc_macro_derive(Bundle, attributes(bundle))]
+pub fn bundle_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Struct(data_struct) = &input.data else {
+        return quote! {
+            compile_error!("the Bundle derive is only supported on structs");
+        }
+        .into();
+    };
+
+    let mut append_ids = Vec::new();
+    let mut write_values = Vec::new();
+    let mut component_ptrs = Vec::new();
+    for (index, field) in data_struct.fields.iter().enumerate() {
+        let ty = &field.ty;
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        };
+
+        if field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("bundle"))
+        {
+            append_ids.push(quote! {
+                <#ty as flecs_ecs::core::Bundle>::append_ids(world, ids);
+            });
+            write_values.push(quote! {
+                flecs_ecs::core::Bundle::write_values(self.#member, world, entity);
+            });
+            component_ptrs.push(quote! {
+                flecs_ecs::core::Bundle::component_ptrs(&mut self.#member, world, ids, data);
+            });
+        } else {
+            append_ids.push(quote! {
+                ids.push(<#ty as flecs_ecs::core::ComponentId>::get_id(world));
+            });
+            write_values.push(quote! {
+                flecs_ecs::core::write_bundle_component(world, entity, self.#member);
+            });
+            component_ptrs.push(quote! {
+                flecs_ecs::core::bundle_component_ptr(world, &mut self.#member, ids, data);
+            });
+        }
+    }
+
+    let output = quote! {
+        impl #impl_generics flecs_ecs::core::Bundle for #name #ty_generics #where_clause {
+            fn append_ids(
+                world: *mut flecs_ecs::core::WorldT,
+                ids: &mut Vec<flecs_ecs::core::IdT>,
+            ) {
+                #(#append_ids)*
+            }
+
+            unsafe fn write_values(
+                self,
+                world: *mut flecs_ecs::core::WorldT,
+                entity: flecs_ecs::core::EntityT,
+            ) {
+                #(#write_values)*
+            }
+
+            fn component_ptrs(
+                &mut self,
+                world: *mut flecs_ecs::core::WorldT,
+                ids: &[flecs_ecs::core::IdT],
+                data: &mut [*mut ::std::ffi::c_void],
+            ) {
+                #(#component_ptrs)*
+            }
+        }
+    };
+
+    output.into()
+}
+
+/// `query!` macro for building a query from the flecs query language, which is parsed at compile time.
+///
+/// The first argument is the world, followed by the terms of the query. Identifiers are Rust types that
+/// implement `ComponentId`. Terms prefixed with `&` or `&mut` access component data and become the elements of
+/// the tuple type of the builder, in order. All other terms only filter the results and are added after them.
+///
+/// The macro expands to a `QueryBuilder`, or to a `RuleBuilder` when the query uses variables other than
+/// `$this`, so more options can be set before calling `build`.
+///
+/// Supported syntax:
+///
+/// - `&Position`, `&mut Position`, `?&Position`: terms with data, `?` makes the tuple element an `Option`.
+/// - `Tag`, `!Tag`, `?Tag`, `TagA || TagB`: terms without data and their operators.
+/// - `(Likes, Apples)`, `(Likes, *)`, `(Likes, _)`, `(Likes, $food)`: pairs, wildcards and variables.
+/// - `Position(up(ChildOf))`, `Position(self|up)`, `Position(parent|cascade)`, `Position($)`, `Position($x)`,
+///   `Position(Game)`, `Position()`: the source of a term and its traversal flags. `$` is the singleton.
+/// - `Likes($x, $y)`: the source and the second element of a pair.
+/// - `[in]`, `[out]`, `[inout]`, `[none]`: the access of a term. `[in]` terms with data must use `&`, while
+///   `[out]` and `[inout]` terms with data must use `&mut`.
+///
+/// ## Term order:
+///
+/// The terms of the query are not kept in source order. The tuple elements must be the first terms of a
+/// query, so the terms with data are added first, followed by the terms without data in the order they
+/// were written. `query!(world, Tag, &Position)` creates the same query as `query!(world, &Position, Tag)`,
+/// and its terms are numbered accordingly in `term_at`, `Iter::field` and the string form of the query.
+/// The terms of an `||` chain never access data, so a chain is always kept together.
+///
+/// ## Example:
+///
+#[cfg_attr(doctest, doc = " ````no_test")]
+/// ```ignore
+/// let query = query!(world, &mut Position, [in] &Velocity, &Position(parent|cascade), !Frozen).build();
+/// query.each(|(pos, vel, parent_pos)| {
+///     pos.x = parent_pos.x + vel.x;
+/// });
+///
+/// let rule = query!(world, (Eats, $food), Healthy($food)).build();
+/// let food = rule.find_var(c"food");
+/// ```
+#[proc_macro]
+pub fn query(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
+    let input = parse_macro_input!(input as query::QueryInput);
+    query::expand(input).into()
+}
+
 // This function generates a series of trait implementations for structs.
 // The implementations depend on the presence or absence of fields in the struct.
 fn impl_cached_component_data_struct(
     data_struct: &syn::DataStruct, // Parsed data structure from the input token stream
     ast: &mut syn::DeriveInput,    // Name of the structure
+    has_meta: bool,                // Whether the members should be registered with the meta addon
 ) -> proc_macro2::TokenStream {
     let is_generic = !ast.generics.params.is_empty();
 
@@ -143,9 +420,21 @@ fn impl_cached_component_data_struct(
     };
 
     let is_tag = if has_fields {
-        quote! { const IS_TAG: bool = false; }
+        quote! {
+            const IS_TAG: bool = false;
+            type TagType = flecs_ecs::core::component_registration::registration_types::FlecsIsNotATag;
+        }
+    } else {
+        quote! {
+            const IS_TAG: bool = true;
+            type TagType = flecs_ecs::core::component_registration::registration_types::FlecsIsATag;
+        }
+    };
+
+    let register_meta = if has_meta {
+        generate_register_meta(name, &data_struct.fields, is_generic)
     } else {
-        quote! { const IS_TAG: bool = true; }
+        quote! {}
     };
 
     let component_info_impl = quote! {
@@ -154,6 +443,8 @@ fn impl_cached_component_data_struct(
             &ONCE_LOCK
         }
 
+        #register_meta
+
         fn __register_lifecycle_hooks(mut type_hooks: &mut flecs_ecs::core::TypeHooksT)  {
             use flecs_ecs::core::component_registration::registration_traits::ComponentInfo;
             const NEEDS_DROP: bool = <#name as flecs_ecs::core::component_registration::registration_traits::ComponentInfo>::NEEDS_DROP;
@@ -216,6 +507,59 @@ fn impl_cached_component_data_struct(
     }
 }
 
+// Maximum number of members of `ecs_struct_desc_t`, `flecs_ecs::addons::meta::MAX_STRUCT_MEMBERS`.
+const MAX_META_MEMBERS: usize = 32;
+
+// Generates the `__register_meta` function which describes the named fields of a struct to the meta addon.
+fn generate_register_meta(name: &Ident, fields: &Fields, is_generic: bool) -> TokenStream {
+    if is_generic {
+        return quote! {
+            compile_error!("the meta attribute is not supported on generic types");
+        };
+    }
+
+    let fields = match fields {
+        Fields::Named(fields) => &fields.named,
+        _ => {
+            return quote! {
+                compile_error!("the meta attribute requires a struct with named fields");
+            };
+        }
+    };
+
+    if fields.len() > MAX_META_MEMBERS {
+        let message = format!(
+            "the meta attribute supports at most {} fields, found {}",
+            MAX_META_MEMBERS,
+            fields.len()
+        );
+        return quote! {
+            compile_error!(#message);
+        };
+    }
+
+    let members = fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_type = &field.ty;
+        // raw identifiers like `r#type` are registered without the `r#` prefix
+        let member_name = syn::LitStr::new(&format!("{}\0", field_name.unraw()), field_name.span());
+        quote! {
+            flecs_ecs::addons::meta::MetaMember::new::<#field_type>(
+                world,
+                unsafe { std::ffi::CStr::from_bytes_with_nul_unchecked(#member_name.as_bytes()) },
+                std::mem::offset_of!(#name, #field_name),
+            )
+        }
+    });
+
+    quote! {
+        fn __register_meta(world: impl flecs_ecs::core::IntoWorld, id: flecs_ecs::core::EntityT) {
+            let world = world.world_ptr_mut();
+            flecs_ecs::addons::meta::struct_init(world, id, &[#(#members),*]);
+        }
+    }
+}
+
 fn generate_variant_constructor(
     variant: &syn::Variant,
     name: &syn::Ident,
@@ -347,11 +691,6 @@ fn impl_cached_component_data_enum(ast: &mut syn::DeriveInput) -> TokenStream {
             }
         }
 
-        fn __enum_data_mut() -> *mut u64 {
-            static mut ENUM_FIELD_ENTITY_ID: [u64; #size_variants as usize] = [0; #size_variants as usize];
-            unsafe { ENUM_FIELD_ENTITY_ID.as_mut_ptr() }
-        }
-
         fn iter() -> Self::VariantIterator {
             vec![#(#variant_constructors),*].into_iter()
         }
@@ -407,6 +746,7 @@ fn impl_cached_component_data_enum(ast: &mut syn::DeriveInput) -> TokenStream {
         impl #impl_generics flecs_ecs::core::component_registration::registration_traits::ComponentInfo for #name #type_generics #where_clause{
             const IS_ENUM: bool = true;
             const IS_TAG: bool = false;
+            type TagType = flecs_ecs::core::component_registration::registration_types::FlecsIsNotATag;
             const IMPLS_CLONE: bool = {
                 use flecs_ecs::core::utility::traits::DoesNotImpl;
                 flecs_ecs::core::utility::types::ImplementsClone::<#name #type_generics>::IMPLS
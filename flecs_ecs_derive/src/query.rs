@@ -0,0 +1,511 @@
+//! Compile time parsing of the flecs query language for the `query!` macro.
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    bracketed,
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Error, Expr, Ident, LitInt, Path, Result, Token,
+};
+
+/// The input of the `query!` macro: the world, followed by the terms of the query.
+pub(crate) struct QueryInput {
+    world: Expr,
+    terms: Vec<Term>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Access {
+    Ref,
+    Mut,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum InOutKind {
+    In,
+    Out,
+    InOut,
+    None,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Oper {
+    And,
+    Or,
+    Not,
+    Optional,
+}
+
+/// The first or second element of a term.
+enum TermId {
+    Type(Path),
+    Var(Ident),
+    Wildcard(Span),
+    Any(Span),
+}
+
+/// The source of a term.
+enum Source {
+    Type(Path),
+    Var(Ident),
+    Singleton,
+    Nothing,
+}
+
+/// The traversal flags of the source of a term.
+enum Trav {
+    SelfTerm,
+    Up(Option<Path>),
+    Cascade(Option<Path>),
+    Parent,
+    Desc,
+}
+
+struct Term {
+    span: Span,
+    inout: Option<(InOutKind, Span)>,
+    oper: Oper,
+    access: Option<Access>,
+    first: TermId,
+    second: Option<TermId>,
+    src: Option<Source>,
+    trav: Vec<Trav>,
+}
+
+impl Parse for QueryInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let world = input.parse()?;
+        let mut terms: Vec<Term> = Vec::new();
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let mut term = input.parse::<Term>()?;
+            while input.peek(Token![||]) {
+                let or = input.parse::<Token![||]>()?;
+                if term.oper != Oper::And {
+                    return Err(Error::new(
+                        or.span(),
+                        "`||` cannot be combined with `!` or `?`",
+                    ));
+                }
+                term.oper = Oper::Or;
+                terms.push(term);
+                term = input.parse()?;
+            }
+            terms.push(term);
+        }
+
+        for (index, term) in terms.iter().enumerate() {
+            let in_or_chain =
+                term.oper == Oper::Or || (index > 0 && terms[index - 1].oper == Oper::Or);
+            if in_or_chain && term.oper != Oper::Or && term.oper != Oper::And {
+                return Err(Error::new(
+                    term.span,
+                    "`||` cannot be combined with `!` or `?`",
+                ));
+            }
+            if in_or_chain && term.access.is_some() {
+                return Err(Error::new(
+                    term.span,
+                    "terms of an `||` chain cannot access data, remove the `&`",
+                ));
+            }
+            term.check_access()?;
+        }
+
+        Ok(Self { world, terms })
+    }
+}
+
+impl Parse for Term {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let span = input.span();
+
+        let inout = if input.peek(syn::token::Bracket) {
+            let content;
+            bracketed!(content in input);
+            let ident = Ident::parse_any(&content)?;
+            let inout = match ident.to_string().as_str() {
+                "in" => InOutKind::In,
+                "out" => InOutKind::Out,
+                "inout" => InOutKind::InOut,
+                "none" => InOutKind::None,
+                _ => {
+                    return Err(Error::new(
+                        ident.span(),
+                        "expected `in`, `out`, `inout` or `none`",
+                    ))
+                }
+            };
+            Some((inout, ident.span()))
+        } else {
+            None
+        };
+
+        let oper = if input.parse::<Option<Token![!]>>()?.is_some() {
+            Oper::Not
+        } else if input.parse::<Option<Token![?]>>()?.is_some() {
+            Oper::Optional
+        } else {
+            Oper::And
+        };
+
+        let access = if input.parse::<Option<Token![&]>>()?.is_some() {
+            if input.parse::<Option<Token![mut]>>()?.is_some() {
+                Some(Access::Mut)
+            } else {
+                Some(Access::Ref)
+            }
+        } else {
+            None
+        };
+
+        let (first, mut second) = if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            let first = content.parse()?;
+            content.parse::<Token![,]>()?;
+            let second = content.parse()?;
+            (first, Some(second))
+        } else {
+            (input.parse()?, None)
+        };
+
+        let mut src = None;
+        let mut trav = Vec::new();
+        if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            if content.is_empty() {
+                src = Some(Source::Nothing);
+            } else {
+                if !peek_trav(&content) {
+                    src = Some(content.parse()?);
+                    if content.parse::<Option<Token![:]>>()?.is_some() {
+                        trav = parse_trav(&content)?;
+                    }
+                } else {
+                    trav = parse_trav(&content)?;
+                }
+
+                if content.parse::<Option<Token![,]>>()?.is_some() {
+                    if second.is_some() {
+                        return Err(content.error("the second element of the pair is already set"));
+                    }
+                    second = Some(content.parse()?);
+                }
+                if !content.is_empty() {
+                    return Err(content.error("expected `,` or `)`"));
+                }
+            }
+        }
+
+        Ok(Self {
+            span,
+            inout,
+            oper,
+            access,
+            first,
+            second,
+            src,
+            trav,
+        })
+    }
+}
+
+impl Parse for TermId {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![$]) {
+            input.parse::<Token![$]>()?;
+            Ok(TermId::Var(Ident::parse_any(input)?))
+        } else if let Some(star) = input.parse::<Option<Token![*]>>()? {
+            Ok(TermId::Wildcard(star.span))
+        } else if let Some(underscore) = input.parse::<Option<Token![_]>>()? {
+            Ok(TermId::Any(underscore.span))
+        } else {
+            Ok(TermId::Type(input.parse()?))
+        }
+    }
+}
+
+impl Parse for Source {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![$]) {
+            input.parse::<Token![$]>()?;
+            if input.peek(Ident::peek_any) {
+                Ok(Source::Var(Ident::parse_any(input)?))
+            } else {
+                Ok(Source::Singleton)
+            }
+        } else if input.peek(LitInt) {
+            let lit = input.parse::<LitInt>()?;
+            if lit.base10_parse::<u64>()? != 0 {
+                return Err(Error::new(
+                    lit.span(),
+                    "expected `0` for a term without source",
+                ));
+            }
+            Ok(Source::Nothing)
+        } else {
+            Ok(Source::Type(input.parse()?))
+        }
+    }
+}
+
+/// Check if the next token is a traversal flag, which can't be parsed as a source.
+fn peek_trav(input: ParseStream) -> bool {
+    input.peek(Token![self])
+        || input.peek(Ident)
+            && matches!(
+                input
+                    .fork()
+                    .parse::<Ident>()
+                    .map(|ident| ident.to_string())
+                    .as_deref(),
+                Ok("up" | "cascade" | "parent" | "desc")
+            )
+}
+
+/// Parse the traversal flags of a source, like `self|up(ChildOf)`.
+fn parse_trav(input: ParseStream) -> Result<Vec<Trav>> {
+    let mut flags = Vec::new();
+    loop {
+        let ident = Ident::parse_any(input)?;
+        let relationship = |input: ParseStream| -> Result<Option<Path>> {
+            if input.peek(syn::token::Paren) {
+                let content;
+                parenthesized!(content in input);
+                Ok(Some(content.parse()?))
+            } else {
+                Ok(None)
+            }
+        };
+
+        flags.push(match ident.to_string().as_str() {
+            "self" => Trav::SelfTerm,
+            "up" => Trav::Up(relationship(input)?),
+            "cascade" => Trav::Cascade(relationship(input)?),
+            "parent" => Trav::Parent,
+            "desc" => Trav::Desc,
+            _ => {
+                return Err(Error::new(
+                    ident.span(),
+                    "expected `self`, `up`, `cascade`, `parent` or `desc`",
+                ))
+            }
+        });
+
+        if input.parse::<Option<Token![|]>>()?.is_none() {
+            return Ok(flags);
+        }
+    }
+}
+
+impl TermId {
+    fn is_var(&self) -> bool {
+        matches!(self, TermId::Var(var) if !is_this(var))
+    }
+
+    /// The Rust type of the element, for terms with data.
+    fn to_type(&self) -> Result<TokenStream> {
+        match self {
+            TermId::Type(path) => Ok(quote! { #path }),
+            TermId::Wildcard(span) => {
+                Ok(quote_spanned! { *span=> flecs_ecs::core::flecs::Wildcard })
+            }
+            TermId::Any(span) => Ok(quote_spanned! { *span=> flecs_ecs::core::flecs::Any }),
+            TermId::Var(var) => Err(Error::new(
+                var.span(),
+                "variables are not supported in terms with data, remove the `&`",
+            )),
+        }
+    }
+
+    fn select(&self, element: &str) -> TokenStream {
+        let select_type = Ident::new(&format!("select_{element}"), Span::call_site());
+        let select_name = Ident::new(&format!("select_{element}_name"), Span::call_site());
+        match self {
+            TermId::Var(var) => {
+                let name = format!("${}", var.unraw());
+                quote! { .#select_name(#name) }
+            }
+            _ => {
+                let ty = self.to_type().unwrap();
+                quote! { .#select_type::<#ty>() }
+            }
+        }
+    }
+}
+
+fn is_this(var: &Ident) -> bool {
+    var == "this" || var == "This"
+}
+
+impl Term {
+    /// Check that the `&`/`&mut` access of a term matches its operator and inout kind.
+    fn check_access(&self) -> Result<()> {
+        let Some(access) = self.access else {
+            return Ok(());
+        };
+
+        if self.oper == Oper::Not {
+            return Err(Error::new(
+                self.span,
+                "`!` terms do not match any data, remove the `&`",
+            ));
+        }
+        if matches!(self.src, Some(Source::Nothing)) {
+            return Err(Error::new(
+                self.span,
+                "terms without a source do not match any data, remove the `&`",
+            ));
+        }
+
+        match (self.inout, access) {
+            (Some((InOutKind::In, span)), Access::Mut) => Err(Error::new(
+                span,
+                "`&mut` access conflicts with `[in]`, use `&` instead",
+            )),
+            (Some((InOutKind::Out | InOutKind::InOut, span)), Access::Ref) => Err(Error::new(
+                span,
+                "`[out]` and `[inout]` terms require `&mut` access",
+            )),
+            (Some((InOutKind::None, span)), _) => Err(Error::new(
+                span,
+                "`[none]` terms do not access any data, remove the `&`",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn uses_vars(&self) -> bool {
+        self.first.is_var()
+            || self.second.as_ref().is_some_and(TermId::is_var)
+            || matches!(&self.src, Some(Source::Var(var)) if !is_this(var))
+    }
+
+    /// The type of the term in the tuple of the query.
+    fn tuple_type(&self) -> Result<TokenStream> {
+        let id = match &self.second {
+            Some(second) => {
+                let first = self.first.to_type()?;
+                let second = second.to_type()?;
+                quote! { (#first, #second) }
+            }
+            None => self.first.to_type()?,
+        };
+        let reference = match self.access {
+            Some(Access::Mut) => quote! { &mut #id },
+            _ => quote! { &#id },
+        };
+        Ok(match self.oper {
+            Oper::Optional => quote! { Option<#reference> },
+            _ => reference,
+        })
+    }
+
+    /// The builder calls that set the source, operator and inout kind of the current term.
+    fn modifiers(&self) -> TokenStream {
+        let src = match &self.src {
+            Some(Source::Type(path)) => quote! { .select_src::<#path>() },
+            Some(Source::Var(var)) => {
+                let name = format!("${}", var.unraw());
+                quote! { .select_src_name(#name) }
+            }
+            Some(Source::Singleton) => quote! { .singleton() },
+            Some(Source::Nothing) => quote! { .setup_src().entity(0) },
+            None => quote! {},
+        };
+
+        // the flags apply to the source, which isn't selected by default for terms without data
+        let setup_src = (!self.trav.is_empty()
+            && matches!(self.src, None | Some(Source::Singleton)))
+        .then(|| quote! { .setup_src() });
+
+        let trav = self.trav.iter().map(|trav| match trav {
+            Trav::SelfTerm => quote! { .self_term() },
+            Trav::Up(Some(rel)) => quote! { .up_type::<#rel>() },
+            Trav::Up(None) => quote! { .up() },
+            Trav::Cascade(Some(rel)) => quote! { .cascade_type::<#rel>() },
+            Trav::Cascade(None) => quote! { .cascade() },
+            Trav::Parent => quote! { .parent() },
+            Trav::Desc => quote! { .desc() },
+        });
+
+        let oper = match self.oper {
+            Oper::And => quote! {},
+            Oper::Or => quote! { .or() },
+            Oper::Not => quote! { .not() },
+            // optional terms with data are `Option`s in the tuple
+            Oper::Optional if self.access.is_some() => quote! {},
+            Oper::Optional => quote! { .optional() },
+        };
+
+        let inout = match self.inout {
+            Some((InOutKind::In, _)) => quote! { .in_() },
+            Some((InOutKind::Out, _)) => quote! { .out() },
+            Some((InOutKind::InOut, _)) => quote! { .inout() },
+            Some((InOutKind::None, _)) => quote! { .inout_none() },
+            None => quote! {},
+        };
+
+        quote! { #src #setup_src #(#trav)* #oper #inout }
+    }
+}
+
+/// Expand the query to the builder calls that create it. Terms with data are the elements of the
+/// tuple type of the builder, in order, and are followed by the terms without data.
+pub(crate) fn expand(input: QueryInput) -> TokenStream {
+    let QueryInput { world, terms } = input;
+
+    let mut types = Vec::new();
+    let mut data_terms = Vec::new();
+    let mut filter_terms = Vec::new();
+    for term in &terms {
+        if term.access.is_some() {
+            match term.tuple_type() {
+                Ok(ty) => types.push(ty),
+                Err(err) => return err.to_compile_error(),
+            }
+            let index = Literal::i32_unsuffixed(types.len() as i32);
+            let modifiers = term.modifiers();
+            data_terms.push(quote_spanned! { term.span=>
+                builder.term_at(#index) #modifiers;
+            });
+        } else {
+            let first = term.first.select("first");
+            let second = term.second.as_ref().map(|second| second.select("second"));
+            let modifiers = term.modifiers();
+            filter_terms.push(quote_spanned! { term.span=>
+                builder.term();
+                builder #first #second #modifiers;
+            });
+        }
+    }
+
+    // variables other than `$this` are only supported by rules
+    let builder = if terms.iter().any(Term::uses_vars) {
+        quote! { rule_builder }
+    } else {
+        quote! { query_builder }
+    };
+
+    quote! {
+        {
+            #[allow(unused_imports)]
+            use flecs_ecs::core::{FilterBuilderImpl as _, TermBuilder as _};
+
+            let mut builder = (#world).#builder::<(#(#types,)*)>();
+            #(#data_terms)*
+            #(#filter_terms)*
+            builder
+        }
+    }
+}
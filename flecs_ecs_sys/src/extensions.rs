@@ -1,8 +1,9 @@
 #[cfg(feature = "flecs_app")]
 use crate::ecs_app_desc_t;
 use crate::{
-    ecs_entity_desc_t, ecs_event_desc_t, ecs_filter_desc_t, ecs_filter_t, ecs_filter_t_magic,
-    ecs_header_t, ecs_iterable_t, ecs_observer_desc_t, ecs_query_desc_t, ecs_term_id_t, ecs_term_t,
+    ecs_bulk_desc_t, ecs_entity_desc_t, ecs_event_desc_t, ecs_filter_desc_t, ecs_filter_t,
+    ecs_filter_t_magic, ecs_header_t, ecs_iterable_t, ecs_member_t, ecs_member_value_range_t,
+    ecs_observer_desc_t, ecs_query_desc_t, ecs_struct_desc_t, ecs_term_id_t, ecs_term_t,
     ecs_type_hooks_t, ecs_type_t, EcsComponent, EcsOpaque, EcsPoly, ECS_FILTER_INIT,
 };
 
@@ -12,6 +13,9 @@ use crate::{ecs_system_desc_t, EcsTickSource};
 #[cfg(feature = "flecs_pipeline")]
 use crate::ecs_pipeline_desc_t;
 
+#[cfg(feature = "flecs_json")]
+use crate::ecs_from_json_desc_t;
+
 impl Default for ecs_type_t {
     fn default() -> Self {
         Self {
@@ -152,6 +156,19 @@ impl Default for ecs_entity_desc_t {
     }
 }
 
+impl Default for ecs_bulk_desc_t {
+    fn default() -> Self {
+        Self {
+            _canary: Default::default(),
+            entities: std::ptr::null_mut(),
+            count: Default::default(),
+            ids: Default::default(),
+            data: std::ptr::null_mut(),
+            table: std::ptr::null_mut(),
+        }
+    }
+}
+
 impl Default for ecs_event_desc_t {
     fn default() -> Self {
         Self {
@@ -220,6 +237,18 @@ impl Default for ecs_app_desc_t {
     }
 }
 
+#[cfg(feature = "flecs_json")]
+impl Default for ecs_from_json_desc_t {
+    fn default() -> Self {
+        Self {
+            name: std::ptr::null(),
+            expr: std::ptr::null(),
+            lookup_action: Default::default(),
+            lookup_ctx: std::ptr::null_mut(),
+        }
+    }
+}
+
 #[allow(clippy::derivable_impls)] // this is generated by bindgen
 impl Default for EcsOpaque {
     fn default() -> Self {
@@ -244,6 +273,43 @@ impl Default for EcsOpaque {
     }
 }
 
+#[allow(clippy::derivable_impls)] // this is generated by bindgen
+impl Default for ecs_member_value_range_t {
+    fn default() -> Self {
+        Self {
+            min: Default::default(),
+            max: Default::default(),
+        }
+    }
+}
+
+impl Default for ecs_member_t {
+    fn default() -> Self {
+        Self {
+            name: std::ptr::null(),
+            type_: Default::default(),
+            count: Default::default(),
+            offset: Default::default(),
+            unit: Default::default(),
+            range: Default::default(),
+            error_range: Default::default(),
+            warning_range: Default::default(),
+            size: Default::default(),
+            member: Default::default(),
+        }
+    }
+}
+
+#[allow(clippy::derivable_impls)] // this is generated by bindgen
+impl Default for ecs_struct_desc_t {
+    fn default() -> Self {
+        Self {
+            entity: Default::default(),
+            members: [Default::default(); 32],
+        }
+    }
+}
+
 #[cfg(feature = "flecs_system")]
 impl Default for EcsTickSource {
     fn default() -> Self {